@@ -0,0 +1,101 @@
+//! Heap allocations per call of `normalize` and `normalize_into`
+//!
+//! Run with `cargo bench --bench alloc`. Prose without digits takes the
+//! passthrough path and needs no FSTs; the corpora in `benches/data/` are
+//! measured too when the FST directory (`fsts/` or `WETEXT_FST_DIR`) exists.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use wetext_rs::{Language, Normalizer, NormalizerConfig};
+
+/// System allocator that counts allocations
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const PROSE: &str = "The quick brown fox jumps over the lazy dog.\n\
+                     Rust makes systems programming safer.\n\
+                     See you at the office tomorrow.";
+
+const CORPORA: &[(Language, &str)] = &[
+    (Language::Zh, include_str!("data/zh.txt")),
+    (Language::En, include_str!("data/en.txt")),
+    (Language::Ja, include_str!("data/ja.txt")),
+];
+
+/// Mean allocations per line of `f`, after a warm-up pass
+fn allocations_per_line<F: FnMut(&str)>(lines: &[&str], mut f: F) -> f64 {
+    lines.iter().for_each(|line| f(line));
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    lines.iter().for_each(|line| f(line));
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) as f64 / lines.len() as f64
+}
+
+fn report(name: &str, normalizer: &mut Normalizer, corpus: &str) {
+    let lines: Vec<&str> = corpus.lines().filter(|l| !l.trim().is_empty()).collect();
+    if let Err(e) = lines
+        .iter()
+        .try_for_each(|l| normalizer.normalize(l).map(drop))
+    {
+        println!("{}: skipped ({})", name, e);
+        return;
+    }
+    let normalize = allocations_per_line(&lines, |l| {
+        std::hint::black_box(normalizer.normalize(l).ok());
+    });
+    let mut buffer = String::new();
+    let into = allocations_per_line(&lines, |l| {
+        buffer.clear();
+        std::hint::black_box(normalizer.normalize_into(&mut buffer, l).ok());
+    });
+    println!(
+        "{}: normalize {:.1}  normalize_into {:.1}  (allocations per line, {} lines)",
+        name,
+        normalize,
+        into,
+        lines.len()
+    );
+}
+
+fn main() {
+    let fst_dir = std::env::var_os("WETEXT_FST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("fsts"));
+
+    let mut normalizer = Normalizer::new(&fst_dir, NormalizerConfig::new());
+    report("prose", &mut normalizer, PROSE);
+
+    if !fst_dir.is_dir() {
+        println!(
+            "FST directory {} not found, skipping corpora",
+            fst_dir.display()
+        );
+        return;
+    }
+    for (lang, corpus) in CORPORA {
+        let config = NormalizerConfig::new().with_lang(*lang);
+        let mut normalizer = Normalizer::new(&fst_dir, config);
+        report(&format!("{:?}", lang), &mut normalizer, corpus);
+    }
+}
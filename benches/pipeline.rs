@@ -0,0 +1,105 @@
+//! Per-stage timings for the normalization pipeline
+//!
+//! Run with `cargo bench`. The FST directory defaults to `fsts/` and can be
+//! overridden with `WETEXT_FST_DIR`; the benchmark is skipped when it is
+//! missing. Each line of the corpora in `benches/data/` is run through the
+//! contractions (English only), tag, reorder and verbalize stages and the
+//! full `normalize` call, and the mean time per line is printed for each
+//! stage. `wetext bench` times the same stages on other text.
+
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use wetext_rs::{Language, Normalizer, NormalizerConfig};
+
+/// Timed passes over each corpus, after one warm-up pass
+const ITERATIONS: u32 = 20;
+
+const CORPORA: &[(Language, &str)] = &[
+    (Language::Zh, include_str!("data/zh.txt")),
+    (Language::En, include_str!("data/en.txt")),
+    (Language::Ja, include_str!("data/ja.txt")),
+];
+
+/// Mean time per call of `f` over every line, after a warm-up pass
+fn time_per_line<F: FnMut(&str)>(lines: &[&str], mut f: F) -> Duration {
+    lines.iter().for_each(|line| f(line));
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        lines.iter().for_each(|line| f(line));
+    }
+    start.elapsed() / (ITERATIONS * lines.len() as u32)
+}
+
+fn bench_language(fst_dir: &Path, lang: Language, corpus: &str) {
+    let lines: Vec<&str> = corpus.lines().filter(|l| !l.trim().is_empty()).collect();
+    let config = NormalizerConfig::new().with_lang(lang);
+    let mut normalizer = Normalizer::new(fst_dir, config);
+
+    let tagged: Vec<String> = match lines.iter().map(|l| normalizer.tag(l)).collect() {
+        Ok(tagged) => tagged,
+        Err(e) => {
+            println!("{:?}: skipped ({})", lang, e);
+            return;
+        }
+    };
+    let reordered: Vec<String> = tagged
+        .iter()
+        .map(|t| normalizer.reorder(t).unwrap_or_default())
+        .collect();
+
+    let mut stages = Vec::new();
+    #[cfg(feature = "contractions")]
+    if lang == Language::En {
+        let contractions = time_per_line(&lines, |l| {
+            black_box(normalizer.fix_contractions(l));
+        });
+        stages.push(("contractions", contractions));
+    }
+    let tag = time_per_line(&lines, |l| {
+        black_box(normalizer.tag(l).ok());
+    });
+    let tagged: Vec<&str> = tagged.iter().map(String::as_str).collect();
+    let reorder = time_per_line(&tagged, |t| {
+        black_box(normalizer.reorder(t).ok());
+    });
+    let reordered: Vec<&str> = reordered.iter().map(String::as_str).collect();
+    let verbalize = time_per_line(&reordered, |t| {
+        black_box(normalizer.verbalize(t).ok());
+    });
+    let total = time_per_line(&lines, |l| {
+        black_box(normalizer.normalize(l).ok());
+    });
+
+    stages.extend([
+        ("tag", tag),
+        ("reorder", reorder),
+        ("verbalize", verbalize),
+        ("normalize", total),
+    ]);
+    let stages: Vec<String> = stages
+        .iter()
+        .map(|(stage, time)| format!("{} {:?}", stage, time))
+        .collect();
+    println!(
+        "{:?}: {}  (per line, {} lines)",
+        lang,
+        stages.join("  "),
+        lines.len()
+    );
+}
+
+fn main() {
+    let fst_dir = std::env::var_os("WETEXT_FST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("fsts"));
+    if !fst_dir.is_dir() {
+        println!("FST directory {} not found, skipping", fst_dir.display());
+        return;
+    }
+
+    for (lang, corpus) in CORPORA {
+        bench_language(&fst_dir, *lang, corpus);
+    }
+}
@@ -0,0 +1,144 @@
+//! Reusable input acceptors
+//!
+//! Every FST pass turns its input into a linear acceptor, one state and
+//! one arc per byte. Building it fresh allocates a state vector and an arc
+//! vector per byte on each call. Instead, each thread keeps one acceptor and
+//! relabels it in place: arcs that already carry the right byte are kept,
+//! so a postprocess pass on the text the tagger just read, or a line
+//! sharing a prefix with the previous one, changes few arcs or none.
+//!
+//! States past the end of a shorter input are kept, cut off from the start
+//! state, so the composition never reaches them.
+
+use std::cell::RefCell;
+
+use rustfst::fst_impls::VectorFst;
+use rustfst::fst_properties::FstProperties;
+use rustfst::prelude::*;
+use rustfst::semirings::TropicalWeight;
+use rustfst::utils::acceptor;
+use rustfst::Label;
+
+use crate::error::{Result, WeTextError};
+
+/// Inputs longer than this get a fresh acceptor, so a thread does not keep
+/// a large one alive after a single long input
+const MAX_POOLED_LEN: usize = 1 << 16;
+
+thread_local! {
+    static POOL: RefCell<InputAcceptor> = RefCell::new(InputAcceptor::new());
+}
+
+/// Linear acceptor relabeled in place for each input
+pub(crate) struct InputAcceptor {
+    fst: VectorFst<TropicalWeight>,
+    /// Number of arcs on the path from the start state to the final state
+    len: usize,
+}
+
+impl InputAcceptor {
+    pub(crate) fn new() -> Self {
+        let mut fst = VectorFst::new();
+        let start = fst.add_state();
+        // Can't fail as the state has just been added
+        fst.set_start(start).unwrap();
+        fst.set_final(start, TropicalWeight::one()).unwrap();
+        Self { fst, len: 0 }
+    }
+
+    /// Make the acceptor accept exactly `bytes`
+    pub(crate) fn set(&mut self, bytes: &[u8]) -> Result<&VectorFst<TropicalWeight>> {
+        self.relabel(bytes)
+            .map_err(|e| WeTextError::FstOperationError(format!("acceptor failed: {}", e)))?;
+        Ok(&self.fst)
+    }
+
+    fn relabel(&mut self, bytes: &[u8]) -> anyhow::Result<()> {
+        let fst = &mut self.fst;
+        for (state, &byte) in bytes.iter().enumerate() {
+            let (state, label) = (state as StateId, byte as Label);
+            if fst.num_states() <= state as usize + 1 {
+                fst.add_state();
+            }
+            if fst.num_trs(state)? == 0 {
+                fst.add_tr(
+                    state,
+                    Tr::new(label, label, TropicalWeight::one(), state + 1),
+                )?;
+                continue;
+            }
+            let mut trs = fst.tr_iter_mut(state)?;
+            if trs[0].ilabel != label {
+                trs.set_ilabel(0, label)?;
+                trs.set_olabel(0, label)?;
+            }
+        }
+        let (old, new) = (self.len as StateId, bytes.len() as StateId);
+        if new != old {
+            fst.delete_final_weight(old)?;
+            if new < old {
+                fst.delete_trs(new)?;
+            }
+            fst.set_final(new, TropicalWeight::one())?;
+        }
+        // Relabeling forgets that a single path is sorted, which matchers on
+        // unsorted grammars rely on
+        let sorted = FstProperties::I_LABEL_SORTED | FstProperties::O_LABEL_SORTED;
+        let known = sorted | FstProperties::NOT_I_LABEL_SORTED | FstProperties::NOT_O_LABEL_SORTED;
+        fst.set_properties_with_mask(sorted, known);
+        self.len = bytes.len();
+        Ok(())
+    }
+}
+
+/// Fresh linear acceptor for `input`
+fn fresh(input: &str) -> VectorFst<TropicalWeight> {
+    let labels: Vec<Label> = input.as_bytes().iter().map(|&b| b as Label).collect();
+    acceptor(&labels, TropicalWeight::one())
+}
+
+/// Run `f` with a linear acceptor for `input`
+///
+/// Uses the calling thread's pooled acceptor, or a fresh one when the input
+/// is long or the pooled one is in use.
+pub(crate) fn with_acceptor<T>(
+    input: &str,
+    f: impl FnOnce(&VectorFst<TropicalWeight>) -> Result<T>,
+) -> Result<T> {
+    if input.len() > MAX_POOLED_LEN {
+        return f(&fresh(input));
+    }
+    POOL.with(|pool| match pool.try_borrow_mut() {
+        Ok(mut pool) => f(pool.set(input.as_bytes())?),
+        Err(_) => f(&fresh(input)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relabel_matches_fresh() {
+        let mut pooled = InputAcceptor::new();
+        for input in ["abc", "abd", "", "abcdef", "xy", "xy", "hello world", "h"] {
+            let fst = pooled.set(input.as_bytes()).unwrap();
+            let paths: Vec<_> = fst.paths_iter().collect();
+            assert_eq!(paths.len(), 1, "{:?}", input);
+            let expected: Vec<Label> = input.bytes().map(Label::from).collect();
+            assert_eq!(paths[0].ilabels, expected);
+            assert_eq!(paths[0].olabels, expected);
+            assert!(fst
+                .properties()
+                .contains(FstProperties::I_LABEL_SORTED | FstProperties::O_LABEL_SORTED));
+        }
+    }
+
+    #[test]
+    fn test_compose_with_pooled() {
+        let normalizer = crate::Rule::replace("ab", "x").to_rewriter().unwrap();
+        for (input, expected) in [("zabz", "zxz"), ("ab", "x"), ("abab", "xx"), ("q", "q")] {
+            assert_eq!(normalizer.normalize(input).unwrap(), expected);
+        }
+    }
+}
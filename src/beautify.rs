@@ -0,0 +1,151 @@
+//! Locale formatting for ITN output
+//!
+//! The ITN verbalizers write recovered numbers as bare digits ("1234567",
+//! "100$", "50%"). This stage typesets them for display: digit grouping,
+//! currency symbol placement and percent spacing.
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::config::{Beautify, CurrencyPlacement, PercentSpacing};
+use crate::format::group_digits;
+
+/// Digit runs with an optional fractional part, or dotted codes ("1.2.3")
+static NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"[0-9]+(?:\.[0-9]+)*").unwrap());
+
+/// Amount followed by a currency symbol ("100$", "100 €")
+static AMOUNT_SYMBOL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([0-9][0-9,.]*) ?([$€£¥￥₩₹₽])").unwrap());
+
+/// Currency symbol followed by an amount ("$100")
+static SYMBOL_AMOUNT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([$€£¥￥₩₹₽]) ?([0-9][0-9,.]*[0-9]|[0-9])").unwrap());
+
+/// Number followed by a percent sign ("50%", "50 ％")
+static PERCENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"([0-9]) *([%％])").unwrap());
+
+/// Characters next to a digit run that mark it as part of a code, time,
+/// version or date rather than a quantity
+fn is_code_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, ':' | '/' | '_' | '-' | '年')
+}
+
+/// Group the integer digits of numbers with at least `group_min_digits`
+/// digits and set their decimal separator
+fn format_numbers(text: &str, options: &Beautify) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    for m in NUMBER.find_iter(text) {
+        let integer = m.as_str().split('.').next().unwrap_or_default();
+        let before = text[..m.start()].chars().next_back();
+        // A minus sign is part of the number; other dashes join codes
+        let before = before.filter(|&c| {
+            c != '-'
+                || text[..m.start() - 1]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| !c.is_whitespace())
+        });
+        let after = text[m.end()..].chars().next();
+        if m.as_str().matches('.').count() > 1
+            || before.is_some_and(|c| is_code_char(c) || c == '.' || c == ',')
+            || after.is_some_and(is_code_char)
+        {
+            continue;
+        }
+        output.push_str(&text[last..m.start()]);
+        match options.thousands_separator {
+            Some(separator)
+                if integer.len() >= options.group_min_digits && !integer.starts_with('0') =>
+            {
+                output.push_str(&group_digits(integer, separator))
+            }
+            _ => output.push_str(integer),
+        }
+        if let Some(fraction) = m.as_str()[integer.len()..].strip_prefix('.') {
+            output.push(options.decimal_separator);
+            output.push_str(fraction);
+        }
+        last = m.end();
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+/// Apply locale formatting to ITN output
+pub(crate) fn beautify(text: &str, options: &Beautify) -> String {
+    // Symbols are placed first, while amounts are still bare digits
+    let text = match options.currency {
+        CurrencyPlacement::AsWritten => text.to_string(),
+        CurrencyPlacement::Before => AMOUNT_SYMBOL
+            .replace_all(text, |caps: &Captures| format!("{}{}", &caps[2], &caps[1]))
+            .into_owned(),
+        CurrencyPlacement::After => SYMBOL_AMOUNT
+            .replace_all(text, |caps: &Captures| format!("{} {}", &caps[2], &caps[1]))
+            .into_owned(),
+    };
+    let text = format_numbers(&text, options);
+
+    match options.percent {
+        PercentSpacing::AsWritten => text,
+        PercentSpacing::Tight => PERCENT.replace_all(&text, "$1$2").into_owned(),
+        PercentSpacing::Spaced => PERCENT.replace_all(&text, "$1 $2").into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_thousands() {
+        let options = Beautify::default();
+        assert_eq!(beautify("共1234567人", &options), "共1,234,567人");
+        assert_eq!(beautify("12345.678", &options), "12,345.678");
+        assert_eq!(beautify("-12345", &options), "-12,345");
+        // Years, short numbers, codes and leading zeros stay as they are
+        assert_eq!(beautify("2024年有1000人", &options), "2024年有1000人");
+        assert_eq!(beautify("10000年", &options), "10000年");
+        assert_eq!(
+            beautify("A12345 00123 1-23456", &options),
+            "A12345 00123 1-23456"
+        );
+
+        let options = Beautify {
+            thousands_separator: Some(' '),
+            group_min_digits: 4,
+            ..Beautify::default()
+        };
+        assert_eq!(beautify("1234", &options), "1 234");
+        let options = Beautify {
+            thousands_separator: None,
+            ..Beautify::default()
+        };
+        assert_eq!(beautify("1234567", &options), "1234567");
+    }
+
+    #[test]
+    fn test_currency_and_percent() {
+        let options = Beautify {
+            currency: CurrencyPlacement::Before,
+            percent: PercentSpacing::Tight,
+            ..Beautify::default()
+        };
+        assert_eq!(beautify("100$ 涨了5 %", &options), "$100 涨了5%");
+        assert_eq!(beautify("12345.5 €", &options), "€12,345.5");
+
+        let options = Beautify {
+            currency: CurrencyPlacement::After,
+            percent: PercentSpacing::Spaced,
+            ..Beautify::default()
+        };
+        assert_eq!(beautify("€100, up 5%", &options), "100 €, up 5 %");
+    }
+
+    #[test]
+    fn test_for_locale() {
+        let options = Beautify::for_locale(crate::format::Locale::DeDe);
+        assert_eq!(beautify("€12345.5, 5%", &options), "12.345,5 €, 5 %");
+        assert_eq!(beautify("v1.2 1.2.3 2024年", &options), "v1.2 1.2.3 2024年");
+    }
+}
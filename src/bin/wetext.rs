@@ -0,0 +1,381 @@
+//! Command-line tools for WeText-RS
+//!
+//! ```text
+//! wetext repl [--fst-dir DIR] [--lang auto|zh|en|ja] [--operator tn|itn]
+//! wetext bench [--fst-dir DIR] [--lang auto|zh|en|ja] [--operator tn|itn] [--iterations N] [FILE]
+//! ```
+//!
+//! The REPL normalizes each input line and prints the tagger output, the
+//! reordered tokens, the verbalizer output and the final result, so grammar
+//! changes can be checked interactively. Lines starting with `:` are
+//! commands; `:help` lists them.
+//!
+//! `bench` times each pipeline stage on the lines of FILE (or standard
+//! input) and prints the mean time per line, to measure performance work on
+//! real text. `cargo bench` runs the same stages on the bundled corpora.
+
+use std::hint::black_box;
+use std::io::{self, BufRead, Read, Write};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use wetext_rs::{Language, Normalizer, NormalizerConfig, Operator};
+
+const USAGE: &str = "\
+usage: wetext repl [--fst-dir DIR] [--lang auto|zh|en|ja] [--operator tn|itn]
+       wetext bench [--fst-dir DIR] [--lang auto|zh|en|ja] [--operator tn|itn] [--iterations N] [FILE]";
+
+const HELP: &str = "\
+commands:
+  :lang auto|zh|en|ja     set the language
+  :op tn|itn              set the operator
+  :dir PATH               load FSTs from another directory
+  :set OPTION on|off      toggle an option (see :config)
+  :reload                 reload the FSTs from disk
+  :config                 show the current configuration
+  :help                   show this help
+  :quit                   exit";
+
+fn parse_lang(value: &str) -> Option<Language> {
+    match value {
+        "auto" => Some(Language::Auto),
+        "zh" => Some(Language::Zh),
+        "en" => Some(Language::En),
+        "ja" => Some(Language::Ja),
+        _ => None,
+    }
+}
+
+fn parse_operator(value: &str) -> Option<Operator> {
+    match value {
+        "tn" => Some(Operator::Tn),
+        "itn" => Some(Operator::Itn),
+        _ => None,
+    }
+}
+
+fn parse_switch(value: &str) -> Option<bool> {
+    match value {
+        "on" | "true" | "1" => Some(true),
+        "off" | "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Boolean options that can be toggled with `:set`
+const OPTIONS: &[&str] = &[
+    "fix_contractions",
+    "traditional_to_simple",
+    "fold_number_glyphs",
+    "katakana_to_full_width",
+    "full_to_half",
+    "remove_interjections",
+    "remove_puncts",
+    "remove_erhua",
+    "tag_oov",
+    "enable_0_to_9",
+    "use_precomposed",
+];
+
+fn set_option(config: NormalizerConfig, name: &str, on: bool) -> Option<NormalizerConfig> {
+    let config = match name {
+        "fix_contractions" => config.with_fix_contractions(on),
+        "traditional_to_simple" => config.with_traditional_to_simple(on),
+        "fold_number_glyphs" => config.with_fold_number_glyphs(on),
+        "katakana_to_full_width" => config.with_katakana_to_full_width(on),
+        "full_to_half" => config.with_full_to_half(on),
+        "remove_interjections" => config.with_remove_interjections(on),
+        "remove_puncts" => config.with_remove_puncts(on),
+        "remove_erhua" => config.with_remove_erhua(on),
+        "tag_oov" => config.with_tag_oov(on),
+        "enable_0_to_9" => config.with_enable_0_to_9(on),
+        "use_precomposed" => config.with_use_precomposed(on),
+        _ => return None,
+    };
+    Some(config)
+}
+
+struct Repl {
+    fst_dir: String,
+    config: NormalizerConfig,
+    normalizer: Normalizer,
+}
+
+impl Repl {
+    fn new(fst_dir: String, config: NormalizerConfig) -> Self {
+        let normalizer = Normalizer::new(&fst_dir, config.clone());
+        Self {
+            fst_dir,
+            config,
+            normalizer,
+        }
+    }
+
+    /// Rebuild the normalizer after a configuration change
+    fn rebuild(&mut self) {
+        self.normalizer = Normalizer::new(&self.fst_dir, self.config.clone());
+    }
+
+    /// Run a `:` command; returns false to exit
+    fn command(&mut self, line: &str) -> bool {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        match (name, args.as_slice()) {
+            (":quit" | ":q" | ":exit", _) => return false,
+            (":help", _) => println!("{}", HELP),
+            (":config", _) => {
+                println!("fst dir: {}", self.fst_dir);
+                println!("{:#?}", self.config);
+            }
+            (":lang", [value]) => match parse_lang(value) {
+                Some(lang) => {
+                    self.config = self.config.clone().with_lang(lang);
+                    self.rebuild();
+                }
+                None => println!("unknown language: {}", value),
+            },
+            (":op", [value]) => match parse_operator(value) {
+                Some(operator) => {
+                    self.config = self.config.clone().with_operator(operator);
+                    self.rebuild();
+                }
+                None => println!("unknown operator: {}", value),
+            },
+            (":dir", [path]) => {
+                self.fst_dir = path.to_string();
+                self.rebuild();
+            }
+            (":set", [option, value]) => {
+                let updated =
+                    parse_switch(value).and_then(|on| set_option(self.config.clone(), option, on));
+                match updated {
+                    Some(config) => {
+                        self.config = config;
+                        self.rebuild();
+                    }
+                    None => println!("usage: :set {} on|off", OPTIONS.join("|")),
+                }
+            }
+            (":reload", _) => {
+                if let Err(e) = self.normalizer.reload() {
+                    println!("reload failed: {}", e);
+                }
+            }
+            _ => println!("unknown command: {} (try :help)", line),
+        }
+        true
+    }
+
+    /// Print every stage for one input line
+    fn run(&mut self, line: &str) {
+        let show = |label: &str, result: &wetext_rs::Result<String>| match result {
+            Ok(text) => println!("  {:<10} {}", label, text),
+            Err(e) => println!("  {:<10} error: {}", label, e),
+        };
+
+        let tagged = self.normalizer.tag(line);
+        show("tagged", &tagged);
+        if let Ok(tagged) = tagged {
+            let reordered = self.normalizer.reorder(&tagged);
+            show("reordered", &reordered);
+            if let Ok(reordered) = reordered {
+                show("verbalized", &self.normalizer.verbalize(&reordered));
+            }
+        }
+        show("result", &self.normalizer.normalize(line));
+    }
+}
+
+/// Options shared by the subcommands
+struct Options {
+    fst_dir: String,
+    config: NormalizerConfig,
+}
+
+impl Options {
+    fn new() -> Self {
+        Self {
+            fst_dir: "fsts".to_string(),
+            config: NormalizerConfig::new(),
+        }
+    }
+
+    /// Apply a shared option; returns false if `arg` is not one
+    fn apply(&mut self, arg: &str, value: &str) -> Result<bool, String> {
+        match arg {
+            "--fst-dir" => self.fst_dir = value.to_string(),
+            "--lang" => {
+                let lang =
+                    parse_lang(value).ok_or_else(|| format!("unknown language: {}", value))?;
+                self.config = self.config.clone().with_lang(lang);
+            }
+            "--operator" => {
+                let operator =
+                    parse_operator(value).ok_or_else(|| format!("unknown operator: {}", value))?;
+                self.config = self.config.clone().with_operator(operator);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+fn repl(args: &[String]) -> Result<(), String> {
+    let mut options = Options::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("{} needs a value\n{}", arg, USAGE))?;
+        if !options.apply(arg, value)? {
+            return Err(format!("unknown argument: {}\n{}", arg, USAGE));
+        }
+    }
+    let Options { fst_dir, config } = options;
+
+    let mut repl = Repl::new(fst_dir, config);
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("wetext> ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        let line = line.map_err(|e| e.to_string())?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with(':') {
+            if !repl.command(line) {
+                return Ok(());
+            }
+        } else {
+            repl.run(line);
+        }
+    }
+}
+
+/// Mean time per call of `f` over every line, after a warm-up pass
+fn time_per_line<F: FnMut(&str)>(lines: &[&str], iterations: u32, mut f: F) -> Duration {
+    lines.iter().for_each(|line| f(line));
+    let start = Instant::now();
+    for _ in 0..iterations {
+        lines.iter().for_each(|line| f(line));
+    }
+    start.elapsed() / (iterations * lines.len() as u32)
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    let mut options = Options::new();
+    let mut iterations: u32 = 10;
+    let mut file = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            file = Some(arg.clone());
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("{} needs a value\n{}", arg, USAGE))?;
+        if arg == "--iterations" {
+            iterations = value
+                .parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| format!("invalid iteration count: {}", value))?;
+        } else if !options.apply(arg, value)? {
+            return Err(format!("unknown argument: {}\n{}", arg, USAGE));
+        }
+    }
+
+    let text = match file {
+        Some(path) => std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?,
+        None => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| e.to_string())?;
+            text
+        }
+    };
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.is_empty() {
+        return Err("no input lines".to_string());
+    }
+
+    let mut normalizer = Normalizer::new(&options.fst_dir, options.config);
+    let tagged = lines
+        .iter()
+        .map(|l| normalizer.tag(l))
+        .collect::<wetext_rs::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+    let reordered: Vec<String> = tagged
+        .iter()
+        .map(|t| normalizer.reorder(t).unwrap_or_default())
+        .collect();
+    let tagged: Vec<&str> = tagged.iter().map(String::as_str).collect();
+    let reordered: Vec<&str> = reordered.iter().map(String::as_str).collect();
+
+    let mut stages = Vec::new();
+    #[cfg(feature = "contractions")]
+    stages.push((
+        "contractions",
+        time_per_line(&lines, iterations, |l| {
+            black_box(normalizer.fix_contractions(l));
+        }),
+    ));
+    stages.push((
+        "tag",
+        time_per_line(&lines, iterations, |l| {
+            black_box(normalizer.tag(l).ok());
+        }),
+    ));
+    stages.push((
+        "reorder",
+        time_per_line(&tagged, iterations, |t| {
+            black_box(normalizer.reorder(t).ok());
+        }),
+    ));
+    stages.push((
+        "verbalize",
+        time_per_line(&reordered, iterations, |t| {
+            black_box(normalizer.verbalize(t).ok());
+        }),
+    ));
+    stages.push((
+        "normalize",
+        time_per_line(&lines, iterations, |l| {
+            black_box(normalizer.normalize(l).ok());
+        }),
+    ));
+    println!(
+        "{} lines, {} iterations, mean per line:",
+        lines.len(),
+        iterations
+    );
+    for (stage, time) in stages {
+        println!("  {:<13} {:?}", stage, time);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("repl") => repl(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Case-insensitive English tagging
+//!
+//! The English grammar only matches lowercase words, so shouting-case ASR
+//! output like "PAID 20 DOLLARS" is tagged from its lowercase form. Words
+//! the pipeline copies through then get their original case back; words it
+//! produced keep the grammar's case.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Latin words, with an optional apostrophe suffix ("DON'T")
+static WORD: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z]+(?:'[A-Za-z]+)?").unwrap());
+
+/// Largest number of (input word, output word) pairs aligned; longer texts
+/// are returned lowercase rather than aligned in quadratic memory
+const MAX_PAIRS: usize = 1 << 22;
+
+/// Whether `text` has capitals the English grammar would not match
+pub(crate) fn has_uppercase(text: &str) -> bool {
+    text.chars().any(char::is_uppercase)
+}
+
+/// Give the words of `output` that were copied from `original` their
+/// original case back
+///
+/// `output` is the normalized form of `original` lowercased. Words are
+/// aligned by their longest common subsequence, so a word the pipeline
+/// produced is not mistaken for a later input word spelled the same.
+pub(crate) fn restore_case(original: &str, output: &str) -> String {
+    let originals: Vec<&str> = WORD.find_iter(original).map(|m| m.as_str()).collect();
+    let outputs: Vec<_> = WORD.find_iter(output).collect();
+    let (n, m) = (originals.len(), outputs.len());
+    if n.saturating_mul(m) > MAX_PAIRS {
+        return output.to_string();
+    }
+    let lower: Vec<String> = originals.iter().map(|word| word.to_lowercase()).collect();
+    let same = |i: usize, j: usize| lower[i] == outputs[j].as_str();
+
+    // common[i][j]: length of the longest common subsequence of the input
+    // words from i and the output words from j
+    let mut common = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            common[i][j] = if same(i, j) {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut restored = String::with_capacity(output.len());
+    let mut copied = 0;
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if same(i, j) {
+            restored.push_str(&output[copied..outputs[j].start()]);
+            restored.push_str(originals[i]);
+            copied = outputs[j].end();
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    restored.push_str(&output[copied..]);
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_case() {
+        assert_eq!(
+            restore_case("PAID 20 DOLLARS", "paid twenty dollars"),
+            "PAID twenty DOLLARS"
+        );
+        // The first "twenty" is the reading of 20, not the copied word
+        assert_eq!(
+            restore_case("20 OR TWENTY", "twenty or twenty"),
+            "twenty OR TWENTY"
+        );
+        assert_eq!(restore_case("Hello 5", "hello five"), "Hello five");
+        assert_eq!(restore_case("", ""), "");
+    }
+
+    #[test]
+    fn test_has_uppercase() {
+        assert!(has_uppercase("20 DOLLARS"));
+        assert!(!has_uppercase("20 dollars"));
+    }
+}
@@ -1,7 +1,14 @@
 //! Configuration types for WeText-RS
 
+use std::collections::HashMap;
+
+use crate::hybrid::EntityClass;
+
+/// Default number of tagger paths rescored when token weights are set
+const DEFAULT_RESCORE_NBEST: usize = 8;
+
 /// Text normalization operation type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Operator {
     /// Text Normalization: numbers → words (e.g., "123" → "一百二十三")
     #[default]
@@ -11,7 +18,7 @@ pub enum Operator {
 }
 
 /// Language type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Language {
     /// Auto-detect language
     #[default]
@@ -24,21 +31,447 @@ pub enum Language {
     Ja,
 }
 
+impl Language {
+    /// Whether support for this language is compiled in
+    ///
+    /// Controlled by the `lang-zh`, `lang-en` and `lang-ja` features (all
+    /// enabled by default). `Auto` is always enabled.
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Language::Auto => true,
+            Language::En => cfg!(feature = "lang-en"),
+            Language::Zh => cfg!(feature = "lang-zh"),
+            Language::Ja => cfg!(feature = "lang-ja"),
+        }
+    }
+}
+
+/// Bias for language detection in `Language::Auto` mode
+///
+/// Only text the detector can't decide on is affected: kanji-only text,
+/// which may be Chinese or Japanese, and text without letters or ideographs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LanguageHint {
+    /// No preference: undecided text is treated as Chinese
+    #[default]
+    Neutral,
+    /// Treat undecided text as Chinese
+    PreferZh,
+    /// Treat kanji-only and undecided text as Japanese
+    PreferJa,
+    /// Treat text without letters or ideographs ("123") as English
+    PreferEn,
+}
+
+/// How `Language::Auto` decides between Chinese and Japanese for kanji-only text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KanjiDisambiguation {
+    /// Follow `language_hint`: Chinese unless Japanese is preferred
+    #[default]
+    Hint,
+    /// Count characters and words used only in Japanese ("駅", "円", "番地")
+    /// or only in Chinese ("这", "们"), falling back to `language_hint` on a tie
+    Dictionary,
+}
+
+/// English variant conventions for TN
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnVariant {
+    /// American English (e.g., "twelve dollars and fifty cents")
+    #[default]
+    American,
+    /// British English: day-first dates, "twelve pounds fifty"
+    British,
+    /// British English with long-scale numbers ("milliard" for 10^9,
+    /// "billion" for 10^12)
+    BritishLongScale,
+}
+
+/// Clock format for reading times (TN only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeFormat {
+    /// Read times as tagged by the FST
+    #[default]
+    AsTagged,
+    /// 12-hour clock: "15:30" -> "下午三点三十分" / "three thirty p m"
+    Hour12,
+    /// 24-hour clock: "3:30 PM" -> "fifteen thirty" / "十五点三十分"
+    Hour24,
+}
+
+/// How years are read in dates (TN only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YearStyle {
+    /// Read years as tagged by the FST
+    #[default]
+    AsTagged,
+    /// Digit by digit ("二零二四"), or in pairs for English ("twenty twenty four")
+    Digits,
+    /// As a cardinal number ("两千零二十四", "two thousand twenty four")
+    Cardinal,
+}
+
+/// Field order assumed for ambiguous numeric dates such as "3/4/2024" (TN only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateOrderPreference {
+    /// Leave the choice to the grammar
+    #[default]
+    AsTagged,
+    /// Day first: "3/4/2024" is 3 April
+    Dmy,
+    /// Month first: "3/4/2024" is 4 March
+    Mdy,
+    /// Year first: "24/3/4" is 4 March 2024
+    Ymd,
+}
+
+/// How the digits after a decimal point are read (TN only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecimalReading {
+    /// Read decimals as tagged by the FST
+    #[default]
+    AsTagged,
+    /// One digit at a time: "12.50" -> "十二点五零", "twelve point five zero"
+    Digits,
+    /// As a number, keeping leading zeros: "12.50" -> "十二点五十", "twelve point fifty"
+    Grouped,
+}
+
+/// How amounts of money with a fractional part are read (TN only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoneyReading {
+    /// Read money as tagged by the FST
+    #[default]
+    AsTagged,
+    /// Read the fraction in minor units: "十二元五角", "twelve dollars and fifty cents"
+    MinorUnits,
+    /// Read the amount as a plain decimal: "十二点五元", "twelve point five dollars"
+    Decimal,
+}
+
+/// How percentages and permille values are read (TN only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PercentReading {
+    /// Read percentages as tagged by the FST
+    #[default]
+    AsTagged,
+    /// The reading of the language: "百分之五十", "五十パーセント", "fifty percent";
+    /// "‰" reads "千分之", "パーミル", "per mille"
+    Native,
+    /// The number followed by the English word, as in code-switched speech:
+    /// "五十percent", "五per mille"
+    LoanWord,
+}
+
+/// How fractions written with a slash are read (TN only)
+///
+/// Chinese and Japanese read "3/4" as "四分之三" / "四分の三" and "1 1/2" as
+/// "一又二分之一" / "一と二分の一" in both styles; the styles differ in English.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FractionReading {
+    /// Read fractions as tagged by the FST
+    #[default]
+    AsTagged,
+    /// "three quarters", "one and a half"
+    Quarters,
+    /// "three fourths", "one and one half"
+    Fourths,
+}
+
+/// How durations such as "3分20秒" and "1h 20m" are read (TN only)
+///
+/// Clock times ("3点20分", "3時20分") are left to the time grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DurationReading {
+    /// Read durations as tagged by the FST
+    #[default]
+    AsTagged,
+    /// "三分二十秒", "one hour twenty minutes"
+    Compact,
+    /// "三分钟二十秒", "one hour and twenty minutes"
+    Full,
+}
+
+/// Scale words used to read large numbers (TN only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberScale {
+    /// Read numbers as tagged by the FST: thousand, million, billion; 万, 亿
+    #[default]
+    AsTagged,
+    /// Indian numbering for English: lakh (10^5) and crore (10^7), so
+    /// 12,34,567 reads "twelve lakh thirty four thousand five hundred sixty seven"
+    Indian,
+    /// 兆 for 10^12 in Chinese, as used in Taiwan: "一兆五千亿" rather than
+    /// "一万五千亿"
+    Zhao,
+}
+
+/// How phone numbers read by the `Phone` rule are split into digit groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PhoneGrouping {
+    /// Keep written separators; group unseparated runs by length: 3-4-4
+    /// for 11 digits, 3-3-4 for 10 digits and in fours otherwise
+    #[default]
+    ByLength,
+    /// Only split where the number is written with separators
+    AsWritten,
+    /// Read Chinese mobile numbers (11 digits starting with 1, with an
+    /// optional +86) in 3-4-4 groups whatever their written separators,
+    /// and other numbers as `ByLength`
+    ChineseMobile,
+}
+
+/// Pause hints inserted into normalized text for TTS engines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PauseHints {
+    /// Insert a comma after long numbers ("，" in CJK text)
+    Punctuation,
+    /// Insert SSML `<break time="..ms"/>` tags after clause punctuation and
+    /// after long numbers
+    Ssml {
+        /// Pause after clause punctuation, in milliseconds
+        clause_ms: u32,
+        /// Pause after long numbers, in milliseconds
+        number_ms: u32,
+    },
+}
+
+/// Script of verbalized Chinese or Japanese output (TN only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputScript {
+    /// Chinese characters
+    #[default]
+    Hanzi,
+    /// Pinyin with tone numbers ("er4 ling2")
+    Pinyin,
+    /// Zhuyin / Bopomofo ("ㄦˋ ㄌㄧㄥˊ")
+    Bopomofo,
+    /// Cantonese jyutping with tone numbers ("ji6 ling4")
+    Jyutping,
+    /// Hepburn romaji for Japanese ("sanbyaku en")
+    Romaji,
+}
+
+/// How Japanese era (元号) years are written (Japanese only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EraStyle {
+    /// Keep years as written
+    #[default]
+    AsWritten,
+    /// Convert era years to Gregorian: "令和6年" -> "2024年"
+    Gregorian,
+    /// Convert Gregorian years to eras: "2019年5月" -> "令和元年5月"
+    Era,
+}
+
+/// How malformed intermediate output is handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Pass malformed tagger output through unchanged
+    #[default]
+    Lenient,
+    /// Fail with an error, to surface grammar bugs
+    Strict,
+}
+
+/// What happens when the tagger or verbalizer accepts no path for its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoMatchPolicy {
+    /// Pass the input through unchanged
+    #[default]
+    Passthrough,
+    /// Fail with `WeTextError::NoMatch`
+    Error,
+    /// Pass the input through, record a warning for the FST and count it
+    /// in `Normalizer::no_match_count`
+    PassthroughWithWarning,
+}
+
+/// How FST output bytes that are not valid UTF-8 are decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidUtf8 {
+    /// Fail with `FstOperationError`
+    #[default]
+    Error,
+    /// Replace each invalid sequence with U+FFFD
+    Replace,
+    /// Drop each invalid sequence
+    Skip,
+    /// Read each byte of an invalid sequence as the code point of the same
+    /// value, which repairs grammars that label Latin-1 characters by code point
+    Latin1,
+}
+
+/// Which characters make TN run the FST pipeline
+///
+/// Text without any trigger character is returned unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TnTrigger {
+    /// ASCII digits only, as in Python WeText
+    #[default]
+    AsciiDigits,
+    /// Any numeric character (full-width digits, "½", "Ⅷ", "①", "²"),
+    /// currency symbols and percent signs
+    Numeric,
+}
+
+/// Where currency symbols go relative to the amount in beautified ITN output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CurrencyPlacement {
+    /// Keep symbols where the verbalizer wrote them
+    #[default]
+    AsWritten,
+    /// Symbol before the amount: "100$" -> "$100"
+    Before,
+    /// Symbol after the amount, separated by a space: "€100" -> "100 €"
+    After,
+}
+
+/// Space between a number and a percent sign in beautified ITN output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PercentSpacing {
+    /// Keep spacing as the verbalizer wrote it
+    #[default]
+    AsWritten,
+    /// No space: "50 %" -> "50%"
+    Tight,
+    /// One space: "50%" -> "50 %"
+    Spaced,
+}
+
+/// Locale formatting applied to recovered numbers in ITN output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Beautify {
+    /// Separator inserted between groups of three integer digits
+    /// ("1234567" -> "1,234,567"), or `None` to leave digits ungrouped
+    pub thousands_separator: Option<char>,
+    /// Minimum number of integer digits before grouping applies, so that
+    /// years and short numbers stay as they are
+    pub group_min_digits: usize,
+    /// Separator written between integer and fractional digits
+    /// ("12.5" -> "12,5" with `','`)
+    pub decimal_separator: char,
+    /// Placement of currency symbols
+    pub currency: CurrencyPlacement,
+    /// Spacing before percent signs
+    pub percent: PercentSpacing,
+}
+
+impl Default for Beautify {
+    fn default() -> Self {
+        Self {
+            thousands_separator: Some(','),
+            group_min_digits: 5,
+            decimal_separator: '.',
+            currency: CurrencyPlacement::default(),
+            percent: PercentSpacing::default(),
+        }
+    }
+}
+
+/// Replacement for masked terms
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum MaskStyle {
+    /// One `*` per character of the term
+    #[default]
+    Stars,
+    /// A fixed token for the whole term (e.g., "<beep>")
+    Token(String),
+}
+
+/// Terms masked in normalized output
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Masking {
+    /// Terms to mask, in their normalized form
+    ///
+    /// Matched ASCII case-insensitively; Latin terms only match whole words.
+    pub terms: Vec<String>,
+    /// Replacement for each term found
+    pub style: MaskStyle,
+}
+
+impl Masking {
+    /// Mask the given terms with stars
+    pub fn new<I, S>(terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            terms: terms.into_iter().map(Into::into).collect(),
+            style: MaskStyle::Stars,
+        }
+    }
+
+    /// Replace each term with `token` instead of stars
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.style = MaskStyle::Token(token.to_string());
+        self
+    }
+}
+
+/// Limits on the search for the best FST path, to bound latency on
+/// adversarial input
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SearchLimits {
+    /// Drop arcs of the composed lattice whose best path costs more than
+    /// `beam` above the best path, before the shortest-path search
+    ///
+    /// Only the n-best and rescoring searches see fewer candidates; the
+    /// best path is always kept.
+    pub beam: Option<f32>,
+    /// Find the best path while composing, expanding the cheapest states
+    /// first and stopping once no open state can lead to a cheaper path
+    ///
+    /// Applies to 1-best searches on FSTs without negative weights; others
+    /// are composed in full. Paths of equal weight may be chosen differently.
+    pub early_stop: bool,
+}
+
+/// Point in the FST pipeline where an extra FST runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// Before the tagger, on the preprocessed text
+    PreTag,
+    /// After the verbalizer, on the spoken form
+    PostVerbalize,
+}
+
 /// Normalizer configuration
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct NormalizerConfig {
     /// Language setting, Auto means auto-detect
     pub lang: Language,
 
+    /// Bias for language detection when `lang` is `Auto`
+    pub language_hint: LanguageHint,
+
+    /// Strategy for kanji-only text when `lang` is `Auto`
+    pub kanji_disambiguation: KanjiDisambiguation,
+
     /// Operation type: TN or ITN
     pub operator: Operator,
 
     /// Whether to fix English contractions (e.g., "don't" → "do not")
+    ///
+    /// Has no effect unless the `contractions` feature is enabled.
     pub fix_contractions: bool,
 
     /// Whether to convert Traditional Chinese to Simplified Chinese
     pub traditional_to_simple: bool,
 
+    /// Whether to rewrite circled numbers, superscripts and vulgar fractions
+    /// (e.g., "①", "10⁵", "½") into digits before tagging (TN only)
+    pub fold_number_glyphs: bool,
+
+    /// Whether to rewrite Chinese numerals mixing digits and characters
+    /// (e.g., "3万5千", "2千零24") into digits before tagging, so they are
+    /// read as one value
+    pub merge_mixed_numerals: bool,
+
+    /// Whether to convert half-width katakana to full-width (e.g., "ｶﾀｶﾅ" → "カタカナ")
+    pub katakana_to_full_width: bool,
+
     /// Whether to convert full-width characters to half-width
     pub full_to_half: bool,
 
@@ -48,6 +481,9 @@ pub struct NormalizerConfig {
     /// Whether to remove punctuation marks
     pub remove_puncts: bool,
 
+    /// Punctuation marks retained when `remove_puncts` is enabled (e.g., "。？！")
+    pub keep_puncts: Vec<char>,
+
     /// Whether to tag OOV (out-of-vocabulary) words
     pub tag_oov: bool,
 
@@ -55,7 +491,238 @@ pub struct NormalizerConfig {
     pub enable_0_to_9: bool,
 
     /// Whether to remove erhua (儿化音) (e.g., "哪儿" → "哪")
+    ///
+    /// In TN this selects the erhua-removing verbalizer FST; in ITN a
+    /// rule-based filter is applied to the output.
     pub remove_erhua: bool,
+
+    /// Entity classes handled by built-in rules instead of the FST (TN only)
+    pub rule_classes: Vec<EntityClass>,
+
+    /// Extra cost per token of a given type (e.g., "date", "cardinal") when
+    /// choosing among tagger paths; negative values favor that type
+    pub token_weights: HashMap<String, f32>,
+
+    /// Number of tagger paths rescored when `token_weights` is non-empty
+    pub rescore_nbest: usize,
+
+    /// Pruning and early termination of the FST path search
+    pub search: SearchLimits,
+
+    /// Decoding of FST output bytes that are not valid UTF-8
+    ///
+    /// Only the invalid sequences are affected; the rest of the output is
+    /// decoded as usual.
+    pub invalid_utf8: InvalidUtf8,
+
+    /// Extra field orders for the reorder stage, keyed by token type
+    ///
+    /// Entries override the built-in orders for the language.
+    pub token_orders: HashMap<String, Vec<String>>,
+
+    /// Verbalizer FSTs for specific token types, relative to the FST directory
+    ///
+    /// Tokens without an entry use the default verbalizer.
+    pub verbalizer_routes: HashMap<String, String>,
+
+    /// Language whose verbalizer reads the tags, or `None` for the language
+    /// that tagged the text
+    ///
+    /// Lets one tagger feed another language's verbalizer, such as a shared
+    /// tagger in front of per-language verbalizers. The tagger's token types
+    /// and fields must be ones the verbalizer accepts.
+    pub verbalizer_lang: Option<Language>,
+
+    /// Extra FSTs run at a pipeline stage, relative to the FST directory
+    ///
+    /// FSTs registered for the same stage run in registration order. Input
+    /// an FST doesn't accept passes through unchanged.
+    pub extra_fsts: Vec<(Stage, String)>,
+
+    /// English variant conventions (English TN only)
+    pub en_variant: EnVariant,
+
+    /// Whether the English tagger sees the input lowercased, with the
+    /// original case restored on the words the pipeline copies through
+    ///
+    /// For shouting-case ASR output ("PAID 20 DOLLARS") the grammar misses.
+    pub en_case_insensitive: bool,
+
+    /// Whether to spell out numbers inside English hyphenated compounds
+    /// before tagging ("6-year-old" → "six-year-old", "24/7" → "twenty-four
+    /// seven") (TN only)
+    pub en_compounds: bool,
+
+    /// Clock format for time tokens (TN only)
+    pub time_format: TimeFormat,
+
+    /// Reading style for years in dates (TN only)
+    pub year_style: YearStyle,
+
+    /// Field order assumed for ambiguous slash dates (TN only)
+    pub date_order: DateOrderPreference,
+
+    /// Reading of the digits after a decimal point (TN only)
+    pub decimal_reading: DecimalReading,
+
+    /// Word read for the decimal point, instead of "点"/"point" (TN only)
+    pub decimal_separator: Option<String>,
+
+    /// Reading of money amounts with a fractional part (TN only)
+    pub money_reading: MoneyReading,
+
+    /// Scale words for large numbers (TN only)
+    pub number_scale: NumberScale,
+
+    /// Reading of percentages and permille values, with "%", "％" or "‰" (TN only)
+    pub percent_reading: PercentReading,
+
+    /// Reading of fractions and mixed numbers such as "3/4" and "1 1/2" (TN only)
+    pub fraction_reading: FractionReading,
+
+    /// Reading of hour, minute and second durations (TN only)
+    pub duration_reading: DurationReading,
+
+    /// Readings of alphanumeric words that override the built-in blend
+    /// reading of `EntityClass::Alphanumeric` (e.g., "B2B" -> "B to B")
+    pub alnum_readings: HashMap<String, String>,
+
+    /// Pause marker inserted between digit groups of phone numbers read by
+    /// the `Phone` rule (e.g., "，" or `<break/>`), for IVR-style reading
+    pub pause_marker: Option<String>,
+
+    /// Digit grouping of phone numbers read by the `Phone` rule
+    pub phone_grouping: PhoneGrouping,
+
+    /// Pause hints added as the last postprocessing stage (TN only)
+    pub pause_hints: Option<PauseHints>,
+
+    /// Locale formatting of recovered numbers, applied before
+    /// postprocessing (ITN only)
+    pub beautify: Option<Beautify>,
+
+    /// Whether to restore capitalization of Latin words in ITN output
+    /// (sentence starts, "I", month and day names, known acronyms)
+    pub truecase: bool,
+
+    /// Terms masked in the output, after postprocessing
+    ///
+    /// Also applied to the spans of `normalize_dual`, `normalize_tokens` and
+    /// `normalize_segmented`.
+    pub masking: Option<Masking>,
+
+    /// Script of verbalized output (Chinese and Japanese TN only)
+    pub output_script: OutputScript,
+
+    /// Conversion between Japanese era and Gregorian years; applied to the
+    /// input for TN and to the output for ITN
+    pub era_style: EraStyle,
+
+    /// Handling of malformed tagger output
+    pub error_policy: ErrorPolicy,
+
+    /// Handling of input the tagger or verbalizer accepts no path for
+    pub no_match: NoMatchPolicy,
+
+    /// Whether input the tagger accepts no path for is normalized sentence
+    /// by sentence, then span by span, so the parts that match still are
+    ///
+    /// Spans that still fail are handled by `no_match` and listed in
+    /// `NormalizeReport::unmatched`.
+    pub partial_match: bool,
+
+    /// Whether `Normalizer::normalize_with_report` scores the tagger's path
+    ///
+    /// The margin to the runner-up is computed from `rescore_nbest` paths.
+    pub report_confidence: bool,
+
+    /// Whether the line-based batch APIs normalize repeated lines only once
+    pub deduplicate_lines: bool,
+
+    /// Whether to use the closest available FST when a required one is missing
+    ///
+    /// For example `zh/itn/tagger.fst` replaces a missing
+    /// `zh/itn/tagger_enable_0_to_9.fst`. Each fallback is recorded in
+    /// `Normalizer::warnings`.
+    pub fst_fallback: bool,
+
+    /// Which characters make TN run the FST pipeline
+    pub tn_trigger: TnTrigger,
+
+    /// Whether to use a pre-composed tagger+verbalizer FST when one exists
+    ///
+    /// See `Normalizer::precompose`. Ignored when `token_weights` or
+    /// `verbalizer_routes` are set, since those need the separate stages.
+    pub use_precomposed: bool,
+
+    /// Whether to compact FSTs as they are loaded
+    ///
+    /// See `FstTextNormalizer::compact`. Read when the `Normalizer` is
+    /// created.
+    pub compact_fsts: bool,
+}
+
+impl Default for NormalizerConfig {
+    fn default() -> Self {
+        Self {
+            lang: Language::default(),
+            language_hint: LanguageHint::default(),
+            kanji_disambiguation: KanjiDisambiguation::default(),
+            operator: Operator::default(),
+            fix_contractions: false,
+            traditional_to_simple: false,
+            fold_number_glyphs: false,
+            merge_mixed_numerals: false,
+            katakana_to_full_width: false,
+            full_to_half: false,
+            remove_interjections: false,
+            remove_puncts: false,
+            keep_puncts: Vec::new(),
+            tag_oov: false,
+            enable_0_to_9: false,
+            remove_erhua: false,
+            rule_classes: Vec::new(),
+            token_weights: HashMap::new(),
+            rescore_nbest: DEFAULT_RESCORE_NBEST,
+            search: SearchLimits::default(),
+            invalid_utf8: InvalidUtf8::default(),
+            token_orders: HashMap::new(),
+            verbalizer_routes: HashMap::new(),
+            verbalizer_lang: None,
+            extra_fsts: Vec::new(),
+            en_variant: EnVariant::default(),
+            en_case_insensitive: false,
+            en_compounds: false,
+            time_format: TimeFormat::default(),
+            year_style: YearStyle::default(),
+            date_order: DateOrderPreference::default(),
+            decimal_reading: DecimalReading::default(),
+            decimal_separator: None,
+            money_reading: MoneyReading::default(),
+            number_scale: NumberScale::default(),
+            percent_reading: PercentReading::default(),
+            fraction_reading: FractionReading::default(),
+            duration_reading: DurationReading::default(),
+            alnum_readings: HashMap::new(),
+            pause_marker: None,
+            phone_grouping: PhoneGrouping::default(),
+            pause_hints: None,
+            beautify: None,
+            truecase: false,
+            masking: None,
+            output_script: OutputScript::default(),
+            era_style: EraStyle::default(),
+            error_policy: ErrorPolicy::default(),
+            no_match: NoMatchPolicy::default(),
+            partial_match: false,
+            report_confidence: false,
+            deduplicate_lines: true,
+            fst_fallback: false,
+            tn_trigger: TnTrigger::default(),
+            use_precomposed: false,
+            compact_fsts: false,
+        }
+    }
 }
 
 impl NormalizerConfig {
@@ -70,6 +737,18 @@ impl NormalizerConfig {
         self
     }
 
+    /// Set the bias for language detection in `Language::Auto` mode
+    pub fn with_language_hint(mut self, hint: LanguageHint) -> Self {
+        self.language_hint = hint;
+        self
+    }
+
+    /// Set how kanji-only text is assigned to Chinese or Japanese
+    pub fn with_kanji_disambiguation(mut self, strategy: KanjiDisambiguation) -> Self {
+        self.kanji_disambiguation = strategy;
+        self
+    }
+
     /// Set the operator
     pub fn with_operator(mut self, operator: Operator) -> Self {
         self.operator = operator;
@@ -88,6 +767,24 @@ impl NormalizerConfig {
         self
     }
 
+    /// Set whether to rewrite number glyphs into digits
+    pub fn with_fold_number_glyphs(mut self, fold: bool) -> Self {
+        self.fold_number_glyphs = fold;
+        self
+    }
+
+    /// Set whether to rewrite mixed digit and Chinese numerals into digits
+    pub fn with_merge_mixed_numerals(mut self, merge: bool) -> Self {
+        self.merge_mixed_numerals = merge;
+        self
+    }
+
+    /// Set whether to convert half-width katakana to full-width
+    pub fn with_katakana_to_full_width(mut self, convert: bool) -> Self {
+        self.katakana_to_full_width = convert;
+        self
+    }
+
     /// Set whether to convert full-width to half-width
     pub fn with_full_to_half(mut self, convert: bool) -> Self {
         self.full_to_half = convert;
@@ -106,6 +803,12 @@ impl NormalizerConfig {
         self
     }
 
+    /// Set the punctuation marks to keep when removing punctuation
+    pub fn with_keep_puncts(mut self, puncts: &str) -> Self {
+        self.keep_puncts = puncts.chars().collect();
+        self
+    }
+
     /// Set whether to remove erhua
     pub fn with_remove_erhua(mut self, remove: bool) -> Self {
         self.remove_erhua = remove;
@@ -118,9 +821,338 @@ impl NormalizerConfig {
         self
     }
 
+    /// Handle an entity class with built-in rules instead of the FST
+    pub fn with_rule_class(mut self, class: EntityClass) -> Self {
+        if !self.rule_classes.contains(&class) {
+            self.rule_classes.push(class);
+        }
+        self
+    }
+
+    /// Set the selection penalty for a token type
+    ///
+    /// Positive weights make readings with this token type less likely,
+    /// negative weights make them more likely.
+    pub fn with_token_weight(mut self, token_type: &str, weight: f32) -> Self {
+        self.token_weights.insert(token_type.to_string(), weight);
+        self
+    }
+
+    /// Set the number of tagger paths rescored with token weights
+    pub fn with_rescore_nbest(mut self, nbest: usize) -> Self {
+        self.rescore_nbest = nbest;
+        self
+    }
+
+    /// Set limits on the FST path search
+    pub fn with_search(mut self, search: SearchLimits) -> Self {
+        self.search = search;
+        self
+    }
+
+    /// Set the decoding of FST output bytes that are not valid UTF-8
+    pub fn with_invalid_utf8(mut self, policy: InvalidUtf8) -> Self {
+        self.invalid_utf8 = policy;
+        self
+    }
+
+    /// Register the field order for a token type emitted by a custom grammar
+    pub fn with_token_order(mut self, token_type: &str, order: &[&str]) -> Self {
+        self.token_orders.insert(
+            token_type.to_string(),
+            order.iter().map(|key| key.to_string()).collect(),
+        );
+        self
+    }
+
+    /// Verbalize tokens of a type with a different FST
+    ///
+    /// # Arguments
+    /// * `token_type` - Token name (e.g., "money")
+    /// * `fst_path` - Verbalizer FST path relative to the FST directory
+    pub fn with_verbalizer_route(mut self, token_type: &str, fst_path: &str) -> Self {
+        self.verbalizer_routes
+            .insert(token_type.to_string(), fst_path.to_string());
+        self
+    }
+
+    /// Verbalize with the FSTs of another language than the one that tagged the text
+    ///
+    /// `Language::Auto` restores the default of verbalizing with the tagging
+    /// language.
+    pub fn with_verbalizer_lang(mut self, lang: Language) -> Self {
+        self.verbalizer_lang = (lang != Language::Auto).then_some(lang);
+        self
+    }
+
+    /// Run an extra FST at a pipeline stage
+    ///
+    /// Lets site-specific grammar patches run alongside the shipped models.
+    ///
+    /// # Arguments
+    /// * `stage` - Where in the pipeline the FST runs
+    /// * `fst_path` - FST path relative to the FST directory
+    pub fn with_extra_fst(mut self, stage: Stage, fst_path: &str) -> Self {
+        self.extra_fsts.push((stage, fst_path.to_string()));
+        self
+    }
+
+    /// Set the English variant
+    pub fn with_en_variant(mut self, variant: EnVariant) -> Self {
+        self.en_variant = variant;
+        self
+    }
+
+    /// Set whether English tagging ignores the case of the input
+    pub fn with_en_case_insensitive(mut self, enable: bool) -> Self {
+        self.en_case_insensitive = enable;
+        self
+    }
+
+    /// Set whether numbers inside English hyphenated compounds are spelled out
+    pub fn with_en_compounds(mut self, enable: bool) -> Self {
+        self.en_compounds = enable;
+        self
+    }
+
+    /// Set the clock format for time tokens
+    pub fn with_time_format(mut self, format: TimeFormat) -> Self {
+        self.time_format = format;
+        self
+    }
+
+    /// Set the reading style for years
+    pub fn with_year_style(mut self, style: YearStyle) -> Self {
+        self.year_style = style;
+        self
+    }
+
+    /// Set the field order assumed for ambiguous slash dates
+    pub fn with_date_order(mut self, order: DateOrderPreference) -> Self {
+        self.date_order = order;
+        self
+    }
+
+    /// Set the reading of the digits after a decimal point
+    pub fn with_decimal_reading(mut self, reading: DecimalReading) -> Self {
+        self.decimal_reading = reading;
+        self
+    }
+
+    /// Set the word read for the decimal point
+    pub fn with_decimal_separator(mut self, separator: &str) -> Self {
+        self.decimal_separator = Some(separator.to_string());
+        self
+    }
+
+    /// Set the reading of money amounts with a fractional part
+    pub fn with_money_reading(mut self, reading: MoneyReading) -> Self {
+        self.money_reading = reading;
+        self
+    }
+
+    /// Set the scale words for large numbers
+    pub fn with_number_scale(mut self, scale: NumberScale) -> Self {
+        self.number_scale = scale;
+        self
+    }
+
+    /// Set the reading of percentages and permille values
+    pub fn with_percent_reading(mut self, reading: PercentReading) -> Self {
+        self.percent_reading = reading;
+        self
+    }
+
+    /// Set the reading of fractions and mixed numbers
+    pub fn with_fraction_reading(mut self, reading: FractionReading) -> Self {
+        self.fraction_reading = reading;
+        self
+    }
+
+    /// Set the reading of durations
+    pub fn with_duration_reading(mut self, reading: DurationReading) -> Self {
+        self.duration_reading = reading;
+        self
+    }
+
+    /// Set the reading of an alphanumeric word
+    pub fn with_alnum_reading(mut self, written: &str, spoken: &str) -> Self {
+        self.alnum_readings
+            .insert(written.to_string(), spoken.to_string());
+        self
+    }
+
+    /// Set the pause marker inserted between phone number digit groups
+    pub fn with_pause_marker(mut self, marker: &str) -> Self {
+        self.pause_marker = Some(marker.to_string());
+        self
+    }
+
+    /// Set the digit grouping of phone numbers
+    pub fn with_phone_grouping(mut self, grouping: PhoneGrouping) -> Self {
+        self.phone_grouping = grouping;
+        self
+    }
+
+    /// Set the pause hints added to normalized text
+    pub fn with_pause_hints(mut self, hints: PauseHints) -> Self {
+        self.pause_hints = Some(hints);
+        self
+    }
+
+    /// Set the locale formatting of ITN output
+    pub fn with_beautify(mut self, beautify: Beautify) -> Self {
+        self.beautify = Some(beautify);
+        self
+    }
+
+    /// Set whether to restore capitalization in ITN output
+    pub fn with_truecase(mut self, truecase: bool) -> Self {
+        self.truecase = truecase;
+        self
+    }
+
+    /// Set the terms masked in the output
+    pub fn with_masking(mut self, masking: Masking) -> Self {
+        self.masking = Some(masking);
+        self
+    }
+
+    /// Set the script of verbalized output
+    pub fn with_output_script(mut self, script: OutputScript) -> Self {
+        self.output_script = script;
+        self
+    }
+
+    /// Set the conversion between Japanese era and Gregorian years
+    pub fn with_era_style(mut self, style: EraStyle) -> Self {
+        self.era_style = style;
+        self
+    }
+
+    /// Set the handling of malformed tagger output
+    pub fn with_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Set the handling of input the tagger or verbalizer accepts no path for
+    pub fn with_no_match(mut self, policy: NoMatchPolicy) -> Self {
+        self.no_match = policy;
+        self
+    }
+
+    /// Set whether unmatched input is normalized sentence by sentence and span by span
+    pub fn with_partial_match(mut self, enable: bool) -> Self {
+        self.partial_match = enable;
+        self
+    }
+
+    /// Set whether normalization reports include a confidence score
+    pub fn with_report_confidence(mut self, report: bool) -> Self {
+        self.report_confidence = report;
+        self
+    }
+
+    /// Set whether repeated lines in batch input are normalized only once
+    pub fn with_deduplicate_lines(mut self, deduplicate: bool) -> Self {
+        self.deduplicate_lines = deduplicate;
+        self
+    }
+
+    /// Set whether to fall back to the closest available FST
+    pub fn with_fst_fallback(mut self, fallback: bool) -> Self {
+        self.fst_fallback = fallback;
+        self
+    }
+
+    /// Set which characters make TN run the FST pipeline
+    pub fn with_tn_trigger(mut self, trigger: TnTrigger) -> Self {
+        self.tn_trigger = trigger;
+        self
+    }
+
+    /// Set whether to use a pre-composed tagger+verbalizer FST
+    pub fn with_use_precomposed(mut self, use_precomposed: bool) -> Self {
+        self.use_precomposed = use_precomposed;
+        self
+    }
+
+    /// Set whether to compact FSTs as they are loaded
+    pub fn with_compact_fsts(mut self, compact_fsts: bool) -> Self {
+        self.compact_fsts = compact_fsts;
+        self
+    }
+
     /// Set whether to enable 0-9 conversion in ITN
     pub fn with_enable_0_to_9(mut self, enable: bool) -> Self {
         self.enable_0_to_9 = enable;
         self
     }
 }
+
+/// Per-call changes to a normalizer's configuration
+///
+/// Fields left as `None` keep the configured value. See
+/// `Normalizer::normalize_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Overrides {
+    /// Language setting
+    pub lang: Option<Language>,
+    /// Bias for language detection
+    pub language_hint: Option<LanguageHint>,
+    /// Operation type
+    pub operator: Option<Operator>,
+    /// Whether to fix English contractions
+    pub fix_contractions: Option<bool>,
+    /// Whether to convert Traditional Chinese to Simplified Chinese
+    pub traditional_to_simple: Option<bool>,
+    /// Whether to convert full-width characters to half-width
+    pub full_to_half: Option<bool>,
+    /// Whether to remove interjections
+    pub remove_interjections: Option<bool>,
+    /// Whether to remove punctuation marks
+    pub remove_puncts: Option<bool>,
+    /// Whether to tag OOV words
+    pub tag_oov: Option<bool>,
+    /// Whether to enable 0-9 digit conversion in ITN
+    pub enable_0_to_9: Option<bool>,
+    /// Whether to remove erhua
+    pub remove_erhua: Option<bool>,
+    /// English variant conventions
+    pub en_variant: Option<EnVariant>,
+    /// Output script for TN
+    pub output_script: Option<OutputScript>,
+}
+
+/// Set `slot` to `value` if given, returning the value it replaced
+fn swap<T: Copy>(slot: &mut T, value: Option<T>) -> Option<T> {
+    value.map(|value| std::mem::replace(slot, value))
+}
+
+impl Overrides {
+    /// Apply the overrides to `config`
+    ///
+    /// # Returns
+    /// The overrides that restore the replaced values
+    pub(crate) fn apply(&self, config: &mut NormalizerConfig) -> Overrides {
+        Overrides {
+            lang: swap(&mut config.lang, self.lang),
+            language_hint: swap(&mut config.language_hint, self.language_hint),
+            operator: swap(&mut config.operator, self.operator),
+            fix_contractions: swap(&mut config.fix_contractions, self.fix_contractions),
+            traditional_to_simple: swap(
+                &mut config.traditional_to_simple,
+                self.traditional_to_simple,
+            ),
+            full_to_half: swap(&mut config.full_to_half, self.full_to_half),
+            remove_interjections: swap(&mut config.remove_interjections, self.remove_interjections),
+            remove_puncts: swap(&mut config.remove_puncts, self.remove_puncts),
+            tag_oov: swap(&mut config.tag_oov, self.tag_oov),
+            enable_0_to_9: swap(&mut config.enable_0_to_9, self.enable_0_to_9),
+            remove_erhua: swap(&mut config.remove_erhua, self.remove_erhua),
+            en_variant: swap(&mut config.en_variant, self.en_variant),
+            output_script: swap(&mut config.output_script, self.output_script),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Resolution of ambiguous numeric dates
+//!
+//! "3/4/2024" is 4 March in the US and 3 April in most other places, and
+//! the grammars pick one order by weight. With a `DateOrderPreference`,
+//! slash dates that are valid in the preferred order are rewritten in ISO
+//! form ("2024-03-04") before tagging, so every grammar reads the same date.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::config::DateOrderPreference;
+
+/// Three numeric fields separated by slashes
+static SLASH_DATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([0-9]{1,4})/([0-9]{1,2})/([0-9]{1,4})").unwrap());
+
+/// Four-digit year of a year field; two-digit years pivot at 69 like POSIX
+/// `strptime` ("68" is 2068, "69" is 1969)
+fn year(field: &str) -> Option<u32> {
+    let value: u32 = field.parse().ok()?;
+    match field.len() {
+        4 => Some(value),
+        2 if value < 69 => Some(2000 + value),
+        2 => Some(1900 + value),
+        _ => None,
+    }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// ISO form of a date read in the given order, if it is a valid date
+fn resolve(fields: [&str; 3], order: DateOrderPreference) -> Option<String> {
+    let [first, second, third] = fields;
+    let (year_field, month, day) = match order {
+        DateOrderPreference::AsTagged => return None,
+        DateOrderPreference::Dmy => (third, second, first),
+        DateOrderPreference::Mdy => (third, first, second),
+        DateOrderPreference::Ymd => (first, second, third),
+    };
+    let year = year(year_field)?;
+    let month: u32 = month.parse().ok()?;
+    let day: u32 = day.parse().ok()?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(format!("{:04}-{:02}-{:02}", year, month, day))
+}
+
+/// Rewrite slash dates that are valid in the preferred order as ISO dates
+///
+/// Dates that are not valid in that order ("13/4/2024" month first) are
+/// left to the grammars.
+pub(crate) fn resolve_slash_dates(text: &str, order: DateOrderPreference) -> String {
+    if order == DateOrderPreference::AsTagged {
+        return text.to_string();
+    }
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in SLASH_DATE.captures_iter(text) {
+        let Some(m) = caps.get(0) else {
+            continue;
+        };
+        // Part of a longer number, path or version
+        let before = text[..m.start()].chars().next_back();
+        let after = text[m.end()..].chars().next();
+        if before.is_some_and(|c| c.is_ascii_digit() || c == '/' || c == '.')
+            || after.is_some_and(|c| c.is_ascii_digit() || c == '/')
+        {
+            continue;
+        }
+        let Some(date) = resolve([&caps[1], &caps[2], &caps[3]], order) else {
+            continue;
+        };
+        output.push_str(&text[last..m.start()]);
+        output.push_str(&date);
+        last = m.end();
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_slash_dates() {
+        let text = "due 3/4/2024, paid 13/4/2024";
+        assert_eq!(
+            resolve_slash_dates(text, DateOrderPreference::Dmy),
+            "due 2024-04-03, paid 2024-04-13"
+        );
+        assert_eq!(
+            resolve_slash_dates(text, DateOrderPreference::Mdy),
+            "due 2024-03-04, paid 13/4/2024"
+        );
+        assert_eq!(
+            resolve_slash_dates("24/3/4 and 3/4/99", DateOrderPreference::Ymd),
+            "2024-03-04 and 3/4/99"
+        );
+        assert_eq!(
+            resolve_slash_dates("3/4/99", DateOrderPreference::Mdy),
+            "1999-03-04"
+        );
+        assert_eq!(
+            resolve_slash_dates(text, DateOrderPreference::AsTagged),
+            text
+        );
+    }
+
+    #[test]
+    fn test_invalid_dates_are_kept() {
+        for text in ["29/2/2023", "31/4/2024", "1/2/3/2024", "v1.3/4/2024", "3/4"] {
+            assert_eq!(resolve_slash_dates(text, DateOrderPreference::Dmy), text);
+        }
+        assert_eq!(
+            resolve_slash_dates("29/2/2024", DateOrderPreference::Dmy),
+            "2024-02-29"
+        );
+    }
+}
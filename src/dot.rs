@@ -0,0 +1,249 @@
+//! Graphviz export of matched FST paths
+//!
+//! Renders the best path an FST takes for an input as a DOT graph, for
+//! grammar authors debugging why an input is rewritten unexpectedly. With a
+//! neighborhood, the grammar states the path runs through are drawn with the
+//! arcs around them, so the alternatives the path did not take are visible.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::Write;
+
+use rustfst::fst_impls::VectorFst;
+use rustfst::prelude::*;
+use rustfst::semirings::TropicalWeight;
+use rustfst::{Label, EPS_LABEL};
+
+use crate::error::{Result, WeTextError};
+
+/// Maximum number of off-path arcs drawn per grammar state
+const MAX_NEIGHBOR_ARCS: usize = 20;
+
+fn op_error(e: anyhow::Error) -> WeTextError {
+    WeTextError::FstOperationError(e.to_string())
+}
+
+/// Printable form of a byte or code point label
+fn label_text(label: Label) -> String {
+    match label {
+        EPS_LABEL => "ε".to_string(),
+        0x21..=0x7e => match char::from_u32(label) {
+            Some('"') => "\\\"".to_string(),
+            Some('\\') => "\\\\".to_string(),
+            Some(c) => c.to_string(),
+            None => format!("0x{:X}", label),
+        },
+        0x100.. => char::from_u32(label).map_or_else(|| format!("U+{:X}", label), String::from),
+        _ => format!("0x{:02X}", label),
+    }
+}
+
+fn arc_label(tr: &Tr<TropicalWeight>) -> String {
+    let weight = *tr.weight.value();
+    if weight == 0.0 {
+        format!("{}:{}", label_text(tr.ilabel), label_text(tr.olabel))
+    } else {
+        format!(
+            "{}:{}/{}",
+            label_text(tr.ilabel),
+            label_text(tr.olabel),
+            weight
+        )
+    }
+}
+
+/// Transitions of a linear FST, in path order
+pub(crate) fn linear_trs(path: &VectorFst<TropicalWeight>) -> Result<Vec<Tr<TropicalWeight>>> {
+    let mut trs = Vec::new();
+    let Some(mut state) = path.start() else {
+        return Ok(trs);
+    };
+    while let Some(tr) = path.get_trs(state).map_err(op_error)?.trs().first() {
+        trs.push(tr.clone());
+        state = tr.nextstate;
+    }
+    Ok(trs)
+}
+
+/// Render a best path as a DOT graph
+pub(crate) fn path_to_dot(path: &VectorFst<TropicalWeight>) -> Result<String> {
+    let trs = linear_trs(path)?;
+    let mut dot = String::from("digraph FST {\n  rankdir = LR;\n");
+    for index in 0..=trs.len() {
+        let shape = if index == trs.len() {
+            "doublecircle"
+        } else {
+            "circle"
+        };
+        let _ = writeln!(dot, "  {} [shape = {}];", index, shape);
+    }
+    for (index, tr) in trs.iter().enumerate() {
+        let _ = writeln!(
+            dot,
+            "  {} -> {} [label = \"{}\"];",
+            index,
+            index + 1,
+            arc_label(tr)
+        );
+    }
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+/// Find the grammar arcs a best path took
+///
+/// Returns `(state, arc index)` pairs, or `None` if the path's labels can't
+/// be followed from the grammar's start state to a final state.
+fn trace<F: Fst<TropicalWeight>>(
+    grammar: &F,
+    labels: &[(Label, Label)],
+) -> Result<Option<Vec<(StateId, usize)>>> {
+    fn step<F: Fst<TropicalWeight>>(
+        grammar: &F,
+        labels: &[(Label, Label)],
+        state: StateId,
+        index: usize,
+        failed: &mut HashSet<(StateId, usize)>,
+        arcs: &mut Vec<(StateId, usize)>,
+    ) -> Result<bool> {
+        if index == labels.len() {
+            return grammar.is_final(state).map_err(op_error);
+        }
+        if failed.contains(&(state, index)) {
+            return Ok(false);
+        }
+        let trs = grammar.get_trs(state).map_err(op_error)?;
+        for (position, tr) in trs.trs().iter().enumerate() {
+            if (tr.ilabel, tr.olabel) != labels[index] {
+                continue;
+            }
+            arcs.push((state, position));
+            if step(grammar, labels, tr.nextstate, index + 1, failed, arcs)? {
+                return Ok(true);
+            }
+            arcs.pop();
+        }
+        failed.insert((state, index));
+        Ok(false)
+    }
+
+    let Some(start) = grammar.start() else {
+        return Ok(None);
+    };
+    let mut arcs = Vec::new();
+    let found = step(grammar, labels, start, 0, &mut HashSet::new(), &mut arcs)?;
+    Ok(found.then_some(arcs))
+}
+
+/// Render a best path inside the grammar it was matched against
+///
+/// The grammar states the path runs through are drawn with every state up
+/// to `neighborhood` arcs away from them (at most `MAX_NEIGHBOR_ARCS` arcs
+/// per state). Arcs on the path are drawn bold and red. Falls back to the
+/// plain path if the path can't be traced through the grammar.
+pub(crate) fn neighborhood_to_dot<F: Fst<TropicalWeight>>(
+    path: &VectorFst<TropicalWeight>,
+    grammar: &F,
+    neighborhood: usize,
+) -> Result<String> {
+    let labels: Vec<(Label, Label)> = linear_trs(path)?
+        .iter()
+        .map(|tr| (tr.ilabel, tr.olabel))
+        .collect();
+    let Some(on_path) = trace(grammar, &labels)? else {
+        return path_to_dot(path);
+    };
+    let on_path: HashSet<(StateId, usize)> = on_path.into_iter().collect();
+
+    // Breadth-first expansion from the path states
+    let mut states: BTreeSet<StateId> = on_path.iter().map(|(state, _)| *state).collect();
+    if let Some(start) = grammar.start() {
+        states.insert(start);
+    }
+    let mut arcs: BTreeMap<(StateId, usize), Tr<TropicalWeight>> = BTreeMap::new();
+    for &(state, position) in &on_path {
+        let trs = grammar.get_trs(state).map_err(op_error)?;
+        let tr = trs.trs()[position].clone();
+        states.insert(tr.nextstate);
+        arcs.insert((state, position), tr);
+    }
+    let mut frontier: Vec<StateId> = states.iter().copied().collect();
+    for _ in 0..neighborhood {
+        let mut next = Vec::new();
+        for state in frontier {
+            let trs = grammar.get_trs(state).map_err(op_error)?;
+            for (position, tr) in trs.trs().iter().enumerate().take(MAX_NEIGHBOR_ARCS) {
+                arcs.entry((state, position)).or_insert_with(|| tr.clone());
+                if states.insert(tr.nextstate) {
+                    next.push(tr.nextstate);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    let mut dot = String::from("digraph FST {\n  rankdir = LR;\n");
+    for &state in &states {
+        let shape = if grammar.is_final(state).map_err(op_error)? {
+            "doublecircle"
+        } else {
+            "circle"
+        };
+        let style = if Some(state) == grammar.start() {
+            ", style = bold"
+        } else {
+            ""
+        };
+        let _ = writeln!(dot, "  {} [shape = {}{}];", state, shape, style);
+    }
+    for (key, tr) in &arcs {
+        let style = if on_path.contains(key) {
+            ", color = red, penwidth = 2"
+        } else {
+            ", color = gray"
+        };
+        let _ = writeln!(
+            dot,
+            "  {} -> {} [label = \"{}\"{}];",
+            key.0,
+            tr.nextstate,
+            arc_label(tr),
+            style
+        );
+    }
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text_normalizer::FstTextNormalizer;
+
+    #[test]
+    fn test_path_dot() {
+        let rule = crate::Rule::replace("a", "x")
+            .weight(1.0)
+            .or(crate::Rule::replace("a", "y").weight(2.0));
+        let normalizer = FstTextNormalizer::from_fst(rule.to_fst().unwrap());
+
+        let dot = normalizer.path_dot("a", 0).unwrap().unwrap();
+        assert!(dot.starts_with("digraph FST {"));
+        assert!(dot.contains("label = \"a:x"));
+        assert!(!dot.contains("a:y"));
+
+        // The neighborhood shows the path not taken
+        let dot = normalizer.path_dot("a", 2).unwrap().unwrap();
+        assert!(dot.contains("color = red"));
+        assert!(dot.contains("a:y"));
+
+        assert!(normalizer.path_dot("b", 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_label_text() {
+        assert_eq!(label_text(EPS_LABEL), "ε");
+        assert_eq!(label_text('"' as Label), "\\\"");
+        assert_eq!(label_text(0xE4), "0xE4");
+        assert_eq!(label_text('二' as Label), "二");
+    }
+}
@@ -0,0 +1,263 @@
+//! English passes over verbalized text
+//!
+//! The English verbalizer FST produces American English and doesn't make
+//! units agree with the number before them. British conventions that only
+//! change wording and unit agreement are applied here after verbalization;
+//! date field order is handled by the token parser. Hyphenated compounds are
+//! expanded before tagging, so the tagger doesn't read them as ranges.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::config::{EnVariant, Language};
+use crate::numbers::{is_en_number_word, num_to_words, Style};
+
+/// Units as (singular, plural), including irregular plurals
+const UNITS: &[(&str, &str)] = &[
+    ("meter", "meters"),
+    ("metre", "metres"),
+    ("kilometer", "kilometers"),
+    ("kilometre", "kilometres"),
+    ("centimeter", "centimeters"),
+    ("centimetre", "centimetres"),
+    ("millimeter", "millimeters"),
+    ("millimetre", "millimetres"),
+    ("mile", "miles"),
+    ("yard", "yards"),
+    ("foot", "feet"),
+    ("inch", "inches"),
+    ("gram", "grams"),
+    ("kilogram", "kilograms"),
+    ("milligram", "milligrams"),
+    ("ton", "tons"),
+    ("tonne", "tonnes"),
+    ("ounce", "ounces"),
+    ("liter", "liters"),
+    ("litre", "litres"),
+    ("milliliter", "milliliters"),
+    ("millilitre", "millilitres"),
+    ("gallon", "gallons"),
+    ("second", "seconds"),
+    ("minute", "minutes"),
+    ("hour", "hours"),
+    ("day", "days"),
+    ("week", "weeks"),
+    ("month", "months"),
+    ("year", "years"),
+    ("century", "centuries"),
+    ("degree", "degrees"),
+    ("byte", "bytes"),
+    ("kilobyte", "kilobytes"),
+    ("megabyte", "megabytes"),
+    ("gigabyte", "gigabytes"),
+    ("terabyte", "terabytes"),
+    ("bit", "bits"),
+    ("watt", "watts"),
+    ("kilowatt", "kilowatts"),
+    ("volt", "volts"),
+    ("ampere", "amperes"),
+    ("calorie", "calories"),
+    ("kilocalorie", "kilocalories"),
+];
+
+/// "X pounds and Y pence" as produced by the money verbalizer
+static POUNDS_AND_PENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(pounds?) and ([a-z -]+?) pence\b").unwrap());
+
+/// Compounds with a leading number: "6-year-old", "3-in-1", "9-to-5"
+static COMPOUND: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b\d+(?:-[A-Za-z]+)+(?:-\d+)?\b").unwrap());
+
+/// "24/7" is not a fraction or a date
+static ROUND_THE_CLOCK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b24/7\b").unwrap());
+
+/// Short-scale names that differ in the long scale
+static SHORT_SCALE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(billion|trillion)\b").unwrap());
+
+/// Apply the wording conventions of `variant` to verbalized English text
+pub(crate) fn apply_variant(text: &str, variant: EnVariant) -> String {
+    match variant {
+        EnVariant::American => text.to_string(),
+        EnVariant::British => british_money(text),
+        EnVariant::BritishLongScale => long_scale(&british_money(text)),
+    }
+}
+
+/// Spell out the numbers inside hyphenated compounds
+///
+/// "6-year-old" becomes "six-year-old" and "3-in-1" "three-in-one"; the
+/// words of a number are hyphenated too ("twenty-four-hour"). Plain ranges
+/// such as "3-5", and a number with a unit symbol or a capitalized code
+/// ("5-mg", "10-K"), are left to the tagger.
+pub(crate) fn expand_compounds(text: &str) -> String {
+    let text = round_the_clock(text);
+    COMPOUND
+        .replace_all(&text, |caps: &regex::Captures| {
+            let parts: Vec<&str> = caps[0].split('-').collect();
+            if let [_, word] = parts.as_slice() {
+                let unit = crate::units::lookup(word).is_some_and(|(symbol, _)| symbol == *word);
+                if unit || word.chars().all(|c| c.is_ascii_uppercase()) {
+                    return caps[0].to_string();
+                }
+            }
+            let parts: Vec<String> = parts
+                .iter()
+                .map(|part| {
+                    if part.chars().all(|c| c.is_ascii_digit()) {
+                        num_to_words(part, Language::En, Style::Cardinal)
+                            .map(|w| w.replace(' ', "-"))
+                            .unwrap_or_else(|_| part.to_string())
+                    } else {
+                        part.to_string()
+                    }
+                })
+                .collect();
+            parts.join("-")
+        })
+        .into_owned()
+}
+
+/// Read "24/7" as "twenty-four seven", unless it is part of a date, path or
+/// longer number ("24/7/2024")
+fn round_the_clock(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    for m in ROUND_THE_CLOCK.find_iter(text) {
+        let before = text[..m.start()].chars().next_back();
+        let after = text[m.end()..].chars().next();
+        if before.is_some_and(|c| c.is_ascii_digit() || c == '/')
+            || after.is_some_and(|c| c.is_ascii_digit() || c == '/')
+        {
+            continue;
+        }
+        output.push_str(&text[last..m.start()]);
+        output.push_str("twenty-four seven");
+        last = m.end();
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+/// Make units agree in number with the reading before them
+///
+/// A unit is singular only after exactly "one" ("one meter", "minus one
+/// degree"); decimals and other numbers take the plural ("one point five
+/// hours"). Units not preceded by a number are left alone.
+pub(crate) fn fix_unit_agreement(text: &str) -> String {
+    let words: Vec<&str> = text.split(' ').collect();
+    let mut output: Vec<String> = Vec::with_capacity(words.len());
+
+    for (i, word) in words.iter().enumerate() {
+        let core = word.trim_end_matches(|c: char| !c.is_alphabetic());
+        let suffix = &word[core.len()..];
+        let unit = UNITS
+            .iter()
+            .find(|(singular, plural)| core == *singular || core == *plural);
+
+        let reading: Vec<&str> = words[..i]
+            .iter()
+            .rev()
+            .take_while(|w| is_en_number_word(w))
+            .copied()
+            .collect();
+        match unit {
+            Some((singular, plural)) if !reading.is_empty() => {
+                let one = matches!(reading.as_slice(), ["one"] | ["one", "minus"]);
+                // "twenty second" is an ordinal, not a duration
+                let unit = if one || *singular == "second" && core == *singular {
+                    singular
+                } else {
+                    plural
+                };
+                output.push(format!("{}{}", unit, suffix));
+            }
+            _ => output.push(word.to_string()),
+        }
+    }
+
+    output.join(" ")
+}
+
+/// "twelve pounds and fifty pence" -> "twelve pounds fifty"
+fn british_money(text: &str) -> String {
+    POUNDS_AND_PENCE.replace_all(text, "$1 $2").into_owned()
+}
+
+/// 10^9 is a milliard and 10^12 a billion in the long scale
+fn long_scale(text: &str) -> String {
+    SHORT_SCALE
+        .replace_all(text, |caps: &regex::Captures| match &caps[1] {
+            "billion" => "milliard",
+            _ => "billion",
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_british_money() {
+        let text = "it costs twelve pounds and fifty pence";
+        assert_eq!(apply_variant(text, EnVariant::American), text);
+        assert_eq!(
+            apply_variant(text, EnVariant::British),
+            "it costs twelve pounds fifty"
+        );
+        assert_eq!(
+            apply_variant("one pound and twenty-five pence", EnVariant::British),
+            "one pound twenty-five"
+        );
+    }
+
+    #[test]
+    fn test_unit_agreement() {
+        assert_eq!(fix_unit_agreement("one meters"), "one meter");
+        assert_eq!(
+            fix_unit_agreement("two point five meter and one point five hour."),
+            "two point five meters and one point five hours."
+        );
+        assert_eq!(fix_unit_agreement("six foot"), "six feet");
+        assert_eq!(fix_unit_agreement("twenty-one inch"), "twenty-one inches");
+        assert_eq!(fix_unit_agreement("a second opinion"), "a second opinion");
+        assert_eq!(fix_unit_agreement("may twenty second"), "may twenty second");
+    }
+
+    #[test]
+    fn test_expand_compounds() {
+        assert_eq!(
+            expand_compounds("a 6-year-old with a 3-in-1 printer"),
+            "a six-year-old with a three-in-one printer"
+        );
+        assert_eq!(expand_compounds("open 24/7"), "open twenty-four seven");
+        assert_eq!(
+            expand_compounds("a 24-hour shift"),
+            "a twenty-four-hour shift"
+        );
+        assert_eq!(expand_compounds("pages 3-5"), "pages 3-5");
+    }
+
+    #[test]
+    fn test_expand_compounds_skips() {
+        // A day-first date, not the phrase
+        assert_eq!(expand_compounds("due 24/7/2024"), "due 24/7/2024");
+        assert_eq!(expand_compounds("see 1/24/7"), "see 1/24/7");
+        // Units and codes are read by the tagger
+        assert_eq!(expand_compounds("a 5-mg dose"), "a 5-mg dose");
+        assert_eq!(expand_compounds("filed a 10-K"), "filed a 10-K");
+        assert_eq!(expand_compounds("a 5-meter pole"), "a five-meter pole");
+    }
+
+    #[test]
+    fn test_long_scale() {
+        assert_eq!(
+            apply_variant("two billion and one trillion", EnVariant::BritishLongScale),
+            "two milliard and one billion"
+        );
+        assert_eq!(
+            apply_variant("two billion", EnVariant::British),
+            "two billion"
+        );
+    }
+}
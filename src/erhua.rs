@@ -0,0 +1,125 @@
+//! Rule-based erhua (儿化音) removal
+//!
+//! The TN verbalizer FST can drop erhua on its own, but that only covers the
+//! TN pipeline. This module provides the same cleanup as plain Rust so it can
+//! be used on ASR transcripts, in ITN mode, or outside the normalizer.
+
+/// Words in which 儿 is a real morpheme rather than an erhua suffix
+///
+/// Based on the erhua whitelist used by WeTextProcessing.
+const WHITELIST: &[&str] = &[
+    "儿女",
+    "儿子",
+    "儿孙",
+    "女儿",
+    "儿媳",
+    "妻儿",
+    "胎儿",
+    "婴儿",
+    "新生儿",
+    "婴幼儿",
+    "幼儿",
+    "少儿",
+    "小儿",
+    "儿歌",
+    "儿童",
+    "儿科",
+    "托儿所",
+    "孤儿",
+    "儿戏",
+    "儿化",
+    "台儿庄",
+    "鹿儿岛",
+    "正儿八经",
+    "吊儿郎当",
+    "生儿育女",
+    "托儿",
+    "健儿",
+    "男儿",
+    "孤儿院",
+    "儿时",
+    "幼儿园",
+    "混血儿",
+    "宠儿",
+    "患儿",
+    "弃儿",
+    "儿郎",
+    "孩儿",
+    "犬儿",
+    "乳儿",
+    "侄儿",
+    "孙儿",
+    "儿媳妇",
+];
+
+/// Remove erhua (儿化音) from Chinese text
+///
+/// Every 儿 (or traditional 兒) is removed unless it is part of a word where
+/// it carries meaning, such as 儿子 or 婴儿.
+///
+/// # Example
+/// ```rust
+/// use wetext_rs::remove_erhua;
+///
+/// assert_eq!(remove_erhua("我们去哪儿玩儿"), "我们去哪玩");
+/// assert_eq!(remove_erhua("他儿子是个婴儿"), "他儿子是个婴儿");
+/// ```
+pub fn remove_erhua(text: &str) -> String {
+    if !text.contains(['儿', '兒']) {
+        return text.to_string();
+    }
+
+    // Work on simplified characters for matching, but keep the original text
+    let chars: Vec<char> = text.chars().collect();
+    let simplified: Vec<char> = chars
+        .iter()
+        .map(|&c| if c == '兒' { '儿' } else { c })
+        .collect();
+
+    let mut keep = vec![true; chars.len()];
+    for (i, &c) in simplified.iter().enumerate() {
+        if c == '儿' && !in_whitelisted_word(&simplified, i) {
+            keep[i] = false;
+        }
+    }
+
+    chars
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(c, keep)| keep.then_some(c))
+        .collect()
+}
+
+/// Check whether position `index` lies inside a whitelisted word
+fn in_whitelisted_word(chars: &[char], index: usize) -> bool {
+    WHITELIST.iter().any(|word| {
+        let word: Vec<char> = word.chars().collect();
+        word.iter()
+            .enumerate()
+            .filter(|(_, &c)| c == '儿')
+            .any(|(offset, _)| {
+                index >= offset
+                    && index - offset + word.len() <= chars.len()
+                    && chars[index - offset..index - offset + word.len()] == word[..]
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_erhua() {
+        assert_eq!(remove_erhua("一点儿"), "一点");
+        assert_eq!(remove_erhua("哪兒"), "哪");
+        assert_eq!(remove_erhua("没有儿化"), "没有儿化");
+    }
+
+    #[test]
+    fn test_whitelist() {
+        assert_eq!(remove_erhua("儿童节快乐"), "儿童节快乐");
+        assert_eq!(remove_erhua("女兒"), "女兒");
+        assert_eq!(remove_erhua("幼儿园门口儿"), "幼儿园门口");
+    }
+}
@@ -25,6 +25,30 @@ pub enum WeTextError {
     #[error("Invalid operator: {0}")]
     InvalidOperator(String),
 
+    /// Invalid number
+    #[error("Invalid number: {0}")]
+    InvalidNumber(String),
+
+    /// Invalid edit range
+    #[error("Invalid edit range: {0}")]
+    InvalidRange(String),
+
+    /// Invalid reference test case
+    #[error("Invalid test case: {0}")]
+    TestCaseError(String),
+
+    /// Normalizer worker thread error
+    #[error("Worker error: {0}")]
+    WorkerError(String),
+
+    /// Punctuation restorer error
+    #[error("Punctuation error: {0}")]
+    PunctuationError(String),
+
+    /// FST accepted no path for its input
+    #[error("No FST match: {0}")]
+    NoMatch(String),
+
     /// Token parse error
     #[error("Token parse error: {0}")]
     TokenParseError(String),
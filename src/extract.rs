@@ -0,0 +1,875 @@
+//! Structured values from tagged tokens
+//!
+//! The taggers already find numbers, dates and amounts in text. This module
+//! reads the fields of their tokens back into values, for applications that
+//! need structured data rather than spoken text. Field values may be digits
+//! ("2024") or spoken numbers ("二零二四", "twenty twenty four"), depending
+//! on the grammar and operator.
+
+use std::ops::Range;
+
+use crate::config::Language;
+use crate::numbers::{words_to_num, Number};
+use crate::rewrite::{period_half, Half};
+use crate::token_parser::Token;
+use crate::units::{self, Quantity};
+
+/// What an extracted number is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    /// A whole number
+    Cardinal,
+    /// A number with a fractional part
+    Decimal,
+    /// An ordinal ("第三", "third"), as its position
+    Ordinal,
+    /// A fraction, as its value
+    Fraction,
+    /// The amount of a measure ("5kg")
+    Measure,
+    /// The amount of money
+    Money,
+    /// The year of a date
+    Year,
+    /// The month of a date (1-12)
+    Month,
+    /// The day of a date
+    Day,
+}
+
+/// A number found in text, as returned by `Normalizer::extract_numbers`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedNumber {
+    /// Byte range of the entity in the input
+    ///
+    /// Adjacent entities the alignment can't separate share a range.
+    pub span: Range<usize>,
+    /// Parsed value
+    pub value: Number,
+    /// What the value is
+    pub kind: NumberKind,
+}
+
+/// A number that doesn't read back the same, as returned by
+/// `Normalizer::verify_readback`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadbackMismatch {
+    /// Number in the original text, or `None` if the normalized text has an
+    /// extra number
+    pub expected: Option<ExtractedNumber>,
+    /// Number read back from the normalized text, or `None` if it was lost
+    pub found: Option<ExtractedNumber>,
+}
+
+/// An amount of money found in text, as returned by `Normalizer::parse_money`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedMoney {
+    /// Byte range of the entity in the input
+    pub span: Range<usize>,
+    /// ISO 4217 code of the currency, if recognized
+    pub currency: Option<&'static str>,
+    /// Amount in major units (`0.5` for "50 cents")
+    pub amount: Number,
+}
+
+/// A measure found in text, as returned by `Normalizer::parse_measures`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedMeasure {
+    /// Byte range of the entity in the input
+    pub span: Range<usize>,
+    /// Amount
+    pub value: Number,
+    /// Unit as tagged ("千克", "kilograms")
+    pub unit: String,
+    /// Unit of the denominator as tagged ("小时" of "每小时"), if any
+    pub denominator: Option<String>,
+    /// Canonical symbol of the whole unit ("kg", "km/h"), if known
+    pub symbol: Option<&'static str>,
+    /// What the unit measures, if known
+    pub quantity: Option<Quantity>,
+}
+
+/// A calendar date, as returned by `Normalizer::parse_dates`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// Year
+    pub year: i32,
+    /// Month (1-12)
+    pub month: u32,
+    /// Day of the month (1-31)
+    pub day: u32,
+}
+
+impl Date {
+    /// Create a date, or `None` if it doesn't exist in the Gregorian calendar
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days)
+            .contains(&day)
+            .then_some(Self { year, month, day })
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A time of day, as returned by `Normalizer::parse_times`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Time {
+    /// Hour on the 24-hour clock (0-23)
+    pub hour: u32,
+    /// Minute (0-59)
+    pub minute: u32,
+    /// Second (0-59)
+    pub second: u32,
+    /// Period of day as tagged ("下午", "p m"), if any
+    pub noon: Option<String>,
+}
+
+impl std::fmt::Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+const EN_MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// Cardinal reading of an English ordinal ("twenty first" -> "twenty one")
+fn en_ordinal_to_cardinal(value: &str) -> Option<String> {
+    let (head, last) = match value.rsplit_once([' ', '-']) {
+        Some((head, last)) => (format!("{} ", head), last),
+        None => (String::new(), value),
+    };
+    let last = match last {
+        "first" => "one".to_string(),
+        "second" => "two".to_string(),
+        "third" => "three".to_string(),
+        "fifth" => "five".to_string(),
+        "eighth" => "eight".to_string(),
+        "ninth" => "nine".to_string(),
+        "twelfth" => "twelve".to_string(),
+        _ => match last.strip_suffix("ieth") {
+            Some(stem) => format!("{}y", stem),
+            None => last.strip_suffix("th")?.to_string(),
+        },
+    };
+    Some(format!("{}{}", head, last))
+}
+
+/// Read a numeric field value written in digits or spoken
+pub(crate) fn read_value(value: &str, lang: Language) -> Option<Number> {
+    let value = value.trim();
+    let plain = value.replace(',', "");
+    if let Ok(n) = plain.parse::<i64>() {
+        return Some(Number::Integer(n));
+    }
+    if plain.contains('.') {
+        if let Ok(n) = plain.parse::<f64>() {
+            return Some(Number::Decimal(n));
+        }
+    }
+    let spoken = value
+        .strip_prefix('第')
+        .unwrap_or(value)
+        .trim_end_matches(['号', '號', '日', '月', '年']);
+    words_to_num(spoken, lang).ok().or_else(|| {
+        let lower = value.to_lowercase();
+        words_to_num(&en_ordinal_to_cardinal(&lower)?, Language::En).ok()
+    })
+}
+
+/// Read a year in digits, digit by digit, as a cardinal, or in English pairs
+fn read_year(value: &str, lang: Language) -> Option<i64> {
+    if lang == Language::En {
+        if let Some(year) = crate::rewrite::read_en_year(value) {
+            return Some(year);
+        }
+    }
+    read_value(value, lang)?.as_i64()
+}
+
+/// Read a day of the month
+fn read_day(value: &str, lang: Language) -> Option<i64> {
+    read_value(value, lang)?.as_i64()
+}
+
+/// Read a month number or English month name
+fn read_month(value: &str, lang: Language) -> Option<i64> {
+    let lower = value.trim().trim_end_matches('.').to_lowercase();
+    if let Some(index) = EN_MONTHS
+        .iter()
+        .position(|m| *m == lower || (lower.len() >= 3 && m.starts_with(&lower)))
+    {
+        return Some(index as i64 + 1);
+    }
+    read_value(value, lang)?
+        .as_i64()
+        .filter(|m| (1..=12).contains(m))
+}
+
+/// Whether a token carries a negative sign field
+fn is_negative(token: &Token) -> bool {
+    token.get("negative").is_some()
+        || matches!(token.get("sign"), Some("-" | "负" | "負" | "マイナス"))
+}
+
+/// Value of a token's amount fields
+///
+/// English tokens split the amount into `integer_part`, `fractional_part`
+/// and a `quantity` ("million"); Chinese and Japanese tokens carry it in
+/// `value` or `integer`.
+pub(crate) fn amount(token: &Token, lang: Language) -> Option<Number> {
+    let value = if let Some(integer) = token.get("integer_part") {
+        let integer = read_value(integer, lang)?.as_i64()?;
+        match token.get("fractional_part") {
+            Some(fraction) => {
+                let digits: Option<String> = fraction
+                    .split_whitespace()
+                    .map(|w| match w {
+                        "oh" | "o" => Some('0'),
+                        _ => read_value(w, lang)?
+                            .as_i64()
+                            .and_then(|d| char::from_digit(u32::try_from(d).ok()?, 10)),
+                    })
+                    .collect();
+                let digits = match digits {
+                    Some(digits) if !digits.is_empty() => digits,
+                    _ => fraction.chars().filter(char::is_ascii_digit).collect(),
+                };
+                Number::Decimal(format!("{}.{}", integer, digits).parse().ok()?)
+            }
+            None => Number::Integer(integer),
+        }
+    } else if let Some(fraction) = token.get("fractional_part") {
+        Number::Decimal(format!("0.{}", fraction.trim()).parse().ok()?)
+    } else {
+        read_value(token.get("value").or_else(|| token.get("integer"))?, lang)?
+    };
+
+    let value = match token
+        .get("quantity")
+        .and_then(|q| read_value(&format!("one {}", q), Language::En))
+    {
+        Some(quantity) => match (value, quantity) {
+            (Number::Integer(n), Number::Integer(q)) => Number::Integer(n.checked_mul(q)?),
+            (value, quantity) => Number::Decimal(value.as_f64() * quantity.as_f64()),
+        },
+        None => value,
+    };
+    Some(with_sign(token, value))
+}
+
+/// Negate a value if the token carries a negative sign
+fn with_sign(token: &Token, value: Number) -> Number {
+    match (value, is_negative(token)) {
+        (Number::Integer(n), true) if n > 0 => Number::Integer(-n),
+        (Number::Decimal(n), true) if n > 0.0 => Number::Decimal(-n),
+        (value, _) => value,
+    }
+}
+
+/// Value of a fraction's `numerator` and `denominator` fields
+fn fraction(token: &Token, lang: Language) -> Option<Number> {
+    let numerator = read_value(token.get("numerator")?, lang)?.as_f64();
+    let denominator = read_value(token.get("denominator")?, lang)?.as_f64();
+    if denominator == 0.0 {
+        return None;
+    }
+    let whole = token
+        .get("integer_part")
+        .and_then(|i| read_value(i, lang))
+        .map_or(0.0, |n| n.as_f64());
+    let value = whole + numerator / denominator;
+    Some(Number::Decimal(if is_negative(token) {
+        -value
+    } else {
+        value
+    }))
+}
+
+/// ISO 4217 code of a currency name or symbol, and how many of the unit
+/// make up one major unit
+fn currency(name: &str, lang: Language) -> Option<(&'static str, u32)> {
+    let name = name.trim().to_lowercase();
+    let currency = match name.as_str() {
+        "¥" | "￥" if lang == Language::Ja => ("JPY", 1),
+        "元" | "块" | "塊" | "圆" | "圓" | "人民币" | "人民幣" | "¥" | "￥" => {
+            ("CNY", 1)
+        }
+        "角" | "毛" => ("CNY", 10),
+        "分" => ("CNY", 100),
+        "$" | "us$" | "美元" | "美金" | "ドル" | "dollar" | "dollars" => ("USD", 1),
+        "cent" | "cents" | "セント" => ("USD", 100),
+        "hk$" | "港元" | "港币" | "港幣" => ("HKD", 1),
+        "€" | "欧元" | "歐元" | "ユーロ" | "euro" | "euros" => ("EUR", 1),
+        "£" | "英镑" | "英鎊" | "ポンド" | "pound" | "pounds" => ("GBP", 1),
+        "penny" | "pence" => ("GBP", 100),
+        "円" | "日元" | "日圆" | "yen" => ("JPY", 1),
+        "₩" | "韩元" | "韓元" | "ウォン" | "won" => ("KRW", 1),
+        "₹" | "卢比" | "盧比" | "ルピー" | "rupee" | "rupees" => ("INR", 1),
+        "₽" | "卢布" | "盧布" | "ルーブル" | "ruble" | "rubles" => ("RUB", 1),
+        _ => return None,
+    };
+    Some(currency)
+}
+
+/// Currency and amount carried by a money token
+///
+/// Amounts given in a minor unit ("fifty cents", "五角") are converted to
+/// the major unit.
+pub(crate) fn money(token: &Token, lang: Language) -> Option<(Option<&'static str>, Number)> {
+    if token.name != "money" {
+        return None;
+    }
+    let amount = amount(token, lang)?;
+    let name = token
+        .get("currency")
+        .or_else(|| token.get("currency_maj"))
+        .or_else(|| token.get("currency_min"));
+    Some(match name.and_then(|name| currency(name, lang)) {
+        Some((code, 1)) => (Some(code), amount),
+        Some((code, per_major)) => (
+            Some(code),
+            Number::Decimal(amount.as_f64() / f64::from(per_major)),
+        ),
+        None => (None, amount),
+    })
+}
+
+/// Read an hour, minute or second field ("3", "三点", "半")
+fn read_clock(value: &str, lang: Language, max: i64) -> Option<u32> {
+    let value = value
+        .trim()
+        .trim_end_matches(['点', '點', '时', '時', '分', '秒']);
+    let n = match value {
+        "半" => 30,
+        _ => read_value(value, lang)?.as_i64()?,
+    };
+    u32::try_from(n).ok().filter(|n| i64::from(*n) <= max)
+}
+
+/// Time of day carried by a time token
+///
+/// The hour is moved to the 24-hour clock using the period of day.
+pub(crate) fn time(token: &Token, lang: Language) -> Option<Time> {
+    if token.name != "time" {
+        return None;
+    }
+    let field = |keys: [&str; 2]| keys.iter().find_map(|key| token.get(key));
+    let hour = read_clock(field(["hour", "hours"])?, lang, 24)?;
+    let minute = match field(["minute", "minutes"]) {
+        Some(minute) => read_clock(minute, lang, 59)?,
+        None => 0,
+    };
+    let second = match field(["second", "seconds"]) {
+        Some(second) => read_clock(second, lang, 59)?,
+        None => 0,
+    };
+    let noon = field(["noon", "suffix"]).map(str::to_string);
+    let hour = match noon.as_deref().and_then(period_half) {
+        Some(Half::Pm) if hour < 12 => hour + 12,
+        Some(Half::Am) if hour == 12 => 0,
+        _ => hour % 24,
+    };
+    Some(Time {
+        hour,
+        minute,
+        second,
+        noon,
+    })
+}
+
+/// Split a measure field into its amount and unit ("五千克" -> 5, "千克")
+///
+/// Known units are matched first; otherwise the longest leading amount is
+/// taken ("三个" -> 3, "个").
+fn split_measure(value: &str, lang: Language) -> Option<(Number, &str)> {
+    let value = value.trim();
+    let (head, unit) =
+        units::split_unit(value, |head| read_value(head, lang).is_some()).or_else(|| {
+            let bounds: Vec<usize> = value.char_indices().map(|(i, _)| i).skip(1).collect();
+            bounds
+                .into_iter()
+                .rev()
+                .map(|i| value.split_at(i))
+                .find(|(head, _)| read_value(head, lang).is_some())
+        })?;
+    Some((read_value(head, lang)?, unit.trim()))
+}
+
+/// Measure carried by a measure token
+///
+/// English tokens carry the unit in `units`; Chinese and Japanese tokens
+/// write it after the amount in `value`, or in `numerator` with the unit
+/// of a rate in `denominator` ("每小时").
+pub(crate) fn measure(
+    token: &Token,
+    lang: Language,
+    span: Range<usize>,
+) -> Option<ExtractedMeasure> {
+    if token.name != "measure" {
+        return None;
+    }
+    let (value, unit) = match token.get("units").or_else(|| token.get("unit")) {
+        Some(unit) => (amount(token, lang)?, unit.trim()),
+        None => {
+            let field = token.get("numerator").or_else(|| token.get("value"))?;
+            let (value, unit) = split_measure(field, lang)?;
+            (with_sign(token, value), unit)
+        }
+    };
+    let denominator = token
+        .get("denominator")
+        .map(|d| {
+            d.trim()
+                .trim_start_matches(['每', '/'])
+                .trim_start_matches("per ")
+                .trim()
+        })
+        .filter(|d| !d.is_empty());
+
+    let (symbol, quantity) = match denominator {
+        Some(denominator) => match (units::lookup(unit), units::lookup(denominator)) {
+            (Some((unit, _)), Some((denominator, _))) => {
+                match units::lookup(&format!("{}/{}", unit, denominator)) {
+                    Some((symbol, quantity)) => (Some(symbol), Some(quantity)),
+                    None => (None, None),
+                }
+            }
+            _ => (None, None),
+        },
+        None => match units::lookup(unit) {
+            Some((symbol, quantity)) => (Some(symbol), Some(quantity)),
+            None => (None, None),
+        },
+    };
+    Some(ExtractedMeasure {
+        span,
+        value,
+        unit: unit.to_string(),
+        denominator: denominator.map(str::to_string),
+        symbol,
+        quantity,
+    })
+}
+
+/// Full date carried by a date token
+///
+/// Dates without a year, month or day ("1月15日") are skipped.
+pub(crate) fn date(token: &Token, lang: Language) -> Option<Date> {
+    if token.name != "date" {
+        return None;
+    }
+    let year = read_year(token.get("year")?, lang)?;
+    let month = read_month(token.get("month")?, lang)?;
+    let day = read_day(token.get("day")?, lang)?;
+    Date::new(
+        i32::try_from(year).ok()?,
+        u32::try_from(month).ok()?,
+        u32::try_from(day).ok()?,
+    )
+}
+
+/// Numbers carried by tagged entities, with their spans
+pub(crate) fn entity_numbers(
+    entities: Vec<(Token, Range<usize>)>,
+    lang: Language,
+) -> Vec<ExtractedNumber> {
+    entities
+        .into_iter()
+        .flat_map(|(token, span)| {
+            numbers(&token, lang)
+                .into_iter()
+                .map(move |(kind, value)| ExtractedNumber {
+                    span: span.clone(),
+                    value,
+                    kind,
+                })
+        })
+        .collect()
+}
+
+/// Whether two numbers are equal, regardless of integer or decimal form
+fn same_value(a: &Number, b: &Number) -> bool {
+    (a.as_f64() - b.as_f64()).abs() < 1e-9
+}
+
+/// Pair up the numbers of two texts and return those that differ
+///
+/// Equal values are matched in order (longest common subsequence); the
+/// unmatched numbers between two matches are paired as substitutions, and
+/// the rest are reported as lost or extra.
+pub(crate) fn compare(
+    expected: Vec<ExtractedNumber>,
+    found: Vec<ExtractedNumber>,
+) -> Vec<ReadbackMismatch> {
+    let (n, m) = (expected.len(), found.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if same_value(&expected[i].value, &found[j].value) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut mismatches = Vec::new();
+    let mut lost = Vec::new();
+    let mut extra = Vec::new();
+    let mut flush = |lost: &mut Vec<ExtractedNumber>, extra: &mut Vec<ExtractedNumber>| {
+        let mut lost = lost.drain(..);
+        let mut extra = extra.drain(..);
+        loop {
+            match (lost.next(), extra.next()) {
+                (None, None) => break,
+                (expected, found) => mismatches.push(ReadbackMismatch { expected, found }),
+            }
+        }
+    };
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && same_value(&expected[i].value, &found[j].value) {
+            flush(&mut lost, &mut extra);
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lost.push(expected[i].clone());
+            i += 1;
+        } else {
+            extra.push(found[j].clone());
+            j += 1;
+        }
+    }
+    flush(&mut lost, &mut extra);
+    mismatches
+}
+
+/// Numbers carried by a token
+pub(crate) fn numbers(token: &Token, lang: Language) -> Vec<(NumberKind, Number)> {
+    let mut numbers = Vec::new();
+    match token.name.as_str() {
+        "cardinal" | "decimal" => {
+            if let Some(value) = amount(token, lang) {
+                let kind = match value {
+                    Number::Integer(_) => NumberKind::Cardinal,
+                    Number::Decimal(_) => NumberKind::Decimal,
+                };
+                numbers.push((kind, value));
+            }
+        }
+        "ordinal" => {
+            if let Some(value) = amount(token, lang) {
+                numbers.push((NumberKind::Ordinal, value));
+            }
+        }
+        "fraction" => {
+            if let Some(value) = fraction(token, lang) {
+                numbers.push((NumberKind::Fraction, value));
+            }
+        }
+        "measure" => {
+            if let Some(value) = measure(token, lang, 0..0)
+                .map(|measure| measure.value)
+                .or_else(|| amount(token, lang))
+                .or_else(|| fraction(token, lang))
+            {
+                numbers.push((NumberKind::Measure, value));
+            }
+        }
+        "money" => {
+            if let Some(value) = amount(token, lang) {
+                numbers.push((NumberKind::Money, value));
+            }
+        }
+        "date" => {
+            if let Some(year) = token.get("year").and_then(|y| read_year(y, lang)) {
+                numbers.push((NumberKind::Year, Number::Integer(year)));
+            }
+            if let Some(month) = token.get("month").and_then(|m| read_month(m, lang)) {
+                numbers.push((NumberKind::Month, Number::Integer(month)));
+            }
+            if let Some(day) = token.get("day").and_then(|d| read_day(d, lang)) {
+                numbers.push((NumberKind::Day, Number::Integer(day)));
+            }
+        }
+        _ => {}
+    }
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(name: &str, fields: &[(&str, &str)]) -> Token {
+        let mut token = Token::new(name);
+        for (key, value) in fields {
+            token.set(key, value);
+        }
+        token
+    }
+
+    #[test]
+    fn test_read_value() {
+        assert_eq!(
+            read_value("1,024", Language::En),
+            Some(Number::Integer(1024))
+        );
+        assert_eq!(
+            read_value("12.5", Language::Zh),
+            Some(Number::Decimal(12.5))
+        );
+        assert_eq!(
+            read_value("二零二四", Language::Zh),
+            Some(Number::Integer(2024))
+        );
+        assert_eq!(
+            read_value("十五日", Language::Zh),
+            Some(Number::Integer(15))
+        );
+        assert_eq!(
+            read_value("twenty first", Language::En),
+            Some(Number::Integer(21))
+        );
+        assert_eq!(read_value("several", Language::En), None);
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(Date::new(2024, 2, 29).unwrap().to_string(), "2024-02-29");
+        assert_eq!(Date::new(2023, 2, 29), None);
+        assert_eq!(Date::new(2024, 13, 1), None);
+
+        let date = token(
+            "date",
+            &[("year", "二零二四"), ("month", "一"), ("day", "十五")],
+        );
+        assert_eq!(super::date(&date, Language::Zh), Date::new(2024, 1, 15));
+        let date = token(
+            "date",
+            &[("month", "Jan."), ("day", "fifteenth"), ("year", "2024")],
+        );
+        assert_eq!(super::date(&date, Language::En), Date::new(2024, 1, 15));
+        let date = token("date", &[("month", "一"), ("day", "十五")]);
+        assert_eq!(super::date(&date, Language::Zh), None);
+    }
+
+    #[test]
+    fn test_money() {
+        let yuan = token("money", &[("value", "一百"), ("currency", "元")]);
+        assert_eq!(
+            money(&yuan, Language::Zh),
+            Some((Some("CNY"), Number::Integer(100)))
+        );
+        let yen = token("money", &[("value", "500"), ("currency", "¥")]);
+        assert_eq!(
+            money(&yen, Language::Ja),
+            Some((Some("JPY"), Number::Integer(500)))
+        );
+        let dollars = token(
+            "money",
+            &[
+                ("integer_part", "twelve"),
+                ("fractional_part", "five"),
+                ("currency_maj", "dollars"),
+            ],
+        );
+        assert_eq!(
+            money(&dollars, Language::En),
+            Some((Some("USD"), Number::Decimal(12.5)))
+        );
+        let cents = token(
+            "money",
+            &[("integer_part", "fifty"), ("currency_maj", "cents")],
+        );
+        assert_eq!(
+            money(&cents, Language::En),
+            Some((Some("USD"), Number::Decimal(0.5)))
+        );
+        let unknown = token("money", &[("value", "3"), ("currency", "贝")]);
+        assert_eq!(
+            money(&unknown, Language::Zh),
+            Some((None, Number::Integer(3)))
+        );
+    }
+
+    #[test]
+    fn test_time() {
+        let afternoon = token(
+            "time",
+            &[("noon", "下午"), ("hour", "三"), ("minute", "三十")],
+        );
+        let afternoon = time(&afternoon, Language::Zh).unwrap();
+        assert_eq!((afternoon.hour, afternoon.minute), (15, 30));
+        assert_eq!(afternoon.noon.as_deref(), Some("下午"));
+        assert_eq!(afternoon.to_string(), "15:30:00");
+
+        let half = token("time", &[("hour", "8"), ("minute", "半")]);
+        assert_eq!(time(&half, Language::Zh).unwrap().to_string(), "08:30:00");
+        let midnight = token("time", &[("hours", "twelve"), ("suffix", "a m")]);
+        assert_eq!(time(&midnight, Language::En).unwrap().hour, 0);
+        let invalid = token("time", &[("hour", "3"), ("minute", "75")]);
+        assert_eq!(time(&invalid, Language::Zh), None);
+    }
+
+    #[test]
+    fn test_measure() {
+        let mass = token("measure", &[("value", "五千克")]);
+        let mass = measure(&mass, Language::Zh, 0..6).unwrap();
+        assert_eq!(mass.value, Number::Integer(5));
+        assert_eq!(mass.unit, "千克");
+        assert_eq!(mass.symbol, Some("kg"));
+        assert_eq!(mass.quantity, Some(Quantity::Mass));
+
+        let speed = token(
+            "measure",
+            &[("numerator", "一百公里"), ("denominator", "每小时")],
+        );
+        let speed = measure(&speed, Language::Zh, 0..6).unwrap();
+        assert_eq!(speed.value, Number::Integer(100));
+        assert_eq!(speed.denominator.as_deref(), Some("小时"));
+        assert_eq!(speed.symbol, Some("km/h"));
+        assert_eq!(speed.quantity, Some(Quantity::Speed));
+
+        let english = token(
+            "measure",
+            &[("integer_part", "two"), ("units", "miles per hour")],
+        );
+        let english = measure(&english, Language::En, 0..6).unwrap();
+        assert_eq!(english.value, Number::Integer(2));
+        assert_eq!(english.symbol, Some("mph"));
+
+        let count = token("measure", &[("value", "三个")]);
+        let count = measure(&count, Language::Zh, 0..6).unwrap();
+        assert_eq!(
+            (count.value, count.unit.as_str()),
+            (Number::Integer(3), "个")
+        );
+        assert_eq!(count.quantity, None);
+    }
+
+    #[test]
+    fn test_compare() {
+        let number = |value: i64, kind| ExtractedNumber {
+            span: 0..0,
+            value: Number::Integer(value),
+            kind,
+        };
+        let expected = vec![
+            number(2024, NumberKind::Year),
+            number(5, NumberKind::Month),
+            number(100, NumberKind::Money),
+        ];
+        let found = vec![
+            number(2024, NumberKind::Year),
+            number(5, NumberKind::Month),
+            ExtractedNumber {
+                value: Number::Decimal(100.0),
+                ..number(0, NumberKind::Money)
+            },
+        ];
+        assert!(compare(expected.clone(), found).is_empty());
+
+        let found = vec![
+            number(2024, NumberKind::Year),
+            number(1000, NumberKind::Money),
+        ];
+        assert_eq!(
+            compare(expected, found),
+            [
+                ReadbackMismatch {
+                    expected: Some(number(5, NumberKind::Month)),
+                    found: Some(number(1000, NumberKind::Money)),
+                },
+                ReadbackMismatch {
+                    expected: Some(number(100, NumberKind::Money)),
+                    found: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        let date = token(
+            "date",
+            &[("year", "二零二四"), ("month", "一"), ("day", "十五")],
+        );
+        assert_eq!(
+            numbers(&date, Language::Zh),
+            [
+                (NumberKind::Year, Number::Integer(2024)),
+                (NumberKind::Month, Number::Integer(1)),
+                (NumberKind::Day, Number::Integer(15)),
+            ]
+        );
+
+        let date = token(
+            "date",
+            &[
+                ("month", "jan"),
+                ("day", "fifteenth"),
+                ("year", "twenty twenty four"),
+            ],
+        );
+        assert_eq!(
+            numbers(&date, Language::En),
+            [
+                (NumberKind::Year, Number::Integer(2024)),
+                (NumberKind::Month, Number::Integer(1)),
+                (NumberKind::Day, Number::Integer(15)),
+            ]
+        );
+
+        let money = token(
+            "money",
+            &[("integer_part", "twelve"), ("fractional_part", "five zero")],
+        );
+        assert_eq!(
+            numbers(&money, Language::En),
+            [(NumberKind::Money, Number::Decimal(12.5))]
+        );
+        let money = token("money", &[("integer_part", "two"), ("quantity", "million")]);
+        assert_eq!(
+            numbers(&money, Language::En),
+            [(NumberKind::Money, Number::Integer(2_000_000))]
+        );
+
+        let cardinal = token("cardinal", &[("value", "负一点五")]);
+        assert_eq!(
+            numbers(&cardinal, Language::Zh),
+            [(NumberKind::Decimal, Number::Decimal(-1.5))]
+        );
+        let fraction = token("fraction", &[("numerator", "1"), ("denominator", "4")]);
+        assert_eq!(
+            numbers(&fraction, Language::Zh),
+            [(NumberKind::Fraction, Number::Decimal(0.25))]
+        );
+        assert!(numbers(&token("char", &[("value", "a")]), Language::Zh).is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+//! Locale-aware number formatting
+//!
+//! The conventions the ITN beautifier uses to typeset recovered numbers,
+//! exposed so applications can format their own values the same way.
+
+use crate::config::{Beautify, CurrencyPlacement, PercentSpacing};
+
+/// Number formatting conventions of a region
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    /// English (US): "1,234,567.89", "$100", "50%"
+    #[default]
+    EnUs,
+    /// English (UK): "1,234,567.89", "£100", "50%"
+    EnGb,
+    /// Chinese (Mainland): "1,234,567.89", "¥100", "50%"
+    ZhCn,
+    /// Chinese (Taiwan): "1,234,567.89", "$100", "50%"
+    ZhTw,
+    /// Japanese: "1,234,567.89", "¥100", "50%"
+    JaJp,
+    /// German: "1.234.567,89", "100 €", "50 %"
+    DeDe,
+    /// French: "1 234 567,89" with narrow no-break spaces, "100 €", "50 %"
+    FrFr,
+}
+
+impl Locale {
+    /// Separator between groups of three integer digits
+    pub fn group_separator(self) -> char {
+        match self {
+            Locale::DeDe => '.',
+            Locale::FrFr => '\u{202F}',
+            _ => ',',
+        }
+    }
+
+    /// Separator between the integer and fractional digits
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Locale::DeDe | Locale::FrFr => ',',
+            _ => '.',
+        }
+    }
+
+    /// Placement of currency symbols
+    pub fn currency_placement(self) -> CurrencyPlacement {
+        match self {
+            Locale::DeDe | Locale::FrFr => CurrencyPlacement::After,
+            _ => CurrencyPlacement::Before,
+        }
+    }
+
+    /// Spacing before percent signs
+    pub fn percent_spacing(self) -> PercentSpacing {
+        match self {
+            Locale::DeDe | Locale::FrFr => PercentSpacing::Spaced,
+            _ => PercentSpacing::Tight,
+        }
+    }
+}
+
+/// Insert `separator` between groups of three digits
+///
+/// `digits` must be ASCII digits.
+pub fn group_digits(digits: &str, separator: char) -> String {
+    let (head, rest) = digits.split_at(match digits.len() % 3 {
+        0 => digits.len().min(3),
+        n => n,
+    });
+    let mut output = head.to_string();
+    for chunk in rest.as_bytes().chunks(3) {
+        output.push(separator);
+        output.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+    }
+    output
+}
+
+/// Format a plain decimal string ("-1234567.89") in a locale
+fn format_digits(number: &str, locale: Locale) -> String {
+    let (sign, number) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number),
+    };
+    let (integer, fraction) = match number.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (number, None),
+    };
+    let mut output = format!(
+        "{}{}",
+        sign,
+        group_digits(integer, locale.group_separator())
+    );
+    if let Some(fraction) = fraction {
+        output.push(locale.decimal_separator());
+        output.push_str(fraction);
+    }
+    output
+}
+
+/// Format a number with the digit grouping and decimal separator of a locale
+///
+/// Fractional digits are written as few as needed to round-trip the value.
+///
+/// # Example
+/// ```rust
+/// use wetext_rs::format::{format_number, Locale};
+///
+/// assert_eq!(format_number(1234567.89, Locale::ZhCn), "1,234,567.89");
+/// assert_eq!(format_number(1234567.89, Locale::DeDe), "1.234.567,89");
+/// ```
+pub fn format_number(value: f64, locale: Locale) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    format_digits(&value.to_string(), locale)
+}
+
+/// Format a currency amount with the symbol placement of a locale
+///
+/// Amounts with a fractional part are written with two decimals.
+pub fn format_currency(amount: f64, symbol: &str, locale: Locale) -> String {
+    let number = if !amount.is_finite() {
+        amount.to_string()
+    } else if amount.fract() == 0.0 {
+        format_digits(&amount.to_string(), locale)
+    } else {
+        format_digits(&format!("{:.2}", amount), locale)
+    };
+    match locale.currency_placement() {
+        CurrencyPlacement::After => format!("{} {}", number, symbol),
+        _ => format!("{}{}", symbol, number),
+    }
+}
+
+/// Format a percentage with the percent spacing of a locale
+pub fn format_percent(value: f64, locale: Locale) -> String {
+    match locale.percent_spacing() {
+        PercentSpacing::Spaced => format!("{} %", format_number(value, locale)),
+        _ => format!("{}%", format_number(value, locale)),
+    }
+}
+
+impl Beautify {
+    /// Beautifier options following the conventions of a locale
+    pub fn for_locale(locale: Locale) -> Self {
+        Self {
+            thousands_separator: Some(locale.group_separator()),
+            decimal_separator: locale.decimal_separator(),
+            currency: locale.currency_placement(),
+            percent: locale.percent_spacing(),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1234567.89, Locale::ZhCn), "1,234,567.89");
+        assert_eq!(format_number(-1234.0, Locale::EnUs), "-1,234");
+        assert_eq!(format_number(0.5, Locale::DeDe), "0,5");
+        assert_eq!(
+            format_number(1234567.89, Locale::FrFr),
+            "1\u{202F}234\u{202F}567,89"
+        );
+        assert_eq!(format_number(f64::NAN, Locale::EnUs), "NaN");
+    }
+
+    #[test]
+    fn test_format_currency_and_percent() {
+        assert_eq!(format_currency(1234.5, "$", Locale::EnUs), "$1,234.50");
+        assert_eq!(format_currency(100.0, "€", Locale::DeDe), "100 €");
+        assert_eq!(format_percent(12.5, Locale::ZhCn), "12.5%");
+        assert_eq!(format_percent(50.0, Locale::FrFr), "50 %");
+    }
+}
@@ -0,0 +1,165 @@
+//! Text front-end for TTS engines
+//!
+//! A TTS engine needs the spoken text, the words to look up pronunciations
+//! for, and which words came from entities such as dates or money (to pick
+//! prosody). `TtsFrontend` produces all three from one normalization pass.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error::Result;
+use crate::normalizer::Normalizer;
+
+/// An entity in the normalized text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntitySpan {
+    /// Token type (e.g., "date", "money")
+    pub label: String,
+    /// Byte offset of the span start in the normalized text
+    pub start: usize,
+    /// Byte offset of the span end (exclusive) in the normalized text
+    pub end: usize,
+    /// Written form of the entity in the input
+    pub original: String,
+}
+
+/// A word in the normalized text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word {
+    /// Text of the word
+    pub text: String,
+    /// Byte offset of the word start in the normalized text
+    pub start: usize,
+    /// Byte offset of the word end (exclusive) in the normalized text
+    pub end: usize,
+    /// Index into `FrontendOutput::entities` of the entity containing the word
+    pub entity: Option<usize>,
+}
+
+/// Result of `TtsFrontend::process`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FrontendOutput {
+    /// Normalized text
+    pub text: String,
+    /// Words of `text`, in order
+    pub words: Vec<Word>,
+    /// Entities of `text`, in order
+    pub entities: Vec<EntitySpan>,
+}
+
+/// Normalization, word segmentation and entity labels in one call
+///
+/// Words follow Unicode word boundaries (UAX #29). No dictionary is
+/// bundled, so CJK text is split into single characters. Like
+/// `Normalizer::normalize_tokens`, postprocessing stages are not applied.
+pub struct TtsFrontend {
+    normalizer: Normalizer,
+}
+
+impl TtsFrontend {
+    /// Create a front-end around a normalizer
+    pub fn new(normalizer: Normalizer) -> Self {
+        Self { normalizer }
+    }
+
+    /// The wrapped normalizer
+    pub fn normalizer(&mut self) -> &mut Normalizer {
+        &mut self.normalizer
+    }
+
+    /// Consume the front-end and return the normalizer
+    pub fn into_inner(self) -> Normalizer {
+        self.normalizer
+    }
+
+    /// Normalize text and segment it into words and entities
+    pub fn process(&mut self, text: &str) -> Result<FrontendOutput> {
+        let mut output = FrontendOutput::default();
+        for segment in self.normalizer.normalize_tokens(text)? {
+            let start = output.text.len();
+            output.text.push_str(&segment.text);
+            if let Some(label) = segment.category {
+                output.entities.push(EntitySpan {
+                    label,
+                    start,
+                    end: output.text.len(),
+                    original: segment.original,
+                });
+            }
+        }
+
+        output.words = output
+            .text
+            .split_word_bound_indices()
+            .filter(|(_, word)| word.chars().any(char::is_alphanumeric))
+            .map(|(start, word)| {
+                let end = start + word.len();
+                Word {
+                    text: word.to_string(),
+                    start,
+                    end,
+                    entity: output
+                        .entities
+                        .iter()
+                        .position(|entity| entity.start <= start && end <= entity.end),
+                }
+            })
+            .collect();
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustfst::fst_traits::SerializableFst;
+
+    use crate::config::{Language, NormalizerConfig};
+
+    #[test]
+    fn test_process() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace("共5", r#"char { value: "共" } money { value: "5" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"money { value: "5" }"#, "五元")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut frontend = TtsFrontend::new(Normalizer::new(dir.path(), config));
+        let output = frontend.process("共5").unwrap();
+        assert_eq!(output.text, "共五元");
+        assert_eq!(
+            output.entities,
+            [EntitySpan {
+                label: "money".to_string(),
+                start: 3,
+                end: 9,
+                original: "5".to_string(),
+            }]
+        );
+        let words: Vec<(&str, Option<usize>)> = output
+            .words
+            .iter()
+            .map(|word| (word.text.as_str(), word.entity))
+            .collect();
+        assert_eq!(words, [("共", None), ("五", Some(0)), ("元", Some(0))]);
+    }
+
+    #[test]
+    fn test_process_plain_text() {
+        // Text without digits passes through TN without loading any FST
+        let mut frontend = TtsFrontend::new(Normalizer::with_defaults("missing"));
+        let output = frontend.process("Hello, world!").unwrap();
+        assert_eq!(output.text, "Hello, world!");
+        assert!(output.entities.is_empty());
+        let words: Vec<&str> = output.words.iter().map(|word| word.text.as_str()).collect();
+        assert_eq!(words, ["Hello", "world"]);
+        assert_eq!(output.words[1].start, 7);
+    }
+}
@@ -0,0 +1,123 @@
+//! Number glyph folding
+//!
+//! Circled numbers (①), superscripts (¹²³) and vulgar fractions (½) are
+//! single characters the FST grammars don't match, so they pass through
+//! and are then dropped by most TTS engines. This stage rewrites them into
+//! digits the taggers understand.
+
+use crate::config::Language;
+
+const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+/// Vulgar fractions as (glyph, numerator, denominator)
+const FRACTIONS: &[(char, u32, u32)] = &[
+    ('½', 1, 2),
+    ('⅓', 1, 3),
+    ('⅔', 2, 3),
+    ('¼', 1, 4),
+    ('¾', 3, 4),
+    ('⅕', 1, 5),
+    ('⅖', 2, 5),
+    ('⅗', 3, 5),
+    ('⅘', 4, 5),
+    ('⅙', 1, 6),
+    ('⅚', 5, 6),
+    ('⅐', 1, 7),
+    ('⅛', 1, 8),
+    ('⅜', 3, 8),
+    ('⅝', 5, 8),
+    ('⅞', 7, 8),
+    ('⅑', 1, 9),
+    ('⅒', 1, 10),
+];
+
+/// Value of a circled or otherwise enclosed number
+fn enclosed_number(c: char) -> Option<u32> {
+    let c = c as u32;
+    let value = match c {
+        0x2460..=0x2473 => c - 0x2460 + 1,  // ①-⑳
+        0x24ea => 0,                        // ⓪
+        0x3251..=0x325f => c - 0x3251 + 21, // ㉑-㉟
+        0x32b1..=0x32bf => c - 0x32b1 + 36, // ㊱-㊿
+        0x2776..=0x277f => c - 0x2776 + 1,  // ❶-❿
+        0x2780..=0x2789 => c - 0x2780 + 1,  // ➀-➉
+        0x278a..=0x2793 => c - 0x278a + 1,  // ➊-➓
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Rewrite number glyphs into digits
+///
+/// Enclosed numbers become plain numbers, vulgar fractions become "n/d"
+/// (joined to a preceding whole number with "又" or "and"), superscripts
+/// after a digit become a power ("10⁵" -> "10的5次方"), and other
+/// superscripts become plain digits. Superscripts after a letter are units
+/// such as "m²" and are left alone.
+pub(crate) fn fold_number_glyphs(text: &str, lang: Language) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let prev = output.chars().last();
+
+        if let Some(n) = enclosed_number(c) {
+            output.push_str(&n.to_string());
+        } else if let Some(&(_, num, den)) = FRACTIONS.iter().find(|(f, ..)| *f == c) {
+            if prev.is_some_and(|p| p.is_ascii_digit()) {
+                output.push_str(match lang {
+                    Language::En => " and ",
+                    _ => "又",
+                });
+            }
+            output.push_str(&format!("{}/{}", num, den));
+        } else if SUPERSCRIPTS.contains(&c) {
+            if prev.is_some_and(char::is_alphabetic) {
+                output.push(c);
+                continue;
+            }
+            let mut exponent = superscript_digit(c).to_string();
+            while let Some(&next) = chars.peek() {
+                if !SUPERSCRIPTS.contains(&next) {
+                    break;
+                }
+                exponent.push(superscript_digit(next));
+                chars.next();
+            }
+            if prev.is_some_and(|p| p.is_ascii_digit()) {
+                output.push_str(&match lang {
+                    Language::En => format!(" to the power of {}", exponent),
+                    Language::Ja => format!("の{}乗", exponent),
+                    Language::Zh | Language::Auto => format!("的{}次方", exponent),
+                });
+            } else {
+                output.push_str(&exponent);
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+fn superscript_digit(c: char) -> char {
+    let index = SUPERSCRIPTS.iter().position(|&s| s == c).unwrap_or(0);
+    char::from_digit(index as u32, 10).unwrap_or('0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_number_glyphs() {
+        assert_eq!(fold_number_glyphs("第②章", Language::Zh), "第2章");
+        assert_eq!(fold_number_glyphs("㉑", Language::Zh), "21");
+        assert_eq!(fold_number_glyphs("3½杯", Language::Zh), "3又1/2杯");
+        assert_eq!(fold_number_glyphs("¾ cup", Language::En), "3/4 cup");
+        assert_eq!(
+            fold_number_glyphs("10¹² bytes", Language::En),
+            "10 to the power of 12 bytes"
+        );
+        assert_eq!(fold_number_glyphs("5m²", Language::Zh), "5m²");
+    }
+}
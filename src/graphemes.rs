@@ -0,0 +1,75 @@
+//! Grapheme-cluster protection around the FST pipeline
+//!
+//! The FSTs work on UTF-8 bytes and know nothing about grapheme clusters.
+//! An emoji ZWJ sequence, a skin-tone modifier or a combining mark the
+//! grammar doesn't cover makes composition fail for the whole input, and
+//! char tokens can split a cluster apart. Such clusters are cut out and
+//! copied through unchanged while the text around them is normalized.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Whether a grapheme cluster should bypass the FSTs
+///
+/// Multi-code-point clusters and characters outside the Basic Multilingual
+/// Plane (emoji, rare CJK extensions) are protected.
+fn is_protected(cluster: &str) -> bool {
+    let mut chars = cluster.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => c as u32 > 0xffff,
+        (Some(_), Some(_)) => cluster != "\r\n",
+        _ => false,
+    }
+}
+
+/// Split text into runs of plain text and protected clusters
+///
+/// Returns `(protected, segment)` pairs in order; consecutive clusters of
+/// the same kind are merged.
+pub(crate) fn split_protected(text: &str) -> Vec<(bool, &str)> {
+    let mut segments: Vec<(bool, &str)> = Vec::new();
+    let mut start = 0;
+    let mut current: Option<bool> = None;
+
+    for (offset, cluster) in text.grapheme_indices(true) {
+        let protected = is_protected(cluster);
+        match current {
+            Some(kind) if kind != protected => {
+                segments.push((kind, &text[start..offset]));
+                start = offset;
+            }
+            _ => {}
+        }
+        current = Some(protected);
+    }
+    if let Some(kind) = current {
+        segments.push((kind, &text[start..]));
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_protected() {
+        let family = "👨\u{200d}👩\u{200d}👧";
+        let text = format!("我有3个{}家人👍🏽", family);
+        assert_eq!(
+            split_protected(&text),
+            vec![
+                (false, "我有3个"),
+                (true, family),
+                (false, "家人"),
+                (true, "👍🏽")
+            ]
+        );
+
+        // Combining marks stay with their base character
+        assert_eq!(
+            split_protected("cafe\u{301} 1"),
+            vec![(false, "caf"), (true, "e\u{301}"), (false, " 1")]
+        );
+        assert_eq!(split_protected("plain 123"), vec![(false, "plain 123")]);
+    }
+}
@@ -0,0 +1,1017 @@
+//! Built-in rule handlers for the hybrid engine
+//!
+//! Some entity classes (phone numbers, URLs, email addresses, mixed
+//! alphanumeric words, ordinals, weekdays) are easy to
+//! recognize and verbalize with plain Rust rules, and the FST grammars often
+//! read them poorly (e.g. a phone number as one big cardinal). When a class is
+//! enabled in the configuration, matching spans are verbalized here and the
+//! remaining text flows through the FST pipeline as usual.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::config::{
+    DurationReading, FractionReading, Language, NormalizerConfig, Operator, PercentReading,
+    PhoneGrouping,
+};
+use crate::numbers::{cjk_digit, digit_word, num_to_words, words_to_num, Style};
+
+/// Entity class handled by built-in rules instead of the FST
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityClass {
+    /// Phone numbers, read digit by digit (e.g., "138-0013-8000")
+    Phone,
+    /// URLs starting with `http://`, `https://` or `www.`
+    Url,
+    /// Email addresses
+    Email,
+    /// Words mixing letters and digits (e.g., "MP3", "5G", "B2B"), read as
+    /// a blend: "MP三", "five G"
+    Alphanumeric,
+    /// Chinese and Japanese ordinals ("第3名", "3番目"), read as "第三名" and
+    /// "三番目"; ITN writes them back with digits, which the ITN grammars
+    /// leave alone for single digits
+    Ordinal,
+    /// Weekdays: "Wed." and "周3" are read as "Wednesday" and "周三"; ITN
+    /// writes spoken weekdays as abbreviations ("wednesday" -> "Wed.",
+    /// "星期三" -> "周三")
+    Weekday,
+}
+
+impl EntityClass {
+    /// Pattern used to detect spans of this class
+    fn pattern(self, operator: Operator) -> &'static Regex {
+        match self {
+            EntityClass::Phone => &PHONE_RE,
+            EntityClass::Url => &URL_RE,
+            EntityClass::Email => &EMAIL_RE,
+            EntityClass::Alphanumeric => &ALNUM_RE,
+            EntityClass::Ordinal if operator == Operator::Itn => &SPOKEN_ORDINAL_RE,
+            EntityClass::Ordinal => &ORDINAL_RE,
+            EntityClass::Weekday if operator == Operator::Itn => &SPOKEN_WEEKDAY_RE,
+            EntityClass::Weekday => &WEEKDAY_RE,
+        }
+    }
+
+    /// Whether built-in rules handle this class for `operator`
+    fn handles(self, operator: Operator) -> bool {
+        operator == Operator::Tn || matches!(self, EntityClass::Ordinal | EntityClass::Weekday)
+    }
+
+    /// Matching priority: URLs and emails contain digit runs that would
+    /// otherwise be picked up as phone numbers
+    fn priority(self) -> u8 {
+        match self {
+            EntityClass::Url => 0,
+            EntityClass::Email => 1,
+            EntityClass::Phone => 2,
+            EntityClass::Alphanumeric => 3,
+            EntityClass::Ordinal => 4,
+            EntityClass::Weekday => 5,
+        }
+    }
+}
+
+static PHONE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[- ]?)?(?:\d{3,4}-\d{3,4}-\d{3,4}|\(\d{3}\) ?\d{3}-\d{4}|1[3-9]\d{9})")
+        .unwrap()
+});
+
+static URL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(?:https?://|www\.)[A-Za-z0-9\-._~:/?#@!$&'*+,;=%]+").unwrap());
+
+static EMAIL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}").unwrap());
+
+// ASCII word boundaries, so that "支持5G网络" still matches "5G"
+static ALNUM_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?-u:\b)(?:[A-Za-z]+[0-9]|[0-9]+[A-Za-z])[A-Za-z0-9]*(?-u:\b)").unwrap()
+});
+
+static ORDINAL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"第[0-9]+|[0-9]+番目").unwrap());
+
+static SPOKEN_ORDINAL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"第[零〇一二两三四五六七八九十百千万]+|[〇一二三四五六七八九十百千万]+番目")
+        .unwrap()
+});
+
+/// English weekday abbreviations ending in a period or comma, and Chinese
+/// weekdays written with a digit
+static WEEKDAY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?-u:\b)(?:Mon|Tues?|Wed|Thu(?:rs?)?|Fri|Sat|Sun)[.,]|(?:周|星期|礼拜)[1-7](?-u:\b)",
+    )
+    .unwrap()
+});
+
+static SPOKEN_WEEKDAY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:mon|tues|wednes|thurs|fri|satur|sun)day\b|(?:星期|礼拜)[一二三四五六日天]",
+    )
+    .unwrap()
+});
+
+const EN_WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+const ZH_WEEKDAYS: [&str; 7] = ["一", "二", "三", "四", "五", "六", "日"];
+
+const JA_WEEKDAYS: [&str; 7] = ["月", "火", "水", "木", "金", "土", "日"];
+
+/// Percentages and permille values, with an optional sign
+static PERCENT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(-?)([0-9]+(?:\.[0-9]+)?) ?([%％‰])").unwrap());
+
+/// Fractions with an optional whole part ("3/4", "1 1/2", "3又1/2")
+static FRACTION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:([0-9]+)(?: |又))?([0-9]+)/([0-9]+)").unwrap());
+
+/// Hour, minute and second durations ("3小时20分钟", "3分20秒", "2時間30分")
+static DURATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:([0-9]+)个?(小时|小時|時間))?(?:([0-9]+)(分钟|分鐘|分))?(?:([0-9]+)(秒钟|秒鐘|秒))?",
+    )
+    .unwrap()
+});
+
+/// Abbreviated English durations ("1h 20m", "5 min 30 sec")
+static EN_DURATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?-u:\b)(?:([0-9]+) ?(hours?|hrs?|h)(?-u:\b) ?)?",
+        r"(?:([0-9]+) ?(minutes?|mins?|m)(?-u:\b) ?)?",
+        r"(?:([0-9]+) ?(seconds?|secs?|s)(?-u:\b))?",
+    ))
+    .unwrap()
+});
+
+/// Built-in English readings of alphanumeric words that aren't read as blends
+const EN_ALNUM_READINGS: &[(&str, &str)] = &[
+    ("B2B", "B to B"),
+    ("B2C", "B to C"),
+    ("C2C", "C to C"),
+    ("P2P", "P to P"),
+];
+
+/// A span of the input handled by a built-in rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RuleSpan {
+    /// Byte offset of the span start
+    pub start: usize,
+    /// Byte offset of the span end (exclusive)
+    pub end: usize,
+    /// Spoken form of the span
+    pub spoken: String,
+}
+
+/// Whether any class enabled in `config` is handled for its operator
+pub(crate) fn is_active(config: &NormalizerConfig) -> bool {
+    config
+        .rule_classes
+        .iter()
+        .any(|class| class.handles(config.operator))
+        || (config.operator == Operator::Tn
+            && (config.percent_reading != PercentReading::AsTagged
+                || config.fraction_reading != FractionReading::AsTagged
+                || config.duration_reading != DurationReading::AsTagged))
+}
+
+/// Find all non-overlapping spans matched by the entity classes enabled in `config`
+///
+/// Spans are returned sorted by start offset.
+pub(crate) fn find_spans(text: &str, lang: Language, config: &NormalizerConfig) -> Vec<RuleSpan> {
+    let mut classes = config.rule_classes.clone();
+    classes.retain(|class| class.handles(config.operator));
+    classes.sort_by_key(|c| c.priority());
+    classes.dedup();
+
+    let mut spans: Vec<RuleSpan> = Vec::new();
+    for class in classes {
+        for m in class.pattern(config.operator).find_iter(text) {
+            let overlaps = spans.iter().any(|s| m.start() < s.end && s.start < m.end());
+            if overlaps {
+                continue;
+            }
+            // The regex crate has no lookaround: digits inside a longer run
+            // (an order number, an ID) are not a phone number
+            if class == EntityClass::Phone && digit_around(text, m.start(), m.end()) {
+                continue;
+            }
+            // Trailing sentence punctuation is not part of a URL; the period
+            // of a weekday abbreviation is, unless it also ends the text
+            let matched = match class {
+                EntityClass::Weekday if m.end() < text.len() => m.as_str().trim_end_matches(','),
+                _ => m.as_str().trim_end_matches(['.', ',', '!', '?', ';', ':']),
+            };
+            spans.push(RuleSpan {
+                start: m.start(),
+                end: m.start() + matched.len(),
+                spoken: match config.alnum_readings.get(matched) {
+                    Some(spoken) if class == EntityClass::Alphanumeric => spoken.clone(),
+                    _ => verbalize(class, matched, lang, config),
+                },
+            });
+        }
+    }
+
+    if config.operator == Operator::Tn && config.percent_reading != PercentReading::AsTagged {
+        for caps in PERCENT_RE.captures_iter(text) {
+            let (Some(m), Some(sign), Some(number), Some(symbol)) =
+                (caps.get(0), caps.get(1), caps.get(2), caps.get(3))
+            else {
+                continue;
+            };
+            // A dash after a digit is a range ("50-60%"), not a sign
+            let negative = !sign.is_empty()
+                && !text[..sign.start()]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_ascii_digit());
+            let start = if negative { m.start() } else { number.start() };
+            if spans.iter().any(|s| start < s.end && s.start < m.end()) {
+                continue;
+            }
+            spans.push(RuleSpan {
+                start,
+                end: m.end(),
+                spoken: read_percent(
+                    number.as_str(),
+                    negative,
+                    symbol.as_str() == "‰",
+                    lang,
+                    config.percent_reading,
+                ),
+            });
+        }
+    }
+
+    if config.operator == Operator::Tn && config.fraction_reading != FractionReading::AsTagged {
+        for caps in FRACTION_RE.captures_iter(text) {
+            let Some(m) = caps.get(0) else {
+                continue;
+            };
+            // Dates ("3/4/2024") and paths are not fractions
+            let before = text[..m.start()].chars().next_back();
+            let after = text[m.end()..].chars().next();
+            if matches!(before, Some('/' | '.'))
+                || before.is_some_and(|c| c.is_ascii_digit())
+                || matches!(after, Some('/' | '.'))
+                || after.is_some_and(|c| c.is_ascii_digit())
+            {
+                continue;
+            }
+            if spans.iter().any(|s| m.start() < s.end && s.start < m.end()) {
+                continue;
+            }
+            let whole = caps.get(1).map(|whole| whole.as_str());
+            let Some(spoken) =
+                read_fraction(whole, &caps[2], &caps[3], lang, config.fraction_reading)
+            else {
+                continue;
+            };
+            spans.push(RuleSpan {
+                start: m.start(),
+                end: m.end(),
+                spoken,
+            });
+        }
+    }
+
+    if config.operator == Operator::Tn && config.duration_reading != DurationReading::AsTagged {
+        for re in [&*DURATION_RE, &*EN_DURATION_RE] {
+            for caps in re.captures_iter(text) {
+                let Some(m) = caps.get(0) else {
+                    continue;
+                };
+                let end = m.start() + m.as_str().trim_end().len();
+                let amounts = [1, 3, 5].map(|i| caps.get(i).map(|amount| amount.as_str()));
+                let units: Vec<&str> = [2, 4, 6]
+                    .into_iter()
+                    .filter_map(|i| caps.get(i).map(|unit| unit.as_str()))
+                    .collect();
+                // A lone "分" is a score or a cent, a lone "m" or "s" a unit
+                // of length or time that the grammar reads
+                if matches!(units[..], [] | ["分" | "m" | "s"]) {
+                    continue;
+                }
+                // Minutes of a clock time ("3点20分") and parts of decimals
+                let mut preceding = text[..m.start()].chars().rev();
+                let before = preceding.next();
+                let clock = matches!(before, Some('点' | '點' | '时' | '時'))
+                    && preceding
+                        .next()
+                        .is_some_and(|c| c.is_ascii_digit() || c == '十' || cjk_digit(c).is_some());
+                let after = text[end..].chars().next();
+                if clock
+                    || matches!(before, Some('.' | ':'))
+                    || before.is_some_and(|c| c.is_ascii_digit())
+                    || matches!(after, Some('之' | '.' | ':'))
+                    || after.is_some_and(|c| c.is_ascii_digit())
+                {
+                    continue;
+                }
+                if spans.iter().any(|s| m.start() < s.end && s.start < end) {
+                    continue;
+                }
+                spans.push(RuleSpan {
+                    start: m.start(),
+                    end,
+                    spoken: read_duration(amounts, lang, config.duration_reading),
+                });
+            }
+        }
+    }
+
+    spans.sort_by_key(|s| s.start);
+    spans
+}
+
+/// Verbalize a matched span
+///
+/// With a pause marker, phone numbers are read in digit groups separated by
+/// the marker.
+fn verbalize(class: EntityClass, text: &str, lang: Language, config: &NormalizerConfig) -> String {
+    let pause = config.pause_marker.as_deref();
+    let words: Vec<String> = match class {
+        EntityClass::Phone => {
+            let groups: Vec<String> = phone_groups(text, config.phone_grouping)
+                .iter()
+                .map(|group| read_phone_group(group, lang))
+                .collect();
+            return match (pause, lang) {
+                (Some(pause), Language::En) => groups.join(&format!(" {} ", pause)),
+                (Some(pause), _) => groups.join(pause),
+                (None, Language::En) => groups.join(" "),
+                (None, _) => groups.concat(),
+            };
+        }
+        EntityClass::Url | EntityClass::Email => spell_address(text, lang),
+        EntityClass::Alphanumeric => return read_alphanumeric(text, lang),
+        EntityClass::Ordinal => return read_ordinal(text, lang, config.operator),
+        EntityClass::Weekday => return read_weekday(text, lang, config.operator),
+    };
+
+    match lang {
+        Language::En => words.join(" "),
+        _ => words.concat(),
+    }
+}
+
+/// Read a mixed alphanumeric word as a blend of letters and numbers
+///
+/// Digit runs of up to two digits are read as numbers and longer runs digit
+/// by digit. English spells out upper-case letter runs ("M P three") and
+/// keeps other runs as words; Chinese and Japanese keep letters as written.
+fn read_alphanumeric(text: &str, lang: Language) -> String {
+    if lang == Language::En {
+        if let Some((_, spoken)) = EN_ALNUM_READINGS.iter().find(|(w, _)| *w == text) {
+            return spoken.to_string();
+        }
+    }
+
+    let mut words: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let digits = c.is_ascii_digit();
+        let len = rest
+            .find(|c: char| c.is_ascii_digit() != digits)
+            .unwrap_or(rest.len());
+        let (run, tail) = rest.split_at(len);
+        rest = tail;
+
+        if digits {
+            let style = if run.len() <= 2 {
+                Style::Cardinal
+            } else {
+                Style::Digits
+            };
+            words.push(num_to_words(run, lang, style).unwrap_or_else(|_| run.to_string()));
+        } else if lang == Language::En
+            && (run.len() <= 2 || run.chars().all(|c| c.is_ascii_uppercase()))
+        {
+            words.extend(run.chars().map(|c| c.to_ascii_uppercase().to_string()));
+        } else {
+            words.push(run.to_string());
+        }
+    }
+
+    match lang {
+        Language::En => words.join(" "),
+        _ => words.concat(),
+    }
+}
+
+/// Read a percentage or permille value
+fn read_percent(
+    number: &str,
+    negative: bool,
+    permille: bool,
+    lang: Language,
+    reading: PercentReading,
+) -> String {
+    let value = num_to_words(number, lang, Style::Cardinal).unwrap_or_else(|_| number.to_string());
+    let english = if permille { "per mille" } else { "percent" };
+    let spoken = match (lang, reading) {
+        (Language::En, _) => format!("{} {}", value, english),
+        (_, PercentReading::LoanWord) => format!("{}{}", value, english),
+        (Language::Ja, _) => {
+            let word = if permille {
+                "パーミル"
+            } else {
+                "パーセント"
+            };
+            format!("{}{}", value, word)
+        }
+        _ => {
+            let word = if permille { "千分之" } else { "百分之" };
+            format!("{}{}", word, value)
+        }
+    };
+    if !negative {
+        return spoken;
+    }
+    let minus = match lang {
+        Language::En => "minus ",
+        Language::Ja => "マイナス",
+        _ => "负",
+    };
+    format!("{}{}", minus, spoken)
+}
+
+/// Read hour, minute and second amounts of a duration
+fn read_duration(amounts: [Option<&str>; 3], lang: Language, reading: DurationReading) -> String {
+    let units: [(&str, &str); 3] = match (lang, reading) {
+        (Language::En, _) => [
+            ("hour", "hours"),
+            ("minute", "minutes"),
+            ("second", "seconds"),
+        ],
+        (Language::Ja, _) => [("時間", ""), ("分", ""), ("秒", "")],
+        (_, DurationReading::Full) => [("小时", ""), ("分钟", ""), ("秒", "")],
+        _ => [("小时", ""), ("分", ""), ("秒", "")],
+    };
+    let parts: Vec<String> = amounts
+        .iter()
+        .zip(units)
+        .filter_map(|(amount, (singular, plural))| {
+            let amount = (*amount)?;
+            let value = match (lang, amount) {
+                (Language::Zh, "2") => "两".to_string(),
+                _ => num_to_words(amount, lang, Style::Cardinal)
+                    .unwrap_or_else(|_| amount.to_string()),
+            };
+            Some(match lang {
+                Language::En if amount.parse() == Ok(1) => format!("{} {}", value, singular),
+                Language::En => format!("{} {}", value, plural),
+                _ => format!("{}{}", value, singular),
+            })
+        })
+        .collect();
+
+    match (lang, reading, parts.split_last()) {
+        (Language::En, DurationReading::Full, Some((last, rest))) if !rest.is_empty() => {
+            format!("{} and {}", rest.join(" "), last)
+        }
+        (Language::En, _, _) => parts.join(" "),
+        _ => parts.concat(),
+    }
+}
+
+/// Read a fraction with an optional whole part
+fn read_fraction(
+    whole: Option<&str>,
+    numerator: &str,
+    denominator: &str,
+    lang: Language,
+    reading: FractionReading,
+) -> Option<String> {
+    let cardinal = |n: &str| num_to_words(n, lang, Style::Cardinal).ok();
+    let count: u64 = numerator.parse().ok()?;
+    let parts: u64 = denominator.parse().ok()?;
+    if parts == 0 {
+        return None;
+    }
+    let whole = whole.map(cardinal).map(Option::unwrap_or_default);
+
+    if lang != Language::En {
+        let (of, and) = match lang {
+            Language::Ja => ("分の", "と"),
+            _ => ("分之", "又"),
+        };
+        let fraction = format!("{}{}{}", cardinal(denominator)?, of, cardinal(numerator)?);
+        return Some(match whole {
+            Some(whole) => format!("{}{}{}", whole, and, fraction),
+            None => fraction,
+        });
+    }
+
+    let (singular, plural) = match (parts, reading) {
+        (2, _) => ("half".to_string(), "halves".to_string()),
+        (4, FractionReading::Quarters) => ("quarter".to_string(), "quarters".to_string()),
+        _ => {
+            let ordinal = num_to_words(parts, lang, Style::Ordinal).ok()?;
+            (ordinal.clone(), format!("{}s", ordinal))
+        }
+    };
+    let fraction = match count {
+        // "one and a half" for quarters, "one and one half" for fourths
+        1 if whole.is_some() && reading == FractionReading::Quarters => {
+            format!("a {}", singular)
+        }
+        1 => format!("one {}", singular),
+        _ => format!("{} {}", cardinal(numerator)?, plural),
+    };
+    Some(match whole {
+        Some(whole) => format!("{} and {}", whole, fraction),
+        None => fraction,
+    })
+}
+
+/// Read an ordinal, or write a spoken one with digits for ITN
+fn read_ordinal(text: &str, lang: Language, operator: Operator) -> String {
+    let (prefix, number, suffix) = match text.strip_prefix('第') {
+        Some(number) => ("第", number, ""),
+        None => ("", text.trim_end_matches("番目"), "番目"),
+    };
+    let number = match operator {
+        Operator::Tn => num_to_words(number, lang, Style::Cardinal).ok(),
+        Operator::Itn => words_to_num(number, lang)
+            .ok()
+            .and_then(|n| n.as_i64())
+            .map(|n| n.to_string()),
+    };
+    match number {
+        Some(number) => format!("{}{}{}", prefix, number, suffix),
+        None => text.to_string(),
+    }
+}
+
+/// Read a weekday, or write a spoken one as an abbreviation for ITN
+///
+/// English abbreviations are read in the language of the text ("Wed." is
+/// "星期三" in Chinese); Chinese weekdays keep the word they are written with.
+fn read_weekday(text: &str, lang: Language, operator: Operator) -> String {
+    let chinese = ["周", "星期", "礼拜"]
+        .into_iter()
+        .find_map(|prefix| Some((prefix, text.strip_prefix(prefix)?)));
+    let (prefix, index) = match chinese {
+        Some((prefix, day)) => {
+            let index = match day {
+                "日" | "天" => Some(6),
+                _ => day
+                    .parse::<usize>()
+                    .ok()
+                    .map(|n| n - 1)
+                    .or_else(|| ZH_WEEKDAYS.iter().position(|d| *d == day)),
+            };
+            (Some(prefix), index)
+        }
+        None => {
+            let name = text.trim_end_matches(['.', ',']).to_ascii_lowercase();
+            (None, EN_WEEKDAYS.iter().position(|d| d.starts_with(&name)))
+        }
+    };
+    let Some(index) = index else {
+        return text.to_string();
+    };
+
+    match (operator, prefix) {
+        (Operator::Itn, Some(_)) => format!("周{}", ZH_WEEKDAYS[index]),
+        (Operator::Itn, None) => {
+            let name = EN_WEEKDAYS[index];
+            format!("{}{}.", name[..1].to_ascii_uppercase(), &name[1..3])
+        }
+        (Operator::Tn, Some(prefix)) => format!("{}{}", prefix, ZH_WEEKDAYS[index]),
+        (Operator::Tn, None) => match lang {
+            Language::Zh => format!("星期{}", ZH_WEEKDAYS[index]),
+            Language::Ja => format!("{}曜日", JA_WEEKDAYS[index]),
+            _ => {
+                let name = EN_WEEKDAYS[index];
+                format!("{}{}", name[..1].to_ascii_uppercase(), &name[1..])
+            }
+        },
+    }
+}
+
+/// Whether a digit directly precedes `start` or follows `end` in `text`
+fn digit_around(text: &str, start: usize, end: usize) -> bool {
+    text[..start]
+        .chars()
+        .next_back()
+        .is_some_and(|c| c.is_ascii_digit())
+        || text[end..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit())
+}
+
+/// Split an address into words: letter runs are kept, digits and symbols are read out
+fn spell_address(text: &str, lang: Language) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut run = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphabetic() {
+            run.push(c);
+            continue;
+        }
+        if !run.is_empty() {
+            words.push(std::mem::take(&mut run));
+        }
+        if c.is_ascii_digit() {
+            words.push(digit_word(c, lang).to_string());
+        } else {
+            let word = symbol_word(c, lang);
+            if word.is_empty() {
+                words.push(c.to_string());
+            } else {
+                words.push(word.to_string());
+            }
+        }
+    }
+    if !run.is_empty() {
+        words.push(run);
+    }
+    words
+}
+
+/// Split a phone number into digit groups
+///
+/// Separators in the text are kept as group boundaries; with
+/// `PhoneGrouping::ByLength` an unseparated run is grouped 3-4-4 for mobile
+/// numbers, 3-3-4 for ten digits and in fours otherwise.
+fn phone_groups(text: &str, grouping: PhoneGrouping) -> Vec<String> {
+    if grouping == PhoneGrouping::ChineseMobile {
+        if let Some(groups) = chinese_mobile_groups(text) {
+            return groups;
+        }
+    }
+    let mut groups = Vec::new();
+    for part in text.split(['-', ' ', '(', ')']).filter(|p| !p.is_empty()) {
+        let (sign, digits) = match part.strip_prefix('+') {
+            Some(rest) => ("+", rest),
+            None => ("", part),
+        };
+        let sizes: &[usize] = match digits.len() {
+            11 => &[3, 4, 4],
+            10 => &[3, 3, 4],
+            _ => &[],
+        };
+        if grouping == PhoneGrouping::AsWritten || (sizes.is_empty() && digits.len() <= 4) {
+            groups.push(part.to_string());
+            continue;
+        }
+        let mut rest = digits;
+        let mut first = true;
+        for size in sizes.iter().copied().chain(std::iter::repeat(4)) {
+            if rest.is_empty() {
+                break;
+            }
+            let (group, tail) = rest.split_at(size.min(rest.len()));
+            groups.push(if first {
+                format!("{}{}", sign, group)
+            } else {
+                group.to_string()
+            });
+            first = false;
+            rest = tail;
+        }
+    }
+    groups
+}
+
+/// Groups of a Chinese mobile number: the country code, then 3-4-4
+fn chinese_mobile_groups(text: &str) -> Option<Vec<String>> {
+    let (code, number) = match text.strip_prefix("+86") {
+        Some(rest) => (Some("+86"), rest),
+        None => (None, text),
+    };
+    let digits: String = number
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' ' | '(' | ')'))
+        .collect();
+    if digits.len() != 11 || !digits.starts_with('1') || !digits.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let mut groups: Vec<String> = code.into_iter().map(str::to_string).collect();
+    groups.extend([&digits[..3], &digits[3..7], &digits[7..]].map(str::to_string));
+    Some(groups)
+}
+
+/// Read one group of a phone number digit by digit
+fn read_phone_group(group: &str, lang: Language) -> String {
+    let words: Vec<&str> = group
+        .chars()
+        .filter_map(|c| match c {
+            '0'..='9' => Some(phone_digit(c, lang)),
+            '+' => Some(symbol_word('+', lang)),
+            _ => None,
+        })
+        .collect();
+    match lang {
+        Language::En => words.join(" "),
+        _ => words.concat(),
+    }
+}
+
+/// Digit reading in phone numbers (Chinese reads 1 as 幺)
+fn phone_digit(c: char, lang: Language) -> &'static str {
+    if matches!(lang, Language::Zh | Language::Auto) && c == '1' {
+        return "幺";
+    }
+    digit_word(c, lang)
+}
+
+fn symbol_word(c: char, lang: Language) -> &'static str {
+    match (c, lang) {
+        ('.', Language::En) => "dot",
+        ('/', Language::En) => "slash",
+        (':', Language::En) => "colon",
+        ('-', Language::En) => "dash",
+        ('_', Language::En) => "underscore",
+        ('@', Language::En) => "at",
+        ('+', Language::En) => "plus",
+        ('.', Language::Ja) => "ドット",
+        ('/', Language::Ja) => "スラッシュ",
+        (':', Language::Ja) => "コロン",
+        ('-', Language::Ja) => "ハイフン",
+        ('_', Language::Ja) => "アンダーバー",
+        ('@', Language::Ja) => "アットマーク",
+        ('+', Language::Ja) => "プラス",
+        ('.', _) => "点",
+        ('/', _) => "斜杠",
+        (':', _) => "冒号",
+        ('-', _) => "杠",
+        ('_', _) => "下划线",
+        ('@', _) => "艾特",
+        ('+', _) => "加",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phone_span() {
+        let config = NormalizerConfig::new().with_rule_class(EntityClass::Phone);
+        let spans = find_spans("请拨打13800138000", Language::Zh, &config);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].spoken, "幺三八零零幺三八零零零");
+        assert_eq!(spans[0].start, "请拨打".len());
+    }
+
+    #[test]
+    fn test_phone_inside_digit_run() {
+        let config = NormalizerConfig::new().with_rule_class(EntityClass::Phone);
+        assert!(find_spans("订单号138001380001234", Language::Zh, &config).is_empty());
+        assert!(find_spans("ID 913800138000", Language::Zh, &config).is_empty());
+        assert_eq!(find_spans("13800138000号", Language::Zh, &config).len(), 1);
+    }
+
+    #[test]
+    fn test_phone_pauses() {
+        let config = NormalizerConfig::new()
+            .with_rule_class(EntityClass::Phone)
+            .with_pause_marker("，");
+        let spans = find_spans("13800138000", Language::Zh, &config);
+        assert_eq!(spans[0].spoken, "幺三八，零零幺三，八零零零");
+
+        let config = config.with_pause_marker("<break/>");
+        let spans = find_spans("+1 (555) 123-4567", Language::En, &config);
+        assert_eq!(
+            spans[0].spoken,
+            "plus one <break/> five five five <break/> one two three <break/> four five six seven"
+        );
+    }
+
+    #[test]
+    fn test_phone_grouping() {
+        let config = NormalizerConfig::new()
+            .with_rule_class(EntityClass::Phone)
+            .with_pause_marker("，");
+        let read = |text: &str, grouping| {
+            let config = config.clone().with_phone_grouping(grouping);
+            find_spans(text, Language::Zh, &config)[0].spoken.clone()
+        };
+        assert_eq!(
+            read("1380-013-8000", PhoneGrouping::ByLength),
+            "幺三八零，零幺三，八零零零"
+        );
+        assert_eq!(
+            read("+86 1380-013-8000", PhoneGrouping::ChineseMobile),
+            "加八六，幺三八，零零幺三，八零零零"
+        );
+        assert_eq!(
+            read("13800138000", PhoneGrouping::AsWritten),
+            "幺三八零零幺三八零零零"
+        );
+        // Other numbers keep the default grouping
+        assert_eq!(
+            read("010-8888-6666", PhoneGrouping::ChineseMobile),
+            "零幺零，八八八八，六六六六"
+        );
+    }
+
+    #[test]
+    fn test_ordinal_span() {
+        let config = NormalizerConfig::new().with_rule_class(EntityClass::Ordinal);
+        let spans = find_spans("第3名和第12届", Language::Zh, &config);
+        let spoken: Vec<&str> = spans.iter().map(|s| s.spoken.as_str()).collect();
+        assert_eq!(spoken, ["第三", "第十二"]);
+        let spans = find_spans("3番目", Language::Ja, &config);
+        assert_eq!(spans[0].spoken, "三番目");
+
+        let config = config.with_operator(Operator::Itn);
+        let spans = find_spans("第三名和第十二届", Language::Zh, &config);
+        let spoken: Vec<&str> = spans.iter().map(|s| s.spoken.as_str()).collect();
+        assert_eq!(spoken, ["第3", "第12"]);
+        let spans = find_spans("三番目", Language::Ja, &config);
+        assert_eq!(spans[0].spoken, "3番目");
+
+        // Other classes are TN only
+        let config = NormalizerConfig::new()
+            .with_rule_class(EntityClass::Phone)
+            .with_operator(Operator::Itn);
+        assert!(!is_active(&config));
+    }
+
+    #[test]
+    fn test_weekday_span() {
+        let config = NormalizerConfig::new().with_rule_class(EntityClass::Weekday);
+        let spoken = |text: &str, lang, config: &NormalizerConfig| {
+            find_spans(text, lang, config)
+                .iter()
+                .map(|s| s.spoken.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            spoken(
+                "Wed., 5 June or next Thurs, see you Sun.",
+                Language::En,
+                &config
+            ),
+            ["Wednesday", "Thursday", "Sunday"]
+        );
+        assert_eq!(
+            spoken("下周3和星期7，每周12次", Language::Zh, &config),
+            ["周三", "星期日"]
+        );
+        assert_eq!(spoken("Fri.", Language::Ja, &config), ["金曜日"]);
+        // Without a period or comma, "Sun" and "Sat" are ordinary words
+        assert!(spoken("Sun rises, Sat down", Language::En, &config).is_empty());
+
+        let config = config.with_operator(Operator::Itn);
+        assert_eq!(
+            spoken("see you on wednesday or Sunday", Language::En, &config),
+            ["Wed.", "Sun."]
+        );
+        assert_eq!(
+            spoken("下个星期三和礼拜天", Language::Zh, &config),
+            ["周三", "周日"]
+        );
+    }
+
+    #[test]
+    fn test_percent_reading() {
+        let config = NormalizerConfig::new().with_percent_reading(PercentReading::Native);
+        assert!(is_active(&config));
+        let spoken = |text: &str, lang, config: &NormalizerConfig| {
+            find_spans(text, lang, config)
+                .iter()
+                .map(|s| s.spoken.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            spoken("涨了50%，利率-2.5％，含量5‰", Language::Zh, &config),
+            ["百分之五十", "负百分之二点五", "千分之五"]
+        );
+        assert_eq!(spoken("50%", Language::Ja, &config), ["五十パーセント"]);
+        assert_eq!(spoken("up 50 %", Language::En, &config), ["fifty percent"]);
+        // A dash between numbers is a range
+        assert_eq!(spoken("50-60%", Language::Zh, &config), ["百分之六十"]);
+
+        let config = config.with_percent_reading(PercentReading::LoanWord);
+        assert_eq!(spoken("50%", Language::Zh, &config), ["五十percent"]);
+        assert!(!is_active(&NormalizerConfig::new()));
+    }
+
+    #[test]
+    fn test_fraction_reading() {
+        let config = NormalizerConfig::new().with_fraction_reading(FractionReading::Quarters);
+        let spoken = |text: &str, lang, config: &NormalizerConfig| {
+            find_spans(text, lang, config)
+                .iter()
+                .map(|s| s.spoken.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            spoken("3/4 cup and 1 1/2 hours or 2/3", Language::En, &config),
+            ["three quarters", "one and a half", "two thirds"]
+        );
+        assert_eq!(
+            spoken("3/4和3又1/2", Language::Zh, &config),
+            ["四分之三", "三又二分之一"]
+        );
+        assert_eq!(spoken("3/4", Language::Ja, &config), ["四分の三"]);
+        // Dates are left to the grammar
+        assert!(spoken("3/4/2024 and 1/0", Language::En, &config).is_empty());
+
+        let config = config.with_fraction_reading(FractionReading::Fourths);
+        assert_eq!(
+            spoken("3/4 and 1 1/2", Language::En, &config),
+            ["three fourths", "one and one half"]
+        );
+    }
+
+    #[test]
+    fn test_duration_reading() {
+        let config = NormalizerConfig::new().with_duration_reading(DurationReading::Compact);
+        let spoken = |text: &str, lang, config: &NormalizerConfig| {
+            find_spans(text, lang, config)
+                .iter()
+                .map(|s| s.spoken.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            spoken(
+                "用时3分20秒，3点20分和三时20分出发，2小时",
+                Language::Zh,
+                &config
+            ),
+            ["三分二十秒", "两小时"]
+        );
+        assert_eq!(spoken("2時間30分", Language::Ja, &config), ["二時間三十分"]);
+        assert_eq!(
+            spoken("took 1h 20m, then 5 min 30 sec", Language::En, &config),
+            ["one hour twenty minutes", "five minutes thirty seconds"]
+        );
+        // Scores, cents and lengths are left to the grammar
+        assert!(spoken("得了3分，5 m", Language::Zh, &config).is_empty());
+
+        let config = config.with_duration_reading(DurationReading::Full);
+        assert_eq!(spoken("3分20秒", Language::Zh, &config), ["三分钟二十秒"]);
+        assert_eq!(
+            spoken("1h 20m 5s", Language::En, &config),
+            ["one hour twenty minutes and five seconds"]
+        );
+    }
+
+    #[test]
+    fn test_url_span() {
+        let text = "visit www.example.com/a1.";
+        let config = NormalizerConfig::new().with_rule_class(EntityClass::Url);
+        let spans = find_spans(text, Language::En, &config);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(&text[spans[0].start..spans[0].end], "www.example.com/a1");
+        assert_eq!(spans[0].spoken, "www dot example dot com slash a one");
+    }
+
+    #[test]
+    fn test_japanese_address() {
+        let config = NormalizerConfig::new().with_rule_class(EntityClass::Email);
+        let spans = find_spans("a_1@example.jp", Language::Ja, &config);
+        assert_eq!(
+            spans[0].spoken,
+            "aアンダーバー一アットマークexampleドットjp"
+        );
+        let config = NormalizerConfig::new().with_rule_class(EntityClass::Phone);
+        let spans = find_spans("090-1234-5678", Language::Ja, &config);
+        assert!(!spans[0].spoken.contains('幺'));
+    }
+
+    #[test]
+    fn test_email_takes_priority_over_phone() {
+        let text = "13800138000@example.com";
+        let config = NormalizerConfig::new()
+            .with_rule_class(EntityClass::Phone)
+            .with_rule_class(EntityClass::Email);
+        let spans = find_spans(text, Language::En, &config);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].end, text.len());
+    }
+
+    #[test]
+    fn test_alphanumeric_span() {
+        let config = NormalizerConfig::new().with_rule_class(EntityClass::Alphanumeric);
+        let spans = find_spans("支持5G和3D", Language::Zh, &config);
+        let spoken: Vec<&str> = spans.iter().map(|s| s.spoken.as_str()).collect();
+        assert_eq!(spoken, ["五G", "三D"]);
+
+        let spans = find_spans("an MP3 for B2B", Language::En, &config);
+        let spoken: Vec<&str> = spans.iter().map(|s| s.spoken.as_str()).collect();
+        assert_eq!(spoken, ["M P three", "B to B"]);
+
+        let config = config.with_alnum_reading("MP3", "mp three");
+        let spans = find_spans("MP3 and 4K", Language::En, &config);
+        let spoken: Vec<&str> = spans.iter().map(|s| s.spoken.as_str()).collect();
+        assert_eq!(spoken, ["mp three", "four K"]);
+    }
+}
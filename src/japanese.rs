@@ -0,0 +1,151 @@
+//! Japanese-specific text passes
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::config::{EraStyle, Language};
+use crate::numbers::words_to_num;
+
+/// Eras as (name, first Gregorian year, first month, first day), newest first
+const ERAS: &[(&str, i64, u32, u32)] = &[
+    ("令和", 2019, 5, 1),
+    ("平成", 1989, 1, 8),
+    ("昭和", 1926, 12, 25),
+    ("大正", 1912, 7, 30),
+    ("明治", 1868, 10, 23),
+];
+
+static ERA_DATE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(令和|平成|昭和|大正|明治)(元|\d{1,2}|[〇一二三四五六七八九十]{1,3})年").unwrap()
+});
+
+static GREGORIAN_DATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d{4})年(?:(\d{1,2})月(?:(\d{1,2})日)?)?").unwrap());
+
+/// Full-width forms of U+FF61..=U+FF9F, in code point order
+const FULL_WIDTH_KANA: &str = "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン゛゜";
+
+/// Convert half-width katakana (ｶﾀｶﾅ) to full-width (カタカナ)
+///
+/// Voiced and semi-voiced sound marks are merged into the preceding kana
+/// (ｶﾞ -> ガ, ﾊﾟ -> パ) where a combined form exists.
+pub(crate) fn katakana_to_full_width(text: &str) -> String {
+    if !text.chars().any(|c| ('\u{ff61}'..='\u{ff9f}').contains(&c)) {
+        return text.to_string();
+    }
+
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        if !('\u{ff61}'..='\u{ff9f}').contains(&c) {
+            output.push(c);
+            continue;
+        }
+        let full = FULL_WIDTH_KANA
+            .chars()
+            .nth(c as usize - 0xff61)
+            .unwrap_or(c);
+        let combined = match (output.chars().last(), full) {
+            (Some('ウ'), '゛') => Some('ヴ'),
+            (Some(prev), '゛') if "カキクケコサシスセソタチツテトハヒフヘホ".contains(prev) => {
+                char::from_u32(prev as u32 + 1)
+            }
+            (Some(prev), '゜') if "ハヒフヘホ".contains(prev) => {
+                char::from_u32(prev as u32 + 2)
+            }
+            _ => None,
+        };
+        match combined {
+            Some(combined) => {
+                output.pop();
+                output.push(combined);
+            }
+            None => output.push(full),
+        }
+    }
+    output
+}
+
+/// Convert years between Japanese eras and the Gregorian calendar
+///
+/// "令和6年" and "令和元年" become "2024年" and "2019年" with
+/// `EraStyle::Gregorian`; `EraStyle::Era` does the reverse, using the
+/// month and day when present to pick the era in a transition year.
+pub(crate) fn convert_era(text: &str, style: EraStyle) -> String {
+    match style {
+        EraStyle::AsWritten => text.to_string(),
+        EraStyle::Gregorian => ERA_DATE
+            .replace_all(text, |caps: &Captures| {
+                let Some(&(_, start, _, _)) = ERAS.iter().find(|(name, ..)| *name == &caps[1])
+                else {
+                    return caps[0].to_string();
+                };
+                let year = match &caps[2] {
+                    "元" => Some(1),
+                    n => n
+                        .parse()
+                        .ok()
+                        .or_else(|| words_to_num(n, Language::Ja).ok()?.as_i64()),
+                };
+                match year {
+                    Some(year) if year >= 1 => format!("{}年", start + year - 1),
+                    _ => caps[0].to_string(),
+                }
+            })
+            .into_owned(),
+        EraStyle::Era => GREGORIAN_DATE
+            .replace_all(text, |caps: &Captures| {
+                let year: i64 = caps[1].parse().unwrap_or(0);
+                let month: u32 = caps
+                    .get(2)
+                    .and_then(|m| m.as_str().parse().ok())
+                    .unwrap_or(12);
+                let day: u32 = caps
+                    .get(3)
+                    .and_then(|d| d.as_str().parse().ok())
+                    .unwrap_or(31);
+                let Some(&(name, start, ..)) = ERAS
+                    .iter()
+                    .find(|&&(_, y, m, d)| (year, month, day) >= (y, m, d))
+                else {
+                    return caps[0].to_string();
+                };
+                let era_year = match year - start + 1 {
+                    1 => "元".to_string(),
+                    n => n.to_string(),
+                };
+                let rest = &caps[0][caps[1].len() + "年".len()..];
+                format!("{}{}年{}", name, era_year, rest)
+            })
+            .into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_katakana_to_full_width() {
+        assert_eq!(katakana_to_full_width("ｶﾀｶﾅ"), "カタカナ");
+        assert_eq!(katakana_to_full_width("ｶﾞｽﾞﾊﾟﾝﾀﾞ"), "ガズパンダ");
+        assert_eq!(katakana_to_full_width("ｳﾞｧｲｵﾘﾝ｡"), "ヴァイオリン。");
+        assert_eq!(katakana_to_full_width("ｱﾞ100円"), "ア゛100円");
+    }
+
+    #[test]
+    fn test_convert_era() {
+        assert_eq!(convert_era("令和6年", EraStyle::Gregorian), "2024年");
+        assert_eq!(convert_era("令和元年5月", EraStyle::Gregorian), "2019年5月");
+        assert_eq!(convert_era("平成三十年", EraStyle::Gregorian), "2018年");
+        assert_eq!(
+            convert_era("2024年1月15日", EraStyle::Era),
+            "令和6年1月15日"
+        );
+        assert_eq!(
+            convert_era("2019年4月30日", EraStyle::Era),
+            "平成31年4月30日"
+        );
+        assert_eq!(convert_era("2019年5月1日", EraStyle::Era), "令和元年5月1日");
+        assert_eq!(convert_era("1500年", EraStyle::Era), "1500年");
+    }
+}
@@ -0,0 +1,64 @@
+//! Chinese/Japanese guess for kanji-only text
+//!
+//! Text without kana may be Chinese or Japanese. Some characters and words
+//! are only used in one of them: Japanese shinjitai forms ("駅", "円",
+//! "発") and address or calendar words ("番地", "丁目", "令和"), or
+//! simplified Chinese forms ("这", "们", "东"). Counting them decides most
+//! short inputs such as addresses and prices.
+
+use crate::config::Language;
+
+/// Characters written this way only in Japanese
+const JA_CHARS: &str =
+    "円駅県様働込畑峠辻枠栃広気発売読転変歳図沢浜関鉄険桜戦経済営労楽薬徳値実単黒";
+
+/// Words used only in Japanese
+const JA_WORDS: &[&str] = &[
+    "丁目",
+    "番地",
+    "曜日",
+    "令和",
+    "平成",
+    "昭和",
+    "株式会社",
+    "時間",
+];
+
+/// Characters written this way only in simplified Chinese
+const ZH_CHARS: &str = "们这个说时国门东车马长书为对发买卖钱块岁亿从电话还";
+
+/// Words used only in Chinese
+const ZH_WORDS: &[&str] = &["星期", "小时", "分钟", "公里", "人民币"];
+
+/// Guess whether kanji-only text is Chinese or Japanese
+///
+/// Returns `None` when the text has as many Chinese as Japanese markers.
+pub(crate) fn guess_language(text: &str) -> Option<Language> {
+    let score = |chars: &str, words: &[&str]| {
+        text.chars().filter(|c| chars.contains(*c)).count()
+            + words
+                .iter()
+                .map(|word| text.matches(word).count())
+                .sum::<usize>()
+    };
+    let ja = score(JA_CHARS, JA_WORDS);
+    let zh = score(ZH_CHARS, ZH_WORDS);
+    match ja.cmp(&zh) {
+        std::cmp::Ordering::Greater => Some(Language::Ja),
+        std::cmp::Ordering::Less => Some(Language::Zh),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess_language() {
+        assert_eq!(guess_language("東京都123番地"), Some(Language::Ja));
+        assert_eq!(guess_language("新宿駅500円"), Some(Language::Ja));
+        assert_eq!(guess_language("这个东西100元"), Some(Language::Zh));
+        assert_eq!(guess_language("价格100元"), None);
+    }
+}
@@ -0,0 +1,152 @@
+//! Human-readable dumps of FST path labels
+//!
+//! `FstTextNormalizer` decodes output labels above 255 as Unicode code
+//! points and runs of lower labels as UTF-8 bytes, or as code points where
+//! they are not valid UTF-8 in a path that has code points. This dump lists
+//! each arc of a path with its raw labels and their decoding, to diagnose
+//! output that decodes wrongly under that guess.
+
+use std::fmt::Write;
+
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::{Semiring, TropicalWeight};
+use rustfst::{Label, SymbolTable, EPS_LABEL};
+
+use crate::dot;
+use crate::error::Result;
+
+/// Decodes labels one arc at a time, assembling UTF-8 byte sequences
+#[derive(Default)]
+struct Decoder {
+    pending: Vec<u8>,
+}
+
+impl Decoder {
+    /// Decoded text for one label: a symbol, a character, or `…` while a
+    /// multi-byte character is incomplete
+    fn push(&mut self, label: Label, symbols: Option<&SymbolTable>) -> String {
+        if label == EPS_LABEL {
+            return "ε".to_string();
+        }
+        if let Some(symbol) = symbols.and_then(|table| table.get_symbol(label)) {
+            return symbol.to_string();
+        }
+        if label > 255 {
+            return char::from_u32(label).map_or_else(|| "�".to_string(), printable);
+        }
+        self.pending.push(label as u8);
+        match std::str::from_utf8(&self.pending) {
+            Ok(text) => {
+                let text = text.chars().map(printable).collect();
+                self.pending.clear();
+                text
+            }
+            Err(e) if e.error_len().is_none() && self.pending.len() < 4 => "…".to_string(),
+            Err(_) => {
+                self.pending.clear();
+                "�".to_string()
+            }
+        }
+    }
+}
+
+fn printable(c: char) -> String {
+    c.escape_debug().to_string()
+}
+
+/// Decode a label sequence with one interpretation for every label
+fn decode_all(labels: &[Label], as_bytes: bool) -> String {
+    let labels = labels.iter().filter(|&&label| label != EPS_LABEL);
+    if as_bytes {
+        let bytes: Vec<u8> = labels.map(|&label| label as u8).collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    } else {
+        labels.filter_map(|&label| char::from_u32(label)).collect()
+    }
+}
+
+/// Dump the arcs of a linear path with raw and decoded labels
+///
+/// Symbol tables take precedence over byte and code point decoding for the
+/// labels they contain.
+pub(crate) fn dump_path(
+    path: &VectorFst<TropicalWeight>,
+    input_symbols: Option<&SymbolTable>,
+    output_symbols: Option<&SymbolTable>,
+) -> Result<String> {
+    let trs = dot::linear_trs(path)?;
+    let mut dump = String::new();
+    let _ = writeln!(
+        dump,
+        "{:>4}  {:>8}  {:>8}  {:>7}  {:<6}  output",
+        "arc", "ilabel", "olabel", "weight", "input"
+    );
+    let mut input = Decoder::default();
+    let mut output = Decoder::default();
+    for (index, tr) in trs.iter().enumerate() {
+        let _ = writeln!(
+            dump,
+            "{:>4}  {:>8}  {:>8}  {:>7}  {:<6}  {}",
+            index,
+            tr.ilabel,
+            tr.olabel,
+            tr.weight.value(),
+            input.push(tr.ilabel, input_symbols),
+            output.push(tr.olabel, output_symbols)
+        );
+    }
+
+    let olabels: Vec<Label> = trs.iter().map(|tr| tr.olabel).collect();
+    let as_code_points = olabels.iter().any(|&label| label > 255);
+    let _ = writeln!(
+        dump,
+        "output as bytes:       {}",
+        decode_all(&olabels, true)
+    );
+    let _ = writeln!(
+        dump,
+        "output as code points: {}",
+        decode_all(&olabels, false)
+    );
+    let _ = writeln!(
+        dump,
+        "normalize decodes as:  {}",
+        if as_code_points {
+            "code points above 255, bytes in between"
+        } else {
+            "bytes (no label is above 255)"
+        }
+    );
+    Ok(dump)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder() {
+        let mut decoder = Decoder::default();
+        let bytes = "二".as_bytes();
+        assert_eq!(decoder.push(bytes[0] as Label, None), "…");
+        assert_eq!(decoder.push(bytes[1] as Label, None), "…");
+        assert_eq!(decoder.push(bytes[2] as Label, None), "二");
+        assert_eq!(decoder.push('二' as Label, None), "二");
+        assert_eq!(decoder.push(EPS_LABEL, None), "ε");
+        assert_eq!(decoder.push(0xFF, None), "�");
+
+        let mut symbols = SymbolTable::new();
+        let label = symbols.add_symbol("<year>");
+        assert_eq!(decoder.push(label, Some(&symbols)), "<year>");
+    }
+
+    #[test]
+    fn test_dump_path() {
+        let normalizer = crate::Rule::replace("1", "一").to_rewriter().unwrap();
+        let dump = normalizer.dump_path("1").unwrap().unwrap();
+        assert!(dump.starts_with(" arc"));
+        assert!(dump.contains("output as bytes:       一"));
+        assert!(dump.contains("normalize decodes as:  bytes"));
+        assert!(normalizer.dump_path("").unwrap().is_none());
+    }
+}
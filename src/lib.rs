@@ -21,16 +21,80 @@
 //! println!("{}", result);  // 二零二四年一月十五日
 //! ```
 
+mod acceptor;
+mod beautify;
+mod casefold;
 mod config;
+#[cfg(feature = "contractions")]
 mod contractions;
+mod dates;
+mod dot;
+#[cfg(feature = "lang-en")]
+mod english;
+mod erhua;
 mod error;
+mod extract;
+pub mod format;
+mod frontend;
+mod glyphs;
+mod graphemes;
+mod hybrid;
+#[cfg(feature = "lang-ja")]
+mod japanese;
+mod kanji;
+mod labels;
+mod mask;
+mod mixed;
 mod normalizer;
+mod numbers;
+mod prosody;
+mod punctuation;
+mod report;
+mod rewrite;
+mod rule;
+mod script;
+mod search;
+mod segmenter;
+mod snapshot;
+mod spell;
+mod stream;
+pub mod testkit;
 mod text_normalizer;
 mod token_parser;
+mod truecase;
+mod units;
+mod worker;
 
-pub use config::{Language, NormalizerConfig, Operator};
+pub use config::{
+    Beautify, CurrencyPlacement, DateOrderPreference, DecimalReading, DurationReading, EnVariant,
+    EraStyle, ErrorPolicy, FractionReading, InvalidUtf8, KanjiDisambiguation, Language,
+    LanguageHint, MaskStyle, Masking, MoneyReading, NoMatchPolicy, NormalizerConfig, NumberScale,
+    Operator, OutputScript, Overrides, PauseHints, PercentReading, PercentSpacing, PhoneGrouping,
+    SearchLimits, Stage, TimeFormat, TnTrigger, YearStyle,
+};
+pub use erhua::remove_erhua;
 pub use error::{Result, WeTextError};
+pub use extract::{
+    Date, ExtractedMeasure, ExtractedMoney, ExtractedNumber, NumberKind, ReadbackMismatch, Time,
+};
+pub use frontend::{EntitySpan, FrontendOutput, TtsFrontend, Word};
+pub use hybrid::EntityClass;
 pub use normalizer::Normalizer;
+pub use numbers::{num_to_words, words_to_num, Number, Style};
+pub use punctuation::PunctuationRestorer;
+pub use report::{
+    Ambiguity, Annotation, CandidateSpan, Capabilities, Capability, Confidence, Document,
+    DocumentSegment, DualSpan, NormalizeReport, NormalizedSegment, OovSpan, Segment,
+};
+pub use rule::Rule;
+pub use segmenter::Segmenter;
+pub use spell::spell_out;
+pub use stream::StreamNormalizer;
+pub use text_normalizer::{FstInfo, FstTextNormalizer};
+pub use token_parser::{Token, TokenParser};
+pub use truecase::truecase;
+pub use units::Quantity;
+pub use worker::{NormalizerWorker, ShardedNormalizer};
 
 /// Convenience function: normalize text with default configuration
 ///
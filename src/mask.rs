@@ -0,0 +1,123 @@
+//! Masking of sensitive terms in normalized output
+//!
+//! Terms are matched in the normalized text, so numbers and dates are
+//! masked by their spoken form. Span-based APIs mask the concatenation of
+//! their spans, so a term split across spans is still found; each span
+//! keeps the part of the mask that covers it.
+
+use std::ops::Range;
+
+use crate::config::{MaskStyle, Masking};
+
+/// Byte ranges of the terms found in `text`, in order and without overlaps
+///
+/// Terms match ASCII case-insensitively. A term that starts or ends with an
+/// ASCII letter or digit only matches at a word boundary on that side.
+fn find_terms(text: &str, terms: &[String]) -> Vec<Range<usize>> {
+    let lower = text.to_ascii_lowercase();
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+    let mut found = Vec::new();
+    for term in terms {
+        let term = term.to_ascii_lowercase();
+        if term.is_empty() {
+            continue;
+        }
+        for (start, _) in lower.match_indices(&term) {
+            let end = start + term.len();
+            let before = lower[..start].chars().next_back();
+            let after = lower[end..].chars().next();
+            if (is_word(term.chars().next()) && is_word(before))
+                || (is_word(term.chars().next_back()) && is_word(after))
+            {
+                continue;
+            }
+            found.push(start..end);
+        }
+    }
+    // Earliest first, longest first at the same position
+    found.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for range in found {
+        match ranges.last() {
+            Some(last) if range.start < last.end => {}
+            _ => ranges.push(range),
+        }
+    }
+    ranges
+}
+
+/// Mask terms in text
+pub(crate) fn mask(text: &str, masking: &Masking) -> String {
+    let mut text = text.to_string();
+    mask_pieces([&mut text], masking);
+    text
+}
+
+/// Mask terms in pieces of text that are read as one concatenated text
+///
+/// With `MaskStyle::Token`, the token goes into the piece where the term
+/// starts and the rest of the term is removed from the following pieces.
+pub(crate) fn mask_pieces<'a, I>(pieces: I, masking: &Masking)
+where
+    I: IntoIterator<Item = &'a mut String>,
+{
+    let mut pieces: Vec<&mut String> = pieces.into_iter().collect();
+    let text: String = pieces.iter().map(|piece| piece.as_str()).collect();
+    let ranges = find_terms(&text, &masking.terms);
+    if ranges.is_empty() {
+        return;
+    }
+
+    let mut offset = 0;
+    for piece in pieces.iter_mut() {
+        let span = offset..offset + piece.len();
+        offset = span.end;
+        let mut output = String::with_capacity(piece.len());
+        let mut last = span.start;
+        for range in ranges
+            .iter()
+            .filter(|range| range.start < span.end && span.start < range.end)
+        {
+            let start = range.start.max(span.start);
+            let end = range.end.min(span.end);
+            output.push_str(&text[last..start]);
+            match &masking.style {
+                MaskStyle::Stars => output.push_str(&"*".repeat(text[start..end].chars().count())),
+                MaskStyle::Token(token) if range.start >= span.start => output.push_str(token),
+                MaskStyle::Token(_) => {}
+            }
+            last = end;
+        }
+        output.push_str(&text[last..span.end]);
+        **piece = output;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask() {
+        let masking = Masking::new(["darn", "坏蛋"]);
+        assert_eq!(
+            mask("Darn it, darnation 坏蛋", &masking),
+            "**** it, darnation **"
+        );
+        let masking = masking.with_token("<beep>");
+        assert_eq!(mask("darn it", &masking), "<beep> it");
+    }
+
+    #[test]
+    fn test_mask_pieces() {
+        let masking = Masking::new(["一百"]).with_token("[masked]");
+        let mut pieces = vec!["共一".to_string(), "百元".to_string()];
+        mask_pieces(pieces.iter_mut(), &masking);
+        assert_eq!(pieces, ["共[masked]", "元"]);
+
+        let masking = Masking::new(["一百"]);
+        let mut pieces = vec!["共一".to_string(), "百元".to_string()];
+        mask_pieces(pieces.iter_mut(), &masking);
+        assert_eq!(pieces, ["共*", "*元"]);
+    }
+}
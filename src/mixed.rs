@@ -0,0 +1,238 @@
+//! Mixed numeral merging
+//!
+//! Chinese text often writes numbers with digits for the multipliers and
+//! Chinese characters for the scales ("3万5千", "2千零24", "1亿2千万"). The
+//! taggers see each digit run on its own and read such a number as several
+//! cardinals. This stage rewrites them into plain digits, so the tagger
+//! reads one value and can attach units or currencies to it.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::numbers::cjk_digit;
+
+/// Runs of digits, Chinese digits and scale characters
+static RUN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[0-9零〇一二两兩三四五六七八九十百千万萬亿億]+").unwrap());
+
+/// A piece of a mixed numeral
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    /// A digit run or a Chinese digit, with its number of digits and
+    /// whether it was written with ASCII digits
+    Number(i64, usize, bool),
+    /// 零 between places
+    Zero,
+    /// 十, 百 or 千
+    Unit(i64),
+    /// 万 or 亿
+    Scale(i64),
+}
+
+/// Split a run into parts
+fn parts(run: &str) -> Option<Vec<Part>> {
+    let mut parts = Vec::new();
+    let mut chars = run.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let part = match c {
+            '0'..='9' => {
+                let mut end = start + 1;
+                while let Some(&(i, d)) = chars.peek() {
+                    if !d.is_ascii_digit() {
+                        break;
+                    }
+                    end = i + 1;
+                    chars.next();
+                }
+                let digits = &run[start..end];
+                // Leading zeros make it a code, not an amount
+                if digits.len() > 1 && digits.starts_with('0') {
+                    return None;
+                }
+                Part::Number(digits.parse().ok()?, digits.len(), true)
+            }
+            '零' | '〇' => Part::Zero,
+            '十' => Part::Unit(10),
+            '百' => Part::Unit(100),
+            '千' => Part::Unit(1000),
+            '万' | '萬' => Part::Scale(10_000),
+            '亿' | '億' => Part::Scale(100_000_000),
+            _ => Part::Number(cjk_digit(c)?, 1, false),
+        };
+        parts.push(part);
+    }
+    Some(parts)
+}
+
+/// Value of a mixed numeral
+///
+/// A number after the last place is added to it when it follows 零
+/// ("2千零24") or fills every lower place ("12万3456"); a single digit is
+/// shorthand for the next lower place ("3万5" is 35000).
+fn value(parts: &[Part]) -> Option<i64> {
+    let mut total: i64 = 0;
+    let mut section: i64 = 0;
+    let mut number: Option<(i64, usize)> = None;
+    let mut zero = false;
+    // Smallest unit of the current section and of the whole numeral so far
+    let mut section_unit = 10_000;
+    let mut scale_unit = i64::MAX;
+    // Last place and the multiplier written before it
+    let mut last: Option<(i64, i64)> = None;
+
+    for (index, &part) in parts.iter().enumerate() {
+        match part {
+            Part::Number(value, digits, _) => {
+                if number.is_some() {
+                    return None;
+                }
+                number = Some((value, digits));
+            }
+            Part::Zero => {
+                if number.is_some() || last.is_none() {
+                    return None;
+                }
+                zero = true;
+            }
+            Part::Unit(unit) => {
+                let multiplier = match number.take() {
+                    Some((value, _)) => value,
+                    // A bare 十 only starts a numeral ("十5万")
+                    None if index == 0 && unit == 10 => 1,
+                    None => return None,
+                };
+                if !(1..10).contains(&multiplier) || unit >= section_unit {
+                    return None;
+                }
+                section = section.checked_add(multiplier.checked_mul(unit)?)?;
+                section_unit = unit;
+                last = Some((unit, multiplier));
+                zero = false;
+            }
+            Part::Scale(scale) => {
+                let value = number.take().map_or(0, |(value, _)| value);
+                let multiplier = section.checked_add(value)?;
+                // Each scale groups up to 9999 of the next smaller one
+                if !(1..10_000).contains(&multiplier) || scale >= scale_unit {
+                    return None;
+                }
+                if scale == 10_000 {
+                    section = multiplier.checked_mul(scale)?;
+                } else {
+                    total = total.checked_add(multiplier)?.checked_mul(scale)?;
+                    section = 0;
+                }
+                section_unit = 10_000;
+                scale_unit = scale;
+                last = Some((scale, multiplier));
+                zero = false;
+            }
+        }
+    }
+
+    let (place, multiplier) = last?;
+    let tail = match number {
+        None if zero => return None,
+        None => 0,
+        Some((value, _)) if zero => {
+            if value >= place / 10 {
+                return None;
+            }
+            value
+        }
+        // Shorthand: "3万5", "2千5"
+        Some((value, 1)) if multiplier < 10 => value.checked_mul(place / 10)?,
+        // Every lower place written out: "12万3456"
+        Some((value, digits)) if 10i64.checked_pow(digits as u32)? == place => value,
+        Some(_) => return None,
+    };
+    total.checked_add(section)?.checked_add(tail)
+}
+
+/// Rewrite numerals mixing digits and Chinese characters into digits
+///
+/// Only numerals with at least two numbers, one of them written in digits,
+/// are rewritten, so "3万" and "三万五千" are left to the grammars. Numerals
+/// after a decimal point or before "年" are left alone, since they would
+/// read differently as digits.
+pub(crate) fn merge_mixed_numerals(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    for m in RUN.find_iter(text) {
+        let before = text[..m.start()].chars().next_back();
+        let after = text[m.end()..].chars().next();
+        if matches!(before, Some('.' | ',' | '-' | '/'))
+            || matches!(after, Some('.' | ',' | '年' | '/'))
+        {
+            continue;
+        }
+        let Some(parts) = parts(m.as_str()) else {
+            continue;
+        };
+        let numbers = parts
+            .iter()
+            .filter(|part| matches!(part, Part::Number(..)))
+            .count();
+        let has_digits = parts
+            .iter()
+            .any(|part| matches!(part, Part::Number(_, _, true)));
+        if numbers < 2 || !has_digits {
+            continue;
+        }
+        let Some(value) = value(&parts) else {
+            continue;
+        };
+        output.push_str(&text[last..m.start()]);
+        output.push_str(&value.to_string());
+        last = m.end();
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_mixed_numerals() {
+        assert_eq!(merge_mixed_numerals("共3万5千元"), "共35000元");
+        assert_eq!(merge_mixed_numerals("2千零24"), "2024");
+        assert_eq!(merge_mixed_numerals("1亿2千万人"), "120000000人");
+        assert_eq!(merge_mixed_numerals("3亿5千2百万"), "352000000");
+        assert_eq!(merge_mixed_numerals("12万3456"), "123456");
+        assert_eq!(merge_mixed_numerals("月薪3万5"), "月薪35000");
+        assert_eq!(merge_mixed_numerals("三万5千"), "35000");
+        assert_eq!(merge_mixed_numerals("5亿零3万"), "500030000");
+        assert_eq!(merge_mixed_numerals("3万5个"), "35000个");
+    }
+
+    #[test]
+    fn test_merge_mixed_numerals_skips() {
+        // Single numbers and all-Chinese numerals are left to the grammars
+        for text in [
+            "3万",
+            "三万五千",
+            "35000",
+            "3.5万",
+            "2千零24年",
+            "12万345",
+            "1千万3",
+            "5 3万",
+        ] {
+            assert_eq!(merge_mixed_numerals(text), text);
+        }
+    }
+
+    #[test]
+    fn test_merge_mixed_numerals_overflow() {
+        // Values past i64 are left alone rather than wrapped
+        for text in [
+            "5千9223372036854775807万",
+            "9223372036854775807亿5千万",
+            "9千9百亿9223372036854775807万",
+        ] {
+            assert_eq!(merge_mixed_numerals(text), text);
+        }
+    }
+}
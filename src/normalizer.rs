@@ -3,19 +3,87 @@
 //! This module provides the main Normalizer struct that orchestrates
 //! the text normalization pipeline.
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, Write};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::SystemTime;
 
-use crate::config::{Language, NormalizerConfig, Operator};
+use crate::beautify;
+use crate::casefold;
+use crate::config::{
+    ErrorPolicy, InvalidUtf8, KanjiDisambiguation, Language, LanguageHint, NoMatchPolicy,
+    NormalizerConfig, Operator, OutputScript, Overrides, SearchLimits, Stage, TnTrigger,
+};
+#[cfg(feature = "contractions")]
 use crate::contractions::fix_contractions;
+use crate::dates;
+#[cfg(feature = "lang-en")]
+use crate::english;
+use crate::erhua::remove_erhua;
 use crate::error::{Result, WeTextError};
-use crate::text_normalizer::FstTextNormalizer;
-use crate::token_parser::TokenParser;
+use crate::extract::{
+    self, Date, ExtractedMeasure, ExtractedMoney, ExtractedNumber, ReadbackMismatch, Time,
+};
+use crate::glyphs;
+use crate::graphemes;
+use crate::hybrid;
+#[cfg(feature = "lang-ja")]
+use crate::japanese;
+use crate::kanji;
+use crate::mask;
+use crate::mixed;
+use crate::prosody;
+use crate::punctuation::{self, PunctuationRestorer};
+use crate::report::{
+    self, Ambiguity, Annotation, CandidateSpan, Capabilities, Capability, Confidence, Document,
+    DocumentSegment, DualSpan, NormalizeReport, NormalizedSegment, Piece, Segment,
+};
+use crate::rewrite;
+use crate::script;
+use crate::segmenter::{self, Segmenter};
+use crate::snapshot;
+use crate::spell;
+use crate::stream;
+use crate::text_normalizer::{FstInfo, FstTextNormalizer};
+use crate::token_parser::{Token, TokenParser};
+use crate::truecase::truecase;
+
+/// Number of lines queued per worker in `normalize_lines_parallel`
+const LINES_PER_WORKER: usize = 64;
+
+/// Caller-supplied decision whether text needs the FST pipeline
+type ShouldNormalize = Arc<dyn Fn(&str, Operator) -> bool + Send + Sync>;
+
+/// Entity tokens with their byte ranges in the input
+type TaggedEntities = Vec<(Token, Range<usize>)>;
+
+/// Maximum number of distinct lines remembered for `deduplicate_lines`
+const DEDUP_CAPACITY: usize = 100_000;
+
+/// A loaded FST together with the modification time of its source file
+struct CachedFst {
+    normalizer: FstTextNormalizer,
+    modified: Option<SystemTime>,
+}
 
 /// FST file cache for lazy loading
 struct FstCache {
-    fsts: HashMap<String, FstTextNormalizer>,
+    fsts: HashMap<String, CachedFst>,
     fst_dir: PathBuf,
+    /// Whether to check source files for changes on every access
+    watch: bool,
+    /// Path search limits applied to every FST handed out
+    search: SearchLimits,
+    /// Decoding of invalid UTF-8 output applied to every FST handed out
+    invalid_utf8: InvalidUtf8,
+    /// Whether to compact FSTs as they are loaded
+    compact: bool,
+    /// Warnings recorded while normalizing, e.g. FST fallbacks
+    warnings: Vec<String>,
 }
 
 impl FstCache {
@@ -23,19 +91,150 @@ impl FstCache {
         Self {
             fsts: HashMap::new(),
             fst_dir: fst_dir.as_ref().to_path_buf(),
+            watch: false,
+            search: SearchLimits::default(),
+            invalid_utf8: InvalidUtf8::default(),
+            compact: false,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Record a warning unless it has been recorded already
+    fn warn(&mut self, warning: String) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
         }
     }
 
     fn get_or_load(&mut self, relative_path: &str) -> Result<&FstTextNormalizer> {
+        if self.watch {
+            // Reload only if the file on disk has changed since it was loaded.
+            // A failed reload, e.g. of a file still being written, keeps the
+            // previously loaded FST in place and is retried on the next access.
+            let stale = self
+                .fsts
+                .get(relative_path)
+                .is_some_and(|cached| self.is_stale(relative_path, cached));
+            if stale {
+                match self.load(relative_path) {
+                    Ok(reloaded) => {
+                        self.fsts.insert(relative_path.to_string(), reloaded);
+                    }
+                    Err(e) => {
+                        // Parse errors may carry a backtrace; the first line
+                        // keeps repeated failures to one warning
+                        let error = e.to_string();
+                        let reason = error.lines().next().unwrap_or_default();
+                        self.warn(format!(
+                            "failed to reload {}, keeping the loaded FST: {}",
+                            relative_path, reason
+                        ));
+                    }
+                }
+            }
+        }
+
         if !self.fsts.contains_key(relative_path) {
-            let full_path = self.fst_dir.join(relative_path);
-            let normalizer = FstTextNormalizer::from_file(&full_path)?;
-            self.fsts.insert(relative_path.to_string(), normalizer);
+            let cached = self.load(relative_path)?;
+            self.fsts.insert(relative_path.to_string(), cached);
+        }
+        let cached = self.fsts.get_mut(relative_path).unwrap();
+        cached.normalizer.set_search(self.search);
+        cached.normalizer.set_invalid_utf8(self.invalid_utf8);
+        Ok(&cached.normalizer)
+    }
+
+    /// Load an FST from disk, recording its modification time
+    fn load(&self, relative_path: &str) -> Result<CachedFst> {
+        let full_path = self.fst_dir.join(relative_path);
+        let modified = Self::modified_time(&full_path);
+        let mut normalizer = FstTextNormalizer::from_file(&full_path)?;
+        if self.compact {
+            normalizer.compact();
+        }
+        Ok(CachedFst {
+            normalizer,
+            modified,
+        })
+    }
+
+    /// Check whether the file backing a cached FST has changed on disk
+    fn is_stale(&self, relative_path: &str, cached: &CachedFst) -> bool {
+        let current = Self::modified_time(&self.fst_dir.join(relative_path));
+        current.is_some() && current != cached.modified
+    }
+
+    fn modified_time(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    /// Reload every cached FST from disk
+    ///
+    /// All files are loaded before any of them is swapped in, so on error
+    /// the cache is left untouched.
+    fn reload_all(&mut self) -> Result<()> {
+        let mut reloaded = Vec::with_capacity(self.fsts.len());
+        for relative_path in self.fsts.keys() {
+            reloaded.push((relative_path.clone(), self.load(relative_path)?));
+        }
+        self.fsts.extend(reloaded);
+        Ok(())
+    }
+
+    /// Reload only the cached FSTs whose files changed on disk
+    ///
+    /// Returns the relative paths that were reloaded. Like `reload_all`,
+    /// either every changed file is swapped in or none is.
+    fn reload_changed(&mut self) -> Result<Vec<String>> {
+        let mut reloaded = Vec::new();
+        for (relative_path, cached) in &self.fsts {
+            if self.is_stale(relative_path, cached) {
+                reloaded.push((relative_path.clone(), self.load(relative_path)?));
+            }
+        }
+        let mut paths: Vec<String> = reloaded.iter().map(|(path, _)| path.clone()).collect();
+        paths.sort();
+        self.fsts.extend(reloaded);
+        Ok(paths)
+    }
+
+    /// Write every cached FST to a snapshot file, sorted by relative path
+    fn save_snapshot(&self, path: &Path) -> Result<()> {
+        let mut fsts: Vec<(&String, &CachedFst)> = self.fsts.iter().collect();
+        fsts.sort_by_key(|(relative_path, _)| *relative_path);
+        snapshot::write(
+            path,
+            fsts.into_iter().map(|(relative_path, cached)| {
+                (relative_path.as_str(), cached.modified, &cached.normalizer)
+            }),
+        )
+    }
+
+    /// Add the FSTs of a snapshot file to the cache
+    ///
+    /// Like `reload_all`, either every FST is swapped in or none is.
+    fn load_snapshot(&mut self, path: &Path) -> Result<Vec<String>> {
+        let entries = snapshot::read(path)?;
+        let paths = entries.iter().map(|entry| entry.path.clone()).collect();
+        for mut entry in entries {
+            if self.compact {
+                entry.normalizer.compact();
+            }
+            let cached = CachedFst {
+                normalizer: entry.normalizer,
+                modified: entry.modified,
+            };
+            self.fsts.insert(entry.path, cached);
         }
-        Ok(self.fsts.get(relative_path).unwrap())
+        Ok(paths)
     }
 }
 
+/// Error for a worker thread that stopped unexpectedly
+fn worker_error<E: std::fmt::Display>(e: E) -> WeTextError {
+    WeTextError::FstOperationError(format!("worker thread stopped: {}", e))
+}
+
 /// WeText Normalizer
 ///
 /// Main entry point for text normalization functionality.
@@ -54,6 +253,18 @@ impl FstCache {
 pub struct Normalizer {
     config: NormalizerConfig,
     cache: FstCache,
+    /// Inputs a tagger or verbalizer accepted no path for, under
+    /// `NoMatchPolicy::PassthroughWithWarning`
+    no_matches: u64,
+    /// Spans the tagger accepted no path for during the last call, with
+    /// `partial_match`
+    unmatched: Vec<String>,
+    /// Replaces the built-in check for whether text needs the FSTs
+    predicate: Option<ShouldNormalize>,
+    /// Punctuation model run before ITN
+    punctuation: Option<Arc<dyn PunctuationRestorer>>,
+    /// Word segmenter whose words the tagger may not split
+    segmenter: Option<Arc<dyn Segmenter>>,
 }
 
 impl Normalizer {
@@ -63,9 +274,18 @@ impl Normalizer {
     /// * `fst_dir` - Directory containing FST weight files
     /// * `config` - Normalizer configuration
     pub fn new<P: AsRef<Path>>(fst_dir: P, config: NormalizerConfig) -> Self {
+        let mut cache = FstCache::new(fst_dir);
+        cache.search = config.search;
+        cache.invalid_utf8 = config.invalid_utf8;
+        cache.compact = config.compact_fsts;
         Self {
             config,
-            cache: FstCache::new(fst_dir),
+            cache,
+            no_matches: 0,
+            unmatched: Vec::new(),
+            predicate: None,
+            punctuation: None,
+            segmenter: None,
         }
     }
 
@@ -74,254 +294,3632 @@ impl Normalizer {
         Self::new(fst_dir, NormalizerConfig::default())
     }
 
+    /// Reload all cached FSTs from disk
+    ///
+    /// Useful for long-running services that need to pick up updated grammar
+    /// files without a restart. All FSTs are loaded before any of them replaces
+    /// its cached version, so on error the previously loaded FSTs stay in use.
+    pub fn reload(&mut self) -> Result<()> {
+        self.cache.reload_all()
+    }
+
+    /// Reload only the cached FSTs whose files have changed on disk
+    ///
+    /// # Returns
+    /// Relative paths (e.g. `"zh/tn/tagger.fst"`) of the FSTs that were reloaded
+    pub fn reload_changed(&mut self) -> Result<Vec<String>> {
+        self.cache.reload_changed()
+    }
+
+    /// Write the FSTs loaded so far to a single snapshot file
+    ///
+    /// FSTs are stored in their in-memory layout (compacted with
+    /// `compact_fsts`), together with the modification time of their source
+    /// file. Normalize some representative text first so that every FST
+    /// the service needs is loaded.
+    pub fn save_snapshot<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.cache.save_snapshot(path.as_ref())
+    }
+
+    /// Load FSTs from a snapshot written by `save_snapshot`
+    ///
+    /// Reading one file replaces opening and converting each FST, for
+    /// faster cold starts. FSTs whose source file changed after the snapshot
+    /// was taken are picked up by `reload_changed` or watch mode.
+    ///
+    /// # Returns
+    /// Relative paths of the FSTs loaded, sorted
+    pub fn load_snapshot<P: AsRef<Path>>(&mut self, path: P) -> Result<Vec<String>> {
+        self.cache.load_snapshot(path.as_ref())
+    }
+
+    /// Metadata of the FSTs loaded so far, sorted by relative path
+    pub fn loaded_fsts(&self) -> Vec<(String, FstInfo)> {
+        let mut fsts: Vec<(String, FstInfo)> = self
+            .cache
+            .fsts
+            .iter()
+            .map(|(path, cached)| (path.clone(), cached.normalizer.info()))
+            .collect();
+        fsts.sort_by(|a, b| a.0.cmp(&b.0));
+        fsts
+    }
+
+    /// Decide with `predicate` whether text needs the FST pipeline
+    ///
+    /// Replaces the built-in check (`tn_trigger` for TN, non-empty for ITN),
+    /// for deployments whose custom grammars match input the crate can't
+    /// anticipate. Text for which `predicate` returns false skips the
+    /// tagger and verbalizer; the other stages still run.
+    pub fn set_should_normalize<F>(&mut self, predicate: F)
+    where
+        F: Fn(&str, Operator) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Arc::new(predicate));
+    }
+
+    /// Restore the built-in check for whether text needs the FST pipeline
+    pub fn clear_should_normalize(&mut self) {
+        self.predicate = None;
+    }
+
+    /// Punctuate ITN input with an external model
+    ///
+    /// The restorer runs on the spoken text before ITN. Marks it places
+    /// inside an entity ("一百，二十三") are moved to the end of the entity,
+    /// so they don't split it for the tagger. The input is tagged once more
+    /// to find the entities.
+    pub fn set_punctuation_restorer<R>(&mut self, restorer: R)
+    where
+        R: PunctuationRestorer + 'static,
+    {
+        self.punctuation = Some(Arc::new(restorer));
+    }
+
+    /// Stop punctuating ITN input
+    pub fn clear_punctuation_restorer(&mut self) {
+        self.punctuation = None;
+    }
+
+    /// Keep entities from splitting the words of an external segmenter
+    ///
+    /// A word that a tagged entity covers only in part ("一会儿" tagged from
+    /// "一") is copied through unchanged, and the text before and after it
+    /// is normalized separately. The input is tagged once more to find the
+    /// entities.
+    pub fn set_segmenter<S>(&mut self, segmenter: S)
+    where
+        S: Segmenter + 'static,
+    {
+        self.segmenter = Some(Arc::new(segmenter));
+    }
+
+    /// Stop segmenting input
+    pub fn clear_segmenter(&mut self) {
+        self.segmenter = None;
+    }
+
+    /// Enable or disable watch mode
+    ///
+    /// In watch mode, the modification time of each FST file is checked
+    /// whenever it is used, and changed files are reloaded transparently.
+    pub fn set_watch(&mut self, watch: bool) {
+        self.cache.watch = watch;
+    }
+
+    /// Scan the FST directory for the pipelines and options it supports
+    ///
+    /// A pipeline is listed when both its tagger and verbalizer are present,
+    /// and an option variant when its FSTs differ from the default ones and
+    /// are present. Languages not compiled in are skipped.
+    pub fn capabilities(&self) -> Capabilities {
+        const VARIANTS: &[&str] = &["enable_0_to_9", "remove_erhua"];
+        const PROCESSING: &[(&str, &str)] = &[
+            ("traditional_to_simple", "traditional_to_simple.fst"),
+            ("full_to_half", "full_to_half.fst"),
+            ("remove_interjections", "remove_interjections.fst"),
+            ("remove_puncts", "remove_puncts.fst"),
+            ("tag_oov", "tag_oov.fst"),
+        ];
+
+        let exists = |path: &str| self.cache.fst_dir.join(path).is_file();
+        let paths = |lang, config: &NormalizerConfig| {
+            Some((
+                Self::tagger_path(lang, config).ok()?,
+                Self::verbalizer_path(lang, config).ok()?,
+            ))
+        };
+
+        let mut pipelines = Vec::new();
+        for lang in [Language::Zh, Language::En, Language::Ja] {
+            for operator in [Operator::Tn, Operator::Itn] {
+                let base = NormalizerConfig::new().with_operator(operator);
+                let Some(default) = paths(lang, &base) else {
+                    continue;
+                };
+                if exists(default.0) && exists(default.1) {
+                    pipelines.push(Capability {
+                        lang,
+                        operator,
+                        option: None,
+                    });
+                }
+                for &option in VARIANTS {
+                    let config = match option {
+                        "enable_0_to_9" => base.clone().with_enable_0_to_9(true),
+                        _ => base.clone().with_remove_erhua(true),
+                    };
+                    let Some(variant) = paths(lang, &config) else {
+                        continue;
+                    };
+                    if variant != default && exists(variant.0) && exists(variant.1) {
+                        pipelines.push(Capability {
+                            lang,
+                            operator,
+                            option: Some(option),
+                        });
+                    }
+                }
+            }
+        }
+
+        let processing = PROCESSING
+            .iter()
+            .filter(|(_, path)| exists(path))
+            .map(|&(option, _)| option)
+            .collect();
+        Capabilities {
+            pipelines,
+            processing,
+        }
+    }
+
+    /// Warnings recorded so far
+    ///
+    /// With `fst_fallback` enabled, a warning is recorded the first time a
+    /// missing FST is replaced by its fallback. In watch mode, one is
+    /// recorded when a changed FST fails to reload.
+    pub fn warnings(&self) -> &[String] {
+        &self.cache.warnings
+    }
+
+    /// Number of inputs passed through because an FST accepted no path for
+    /// them, counted under `NoMatchPolicy::PassthroughWithWarning`
+    pub fn no_match_count(&self) -> u64 {
+        self.no_matches
+    }
+
+    /// Spans the tagger accepted no path for, even on their own, during the
+    /// last call to `normalize` or `normalize_with_config`
+    ///
+    /// Only recorded with `partial_match` enabled.
+    pub fn unmatched_spans(&self) -> &[String] {
+        &self.unmatched
+    }
+
     /// Normalize text using the configured settings
     pub fn normalize(&mut self, text: &str) -> Result<String> {
         self.normalize_with_config(text, &self.config.clone())
     }
 
+    /// Normalize text, borrowing the input when nothing would change
+    ///
+    /// Pure-ASCII TN input without digits (most English prose) is returned
+    /// as `Cow::Borrowed` without running the pipeline, unless an option that
+    /// can rewrite such text is enabled. The result always equals `normalize`.
+    pub fn normalize_cow<'a>(&mut self, text: &'a str) -> Result<Cow<'a, str>> {
+        if self.is_passthrough(text, &self.config) {
+            return Ok(Cow::Borrowed(text));
+        }
+        let config = self.config.clone();
+        Ok(Cow::Owned(self.normalize_detected(text, &config)?.0))
+    }
+
+    /// Normalize text, appending the result to `output`
+    ///
+    /// For tight loops that reuse one buffer: the configuration is not
+    /// cloned as in `normalize`, and text that needs no normalization is
+    /// copied into `output` without allocating once it has grown large
+    /// enough. On error, `output` is left unchanged.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut buffer = String::new();
+    /// for line in lines {
+    ///     buffer.clear();
+    ///     normalizer.normalize_into(&mut buffer, line)?;
+    ///     synthesize(&buffer);
+    /// }
+    /// ```
+    pub fn normalize_into(&mut self, output: &mut String, text: &str) -> Result<()> {
+        let config = std::mem::take(&mut self.config);
+        let result = if self.is_passthrough(text, &config) {
+            output.push_str(text);
+            Ok(())
+        } else {
+            self.normalize_detected(text, &config)
+                .map(|(normalized, _)| output.push_str(&normalized))
+        };
+        self.config = config;
+        result
+    }
+
+    /// Normalize several texts into reused buffers
+    ///
+    /// `outputs` is resized to the number of texts and each buffer is
+    /// cleared and filled with the normalized text, keeping its capacity.
+    /// Stops at the first error.
+    pub fn normalize_batch_into<S: AsRef<str>>(
+        &mut self,
+        texts: &[S],
+        outputs: &mut Vec<String>,
+    ) -> Result<()> {
+        outputs.resize_with(texts.len(), String::new);
+        for (text, output) in texts.iter().zip(outputs.iter_mut()) {
+            output.clear();
+            self.normalize_into(output, text.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Normalize text with some options changed for this call only
+    ///
+    /// Unlike `normalize_with_config`, the configuration is not cloned: the
+    /// overridden fields are swapped in and restored afterwards.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let overrides = Overrides {
+    ///     lang: Some(Language::En),
+    ///     remove_puncts: Some(false),
+    ///     ..Default::default()
+    /// };
+    /// let result = normalizer.normalize_with("$100", &overrides)?;
+    /// ```
+    pub fn normalize_with(&mut self, text: &str, overrides: &Overrides) -> Result<String> {
+        let mut config = std::mem::take(&mut self.config);
+        let restore = overrides.apply(&mut config);
+        let result = self.normalize_with_config(text, &config);
+        restore.apply(&mut config);
+        self.config = config;
+        result
+    }
+
+    /// Normalize text with a language detection hint for this call only
+    ///
+    /// In `Language::Auto` mode, text the detector can't decide on, such as
+    /// kanji-only text, is normalized in the hinted language. A configured
+    /// language is not affected.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Kanji-only input on a Japanese channel
+    /// let result = normalizer.normalize_with_hint("東京都123番地", LanguageHint::PreferJa)?;
+    /// ```
+    pub fn normalize_with_hint(&mut self, text: &str, hint: LanguageHint) -> Result<String> {
+        let overrides = Overrides {
+            language_hint: Some(hint),
+            ..Default::default()
+        };
+        self.normalize_with(text, &overrides)
+    }
+
     /// Normalize text with a specific configuration
     pub fn normalize_with_config(
         &mut self,
         text: &str,
         config: &NormalizerConfig,
     ) -> Result<String> {
-        let mut text = text.to_string();
-
-        // 1. Fix English contractions
-        if config.fix_contractions && text.contains('\'') {
-            text = fix_contractions(&text);
+        self.unmatched.clear();
+        if self.is_passthrough(text, config) {
+            return Ok(text.to_string());
         }
+        let search = std::mem::replace(&mut self.cache.search, config.search);
+        let invalid_utf8 = std::mem::replace(&mut self.cache.invalid_utf8, config.invalid_utf8);
+        let result = self.normalize_detected(text, config);
+        self.cache.search = search;
+        self.cache.invalid_utf8 = invalid_utf8;
+        Ok(result?.0)
+    }
 
-        // 2. Preprocessing
-        text = self.preprocess(&text, config)?;
+    /// Whether the pipeline would return `text` unchanged, checked cheaply
+    ///
+    /// TN only rewrites ASCII text that `should_normalize` accepts, except
+    /// through the options listed here, whitespace trimming and contraction fixing.
+    fn is_passthrough(&self, text: &str, config: &NormalizerConfig) -> bool {
+        if config.operator != Operator::Tn
+            || !text.is_ascii()
+            || self.should_normalize(text, config)
+            || text.trim().len() != text.len()
+            || (config.fix_contractions && text.contains('\''))
+        {
+            return false;
+        }
+        let lang = match config.lang {
+            Language::Auto => Self::detect_language(text, config),
+            lang => lang,
+        };
+        lang.is_enabled()
+            && config.rule_classes.is_empty()
+            && config.pause_hints.is_none()
+            && config.masking.is_none()
+            && config.output_script == OutputScript::Hanzi
+            && !config.traditional_to_simple
+            && !config.full_to_half
+            && !config.remove_interjections
+            && !config.remove_puncts
+            && !config.tag_oov
+    }
 
-        // 3. Detect language
-        let lang = if config.lang == Language::Auto {
-            Self::detect_language(&text)
+    /// Normalize text, also returning the language it was normalized as
+    fn normalize_detected(
+        &mut self,
+        text: &str,
+        config: &NormalizerConfig,
+    ) -> Result<(String, Language)> {
+        let punctuated;
+        let text = if config.operator == Operator::Itn && self.punctuation.is_some() {
+            punctuated = self.restore_punctuation(text, config)?;
+            punctuated.as_str()
         } else {
-            config.lang
+            text
         };
 
-        // 4. Check if normalization is needed
-        if self.should_normalize(&text, config.operator, config.remove_erhua) {
-            // English ITN is not supported in Python wetext (raises NotImplementedError).
-            // Fallback to Chinese ITN as a workaround, matching Python behavior.
-            let lang = if lang == Language::En && config.operator == Operator::Itn {
-                Language::Zh
-            } else {
-                lang
-            };
+        // 1-3. Fix contractions, preprocess and detect language
+        let (mut text, lang) = self.prepare(text, config)?;
+
+        // 4. Tag, reorder and verbalize, routing rule-handled spans around the
+        // FST and keeping words the tagger would split
+        text = self.run_segmented(&text, lang, config)?;
 
-            // 4.1 Tagger: tag entities
-            text = self.tag(&text, lang, config)?;
+        // ITN output years are converted after the FST has written them as digits
+        #[cfg(feature = "lang-ja")]
+        if lang == Language::Ja && config.operator == Operator::Itn {
+            text = japanese::convert_era(&text, config.era_style);
+        }
 
-            // 4.2 Reorder: reorder token fields
-            text = self.reorder(&text, lang, config.operator)?;
+        // The ITN FSTs have no erhua-removing variant, so strip it here
+        if config.operator == Operator::Itn && config.remove_erhua {
+            text = remove_erhua(&text);
+        }
 
-            // 4.3 Verbalizer: convert to spoken form
-            text = self.verbalize(&text, lang, config)?;
+        if let Some(options) = config
+            .beautify
+            .as_ref()
+            .filter(|_| config.operator == Operator::Itn)
+        {
+            text = beautify::beautify(&text, options);
+        }
+        if config.truecase && config.operator == Operator::Itn {
+            text = truecase(&text);
         }
 
         // 5. Postprocessing
         text = self.postprocess(&text, config)?;
+        if let Some(masking) = &config.masking {
+            text = mask::mask(&text, masking);
+        }
+
+        // 6. Phonetic output script
+        if config.operator == Operator::Tn {
+            text = script::to_script(&text, config.output_script, lang);
+        }
 
-        Ok(text)
+        Ok((text, lang))
     }
 
-    /// Detect text language
-    ///
-    /// **Note:** This implementation extends the original Python version with Japanese detection.
-    /// Python wetext only detects Chinese vs English. This Rust version adds Japanese support
-    /// by detecting Hiragana/Katakana characters.
+    /// Normalize text one sentence at a time
     ///
-    /// Detection priority:
-    /// 1. Japanese (Hiragana/Katakana) - Rust extension, not in Python version
-    /// 2. Chinese (CJK Unified Ideographs)
-    /// 3. Numeric-only text (digits, punctuation, symbols) - treated as Chinese
-    /// 4. Default to English
-    fn detect_language(text: &str) -> Language {
-        let mut has_cjk = false;
-        let mut has_alpha = false;
-
-        for ch in text.chars() {
-            // [Rust Extension] Japanese detection via Hiragana/Katakana
-            // Japanese Hiragana: U+3040 - U+309F
-            // Japanese Katakana: U+30A0 - U+30FF
-            // Note: Python wetext does NOT have this detection - it would return "zh" for Japanese text
-            if ('\u{3040}'..='\u{309f}').contains(&ch) || ('\u{30a0}'..='\u{30ff}').contains(&ch) {
-                return Language::Ja;
+    /// Sentences end at `。！？；!?;`, line breaks, and full stops followed by
+    /// whitespace. Each is normalized separately and the whitespace between
+    /// them is kept, so the output can later be updated with `renormalize_edit`.
+    pub fn normalize_sentences(&mut self, text: &str) -> Result<String> {
+        let mut output = String::with_capacity(text.len());
+        for sentence in stream::split_sentences(text) {
+            let body = sentence.trim();
+            if body.is_empty() {
+                output.push_str(sentence);
+                continue;
             }
+            let leading = &sentence[..sentence.len() - sentence.trim_start().len()];
+            let trailing = &sentence[sentence.trim_end().len()..];
+            output.push_str(leading);
+            output.push_str(&self.normalize(body)?);
+            output.push_str(trailing);
+        }
+        Ok(output)
+    }
 
-            // CJK Unified Ideographs: U+4E00 - U+9FFF
-            // Note: These are shared between Chinese and Japanese
-            // If we find hiragana/katakana, it's Japanese; otherwise treat as Chinese
-            if ('\u{4e00}'..='\u{9fff}').contains(&ch) {
-                has_cjk = true;
+    /// Normalize a multilingual document sentence by sentence
+    ///
+    /// Each sentence (as split by `normalize_sentences`) is normalized on
+    /// its own and returned with the language it was normalized as. With
+    /// `Language::Auto` the language is detected per sentence, so documents
+    /// mixing Chinese, English and Japanese get routing metadata for each
+    /// part. Blank text between sentences is not returned.
+    pub fn normalize_document(&mut self, text: &str) -> Result<Document> {
+        let config = self.config.clone();
+        let mut segments = Vec::new();
+        for sentence in stream::split_sentences(text) {
+            let sentence = sentence.trim();
+            if sentence.is_empty() {
+                continue;
             }
+            let (normalized, lang) = self.normalize_detected(sentence, &config)?;
+            segments.push(DocumentSegment {
+                text: sentence.to_string(),
+                lang,
+                normalized,
+            });
+        }
+        Ok(Document { segments })
+    }
 
-            // Track if there are any ASCII alphabetic characters
-            if ch.is_ascii_alphabetic() {
-                has_alpha = true;
-            }
+    /// Re-normalize text after an edit, reusing the output of unchanged sentences
+    ///
+    /// `previous_output` must be the output of `normalize_sentences` (or of
+    /// this method) for `previous_input`. The bytes in `edit` of
+    /// `previous_input` are replaced with `replacement`, and only the
+    /// sentences touched by the edit are normalized again. If the previous
+    /// output doesn't split into as many sentences as the input (e.g. with
+    /// `remove_puncts`), the whole text is normalized again.
+    ///
+    /// # Returns
+    /// The edited input and its normalized output
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let output = normalizer.normalize_sentences("共5元。共6元。").unwrap();
+    /// let (input, output) = normalizer
+    ///     .renormalize_edit("共5元。共6元。", &output, 13..14, "7")
+    ///     .unwrap();
+    /// // "共五元。共七元。", with the first sentence reused
+    /// ```
+    pub fn renormalize_edit(
+        &mut self,
+        previous_input: &str,
+        previous_output: &str,
+        edit: Range<usize>,
+        replacement: &str,
+    ) -> Result<(String, String)> {
+        if edit.start > edit.end
+            || edit.end > previous_input.len()
+            || !previous_input.is_char_boundary(edit.start)
+            || !previous_input.is_char_boundary(edit.end)
+        {
+            return Err(WeTextError::InvalidRange(format!(
+                "{:?} in text of {} bytes",
+                edit,
+                previous_input.len()
+            )));
         }
+        let input = format!(
+            "{}{}{}",
+            &previous_input[..edit.start],
+            replacement,
+            &previous_input[edit.end..]
+        );
 
-        // If contains CJK but no Japanese-specific characters, treat as Chinese
-        if has_cjk {
-            return Language::Zh;
+        let sentences = stream::split_sentences(previous_input);
+        let outputs = stream::split_sentences(previous_output);
+        if sentences.is_empty() || sentences.len() != outputs.len() {
+            let output = self.normalize_sentences(&input)?;
+            return Ok((input, output));
         }
 
-        // Numeric-only text (no alphabetic characters) treated as Chinese
-        // This covers cases like "123", "3/4", "1.5", "2024年" (when year char is not present)
-        if !text.is_empty() && !has_alpha {
-            return Language::Zh;
+        // Byte range of each sentence in the previous input
+        let mut bounds = Vec::with_capacity(sentences.len());
+        let mut pos = 0;
+        for sentence in &sentences {
+            bounds.push(pos..pos + sentence.len());
+            pos += sentence.len();
         }
 
-        Language::En
+        // Sentences ending at the edit may merge with it, as may the one after it
+        let first = bounds
+            .iter()
+            .position(|r| r.end >= edit.start)
+            .unwrap_or(bounds.len() - 1);
+        let last = bounds
+            .iter()
+            .position(|r| r.end > edit.end)
+            .unwrap_or(bounds.len() - 1);
+        let start = bounds[first].start;
+        let end = bounds[last].end + replacement.len() - (edit.end - edit.start);
+
+        let mut output = outputs[..first].concat();
+        output.push_str(&self.normalize_sentences(&input[start..end])?);
+        output.push_str(&outputs[last + 1..].concat());
+        Ok((input, output))
     }
 
-    /// Check if normalization is needed
-    fn should_normalize(&self, text: &str, operator: Operator, remove_erhua: bool) -> bool {
-        if operator == Operator::Tn {
-            // TN: needs normalization if contains digits
-            if text.chars().any(|c| c.is_ascii_digit()) {
-                return true;
-            }
-            // Or if need to remove erhua
-            if remove_erhua && (text.contains('儿') || text.contains('兒')) {
-                return true;
-            }
-            false
-        } else {
-            // ITN: non-empty text needs processing
-            !text.is_empty()
+    /// Read text character by character
+    ///
+    /// Letters and digits are read by name in the configured language, for
+    /// serial numbers, captchas and booking codes. The FSTs are not used.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let spoken = normalizer.normalize_spellout("AB12");
+    /// // "诶比一二" for Chinese, "ay bee one two" for English
+    /// ```
+    pub fn normalize_spellout(&self, text: &str) -> String {
+        let lang = match self.config.lang {
+            Language::Auto => Self::detect_language(text, &self.config),
+            lang => lang,
+        };
+        spell::spell_out(text, lang)
+    }
+
+    /// Normalize text and return aligned written/spoken pairs
+    ///
+    /// Each tagged entity becomes one span holding its original written form
+    /// and its spoken form; the text between entities is returned as
+    /// unchanged spans. Concatenating the `written` fields gives back the
+    /// preprocessed input. Postprocessing stages are not applied.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let spans = normalizer.normalize_dual("今天是2024年").unwrap();
+    /// // [("今天是", "今天是"), ("2024年", "二零二四年")]
+    /// ```
+    pub fn normalize_dual(&mut self, text: &str) -> Result<Vec<DualSpan>> {
+        let mut spans = self.dual_spans(text)?;
+        if let Some(masking) = &self.config.masking {
+            mask::mask_pieces(spans.iter_mut().map(|span| &mut span.spoken), masking);
         }
+        Ok(spans)
     }
 
-    /// Preprocessing step
-    fn preprocess(&mut self, text: &str, config: &NormalizerConfig) -> Result<String> {
-        let mut result = text.trim().to_string();
+    /// Spans of `normalize_dual`, before masking
+    fn dual_spans(&mut self, text: &str) -> Result<Vec<DualSpan>> {
+        let config = self.config.clone();
+        let (text, lang) = self.prepare(text, &config)?;
+        if text.is_empty() {
+            return Ok(Vec::new());
+        }
+        if !self.should_normalize(&text, &config) {
+            return Ok(vec![DualSpan::unchanged(&text)]);
+        }
 
-        if config.traditional_to_simple {
-            let fst = self.cache.get_or_load("traditional_to_simple.fst")?;
-            result = fst.normalize(&result)?;
+        let lang = Self::pipeline_lang(lang, config.operator);
+        let tagged = self.tag_with(&text, lang, &config)?;
+        let parser = Self::token_parser(lang, &config);
+        let mut tokens = match parser.parse(&tagged) {
+            Ok(tokens) if !tokens.is_empty() => tokens,
+            _ => {
+                // Not in token format: the whole text is one span
+                let spoken = self.run_fst(&text, lang, &config)?;
+                return Ok(vec![DualSpan::normalized(&text, &spoken)]);
+            }
+        };
+        if rewrite::is_active(&config) {
+            rewrite::rewrite_tokens(&mut tokens, lang, &config);
         }
 
-        Ok(result)
-    }
+        let mut pieces = Vec::with_capacity(tokens.len());
+        for token in &tokens {
+            if token.name == "char" {
+                let value = token.members.get("value").cloned().unwrap_or_default();
+                pieces.push(Piece::Char(value));
+            } else {
+                let spoken = self.verbalize_with(&parser.serialize(token), lang, &config)?;
+                pieces.push(Piece::Entity(Self::english_pass(spoken, lang, &config)));
+            }
+        }
 
-    /// Postprocessing step
-    fn postprocess(&mut self, text: &str, config: &NormalizerConfig) -> Result<String> {
-        let mut result = text.to_string();
+        let separator = if lang == Language::En { " " } else { "" };
+        Ok(report::align_dual(&text, &pieces, separator))
+    }
 
-        if config.full_to_half {
-            let fst = self.cache.get_or_load("full_to_half.fst")?;
-            result = fst.normalize(&result)?;
+    /// Normalize text and return it split into tokens
+    ///
+    /// Each tagged token becomes one segment: plain characters (or English
+    /// words) are unlabeled, and each entity keeps its token type as label,
+    /// so downstream prosody models can tell which words came from numbers.
+    /// Postprocessing stages are not applied.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let segments = normalizer.normalize_segmented("共100元").unwrap();
+    /// // [("共", None), ("一百元", Some("money"))]
+    /// ```
+    pub fn normalize_segmented(&mut self, text: &str) -> Result<Vec<Segment>> {
+        let mut segments = self.segments(text)?;
+        if let Some(masking) = &self.config.masking {
+            mask::mask_pieces(
+                segments.iter_mut().map(|segment| &mut segment.text),
+                masking,
+            );
         }
+        Ok(segments)
+    }
 
-        if config.remove_interjections {
-            let fst = self.cache.get_or_load("remove_interjections.fst")?;
-            result = fst.normalize(&result)?;
+    /// Segments of `normalize_segmented`, before masking
+    fn segments(&mut self, text: &str) -> Result<Vec<Segment>> {
+        let config = self.config.clone();
+        let (text, lang) = self.prepare(text, &config)?;
+        if text.is_empty() {
+            return Ok(Vec::new());
+        }
+        let plain = |text: &str| Segment {
+            text: text.to_string(),
+            label: None,
+        };
+        if !self.should_normalize(&text, &config) {
+            return Ok(text.split_whitespace().map(plain).collect());
         }
 
-        if config.remove_puncts {
-            let fst = self.cache.get_or_load("remove_puncts.fst")?;
-            result = fst.normalize(&result)?;
+        let lang = Self::pipeline_lang(lang, config.operator);
+        let tagged = self.tag_with(&text, lang, &config)?;
+        let parser = Self::token_parser(lang, &config);
+        let mut tokens = match parser.parse(&tagged) {
+            Ok(tokens) if !tokens.is_empty() => tokens,
+            _ => {
+                let spoken = self.run_fst(&text, lang, &config)?;
+                return Ok(vec![plain(&spoken)]);
+            }
+        };
+        if rewrite::is_active(&config) {
+            rewrite::rewrite_tokens(&mut tokens, lang, &config);
         }
 
-        if config.tag_oov {
-            let fst = self.cache.get_or_load("tag_oov.fst")?;
-            result = fst.normalize(&result)?;
+        let mut segments = Vec::with_capacity(tokens.len());
+        for token in &tokens {
+            if token.name == "char" {
+                let value = token.get("value").unwrap_or_default();
+                if !value.trim().is_empty() {
+                    segments.push(plain(value));
+                }
+            } else {
+                let spoken = self.verbalize_with(&parser.serialize(token), lang, &config)?;
+                segments.push(Segment {
+                    text: Self::english_pass(spoken, lang, &config),
+                    label: Some(token.name.clone()),
+                });
+            }
         }
+        Ok(segments)
+    }
 
-        Ok(result.trim().to_string())
+    /// Normalize text and return each entity with its type and written form
+    ///
+    /// Like `normalize_dual`, but every normalized span is labeled with the
+    /// token type it was tagged as, so applications can tell what was a
+    /// date, what was money and what was left untouched. Untouched text has
+    /// no category. Postprocessing stages are not applied.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let segments = normalizer.normalize_tokens("共100元").unwrap();
+    /// // [("一百元", Some("money"), "100元")] after the untouched "共"
+    /// ```
+    pub fn normalize_tokens(&mut self, text: &str) -> Result<Vec<NormalizedSegment>> {
+        let mut segments = self.token_segments(text)?;
+        if let Some(masking) = &self.config.masking {
+            mask::mask_pieces(
+                segments.iter_mut().map(|segment| &mut segment.text),
+                masking,
+            );
+        }
+        Ok(segments)
     }
 
-    /// Tag entities using tagger FST
-    fn tag(&mut self, text: &str, lang: Language, config: &NormalizerConfig) -> Result<String> {
-        let fst_path = match (lang, config.operator) {
-            (Language::En, Operator::Tn) => "en/tn/tagger.fst",
-            (Language::Zh, Operator::Tn) => "zh/tn/tagger.fst",
-            (Language::Zh, Operator::Itn) => {
-                if config.enable_0_to_9 {
-                    "zh/itn/tagger_enable_0_to_9.fst"
+    /// Segments of `normalize_tokens`, before masking
+    fn token_segments(&mut self, text: &str) -> Result<Vec<NormalizedSegment>> {
+        let config = self.config.clone();
+        let (text, lang) = self.prepare(text, &config)?;
+        if text.is_empty() {
+            return Ok(Vec::new());
+        }
+        if !self.should_normalize(&text, &config) {
+            return Ok(report::label_spans(vec![DualSpan::unchanged(&text)], &[]));
+        }
+
+        let lang = Self::pipeline_lang(lang, config.operator);
+        let tagged = self.tag_with(&text, lang, &config)?;
+        let parser = Self::token_parser(lang, &config);
+        let mut tokens = match parser.parse(&tagged) {
+            Ok(tokens) if !tokens.is_empty() => tokens,
+            _ => {
+                let spoken = self.run_fst(&text, lang, &config)?;
+                return Ok(vec![NormalizedSegment {
+                    text: spoken,
+                    category: None,
+                    original: text,
+                }]);
+            }
+        };
+        if rewrite::is_active(&config) {
+            rewrite::rewrite_tokens(&mut tokens, lang, &config);
+        }
+
+        let mut pieces = Vec::with_capacity(tokens.len());
+        let mut runs: Vec<Vec<String>> = Vec::new();
+        let mut in_run = false;
+        for token in &tokens {
+            if token.name == "char" {
+                pieces.push(Piece::Char(
+                    token.get("value").unwrap_or_default().to_string(),
+                ));
+                in_run = false;
+            } else {
+                let spoken = self.verbalize_with(&parser.serialize(token), lang, &config)?;
+                pieces.push(Piece::Entity(Self::english_pass(spoken, lang, &config)));
+                if !in_run {
+                    runs.push(Vec::new());
+                }
+                runs.last_mut().unwrap().push(token.name.clone());
+                in_run = true;
+            }
+        }
+
+        let separator = if lang == Language::En { " " } else { "" };
+        let spans = report::align_dual(&text, &pieces, separator);
+        Ok(report::label_spans(spans, &runs))
+    }
+
+    /// Find the spans normalization would rewrite, without verbalizing them
+    ///
+    /// Runs only the tagger of the configured language and operator, so it
+    /// is cheap enough to highlight candidates while the user types. Spans
+    /// are byte ranges into `text`; consecutive entities form one span.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let spans = normalizer.find_normalizable_spans("共100元").unwrap();
+    /// // [CandidateSpan { span: 3..9, category: "money", original: "100元" }]
+    /// ```
+    pub fn find_normalizable_spans(&mut self, text: &str) -> Result<Vec<CandidateSpan>> {
+        let config = self.config.clone();
+        let (entities, _) = self.tag_entities_with(text, &config)?;
+        let mut spans: Vec<CandidateSpan> = Vec::new();
+        for (token, span) in entities {
+            match spans.last_mut() {
+                Some(last) if last.span == span => {
+                    if !last.category.split('+').any(|name| name == token.name) {
+                        last.category.push('+');
+                        last.category.push_str(&token.name);
+                    }
+                }
+                _ => spans.push(CandidateSpan {
+                    original: text.get(span.clone()).unwrap_or_default().to_string(),
+                    span,
+                    category: token.name,
+                }),
+            }
+        }
+        Ok(spans)
+    }
+
+    /// Extract the numbers the tagger finds in text
+    ///
+    /// Dates give their year, month and day, money and measures their
+    /// amount, and cardinals, decimals, ordinals and fractions their value.
+    /// The configured operator decides which tagger reads the text, so ITN
+    /// extracts from spoken text ("二零二四年") and TN from written text.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let numbers = normalizer.extract_numbers("共100元", Language::Zh).unwrap();
+    /// // [ExtractedNumber { span: 3..9, value: Integer(100), kind: Money }]
+    /// ```
+    pub fn extract_numbers(&mut self, text: &str, lang: Language) -> Result<Vec<ExtractedNumber>> {
+        let (entities, lang) = self.tag_entities(text, lang)?;
+        Ok(extract::entity_numbers(entities, lang))
+    }
+
+    /// Find full dates in text
+    ///
+    /// Uses the tagger of the configured operator for `lang`. Dates missing
+    /// a year, month or day are skipped.
+    ///
+    /// # Returns
+    /// Byte range of each date in `text`, with its value
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let dates = normalizer.parse_dates("2024年1月15日", Language::Zh).unwrap();
+    /// // [(0..16, Date { year: 2024, month: 1, day: 15 })]
+    /// ```
+    pub fn parse_dates(&mut self, text: &str, lang: Language) -> Result<Vec<(Range<usize>, Date)>> {
+        let (entities, lang) = self.tag_entities(text, lang)?;
+        Ok(entities
+            .into_iter()
+            .filter_map(|(token, span)| Some((span, extract::date(&token, lang)?)))
+            .collect())
+    }
+
+    /// Find amounts of money in text
+    ///
+    /// Uses the tagger of the configured operator for `lang`.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let money = normalizer.parse_money("共100元", Language::Zh).unwrap();
+    /// // [ExtractedMoney { span: 3..9, currency: Some("CNY"), amount: Integer(100) }]
+    /// ```
+    pub fn parse_money(&mut self, text: &str, lang: Language) -> Result<Vec<ExtractedMoney>> {
+        let (entities, lang) = self.tag_entities(text, lang)?;
+        Ok(entities
+            .into_iter()
+            .filter_map(|(token, span)| {
+                let (currency, amount) = extract::money(&token, lang)?;
+                Some(ExtractedMoney {
+                    span,
+                    currency,
+                    amount,
+                })
+            })
+            .collect())
+    }
+
+    /// Find times of day in text
+    ///
+    /// Uses the tagger of the configured operator for `lang`. Hours are
+    /// returned on the 24-hour clock, with the period of day kept as tagged.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let times = normalizer.parse_times("下午3点30分", Language::Zh).unwrap();
+    /// // [(0..15, Time { hour: 15, minute: 30, second: 0, noon: Some("下午") })]
+    /// ```
+    pub fn parse_times(&mut self, text: &str, lang: Language) -> Result<Vec<(Range<usize>, Time)>> {
+        let (entities, lang) = self.tag_entities(text, lang)?;
+        Ok(entities
+            .into_iter()
+            .filter_map(|(token, span)| Some((span, extract::time(&token, lang)?)))
+            .collect())
+    }
+
+    /// Find measures in text
+    ///
+    /// Uses the tagger of the configured operator for `lang`. Common units
+    /// are mapped to a canonical symbol and the quantity they measure.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let measures = normalizer.parse_measures("时速100km/h", Language::Zh).unwrap();
+    /// // value: Integer(100), symbol: Some("km/h"), quantity: Some(Speed)
+    /// ```
+    pub fn parse_measures(&mut self, text: &str, lang: Language) -> Result<Vec<ExtractedMeasure>> {
+        let (entities, lang) = self.tag_entities(text, lang)?;
+        Ok(entities
+            .into_iter()
+            .filter_map(|(token, span)| extract::measure(&token, lang, span))
+            .collect())
+    }
+
+    /// Check that the numbers of normalized text read back as in the original
+    ///
+    /// The original is tagged with the TN tagger and the normalized text
+    /// with the ITN tagger of the same language, and the numbers they carry
+    /// (dates, amounts, cardinals) are compared in order. A safety net for
+    /// readouts where a wrong number is costly. Needs the ITN FSTs of the
+    /// language.
+    ///
+    /// # Returns
+    /// The numbers that differ, were lost or were added; empty if all match
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let mismatches = normalizer.verify_readback("共100元", "共一千元").unwrap();
+    /// // [ReadbackMismatch { expected: Some(.. Integer(100) ..), found: Some(.. Integer(1000) ..) }]
+    /// ```
+    pub fn verify_readback(
+        &mut self,
+        original: &str,
+        normalized: &str,
+    ) -> Result<Vec<ReadbackMismatch>> {
+        let config = self.config.clone().with_operator(Operator::Tn);
+        let (written, lang) = self.tag_entities_with(original, &config)?;
+        let config = config.with_lang(lang).with_operator(Operator::Itn);
+        let (spoken, itn_lang) = self.tag_entities_with(normalized, &config)?;
+
+        Ok(extract::compare(
+            extract::entity_numbers(written, lang),
+            extract::entity_numbers(spoken, itn_lang),
+        ))
+    }
+
+    /// Run the punctuation restorer, keeping its marks out of entities
+    fn restore_punctuation(&mut self, text: &str, config: &NormalizerConfig) -> Result<String> {
+        let Some(restorer) = &self.punctuation else {
+            return Ok(text.to_string());
+        };
+        let restored = restorer.restore(text)?;
+        let edits = punctuation::edits(text, &restored)?;
+        if edits.is_empty() {
+            return Ok(restored);
+        }
+        let (entities, _) = self.tag_entities_with(text, config)?;
+        let spans: Vec<Range<usize>> = entities.into_iter().map(|(_, span)| span).collect();
+        Ok(punctuation::apply(text, edits, &spans))
+    }
+
+    /// Tag text and locate each entity token in it
+    ///
+    /// Ranges are byte offsets into `text`, or into the preprocessed text if
+    /// preprocessing changed more than surrounding whitespace. Consecutive
+    /// entities share the range of their run.
+    fn tag_entities(&mut self, text: &str, lang: Language) -> Result<(TaggedEntities, Language)> {
+        let config = self.config.clone().with_lang(lang);
+        self.tag_entities_with(text, &config)
+    }
+
+    /// Tag text with the given configuration and locate each entity token in it
+    fn tag_entities_with(
+        &mut self,
+        text: &str,
+        config: &NormalizerConfig,
+    ) -> Result<(TaggedEntities, Language)> {
+        let (prepared, lang) = self.prepare(text, config)?;
+        if prepared.is_empty() || !self.should_normalize(&prepared, config) {
+            return Ok((Vec::new(), lang));
+        }
+
+        let lang = Self::pipeline_lang(lang, config.operator);
+        let base = text.find(prepared.as_str()).unwrap_or(0);
+        let entities = self
+            .locate_entities(&prepared, lang, config)?
+            .into_iter()
+            .map(|(token, range)| (token, base + range.start..base + range.end))
+            .collect();
+        Ok((entities, lang))
+    }
+
+    /// Tag prepared text and locate each entity token in it
+    fn locate_entities(
+        &mut self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<TaggedEntities> {
+        let tagged = self.tag_with(text, lang, config)?;
+        let tokens = Self::token_parser(lang, config).parse(&tagged)?;
+
+        let mut pieces = Vec::with_capacity(tokens.len());
+        let mut runs: Vec<Vec<usize>> = Vec::new();
+        let mut in_run = false;
+        for (index, token) in tokens.iter().enumerate() {
+            if token.name == "char" {
+                pieces.push(Piece::Char(
+                    token.get("value").unwrap_or_default().to_string(),
+                ));
+                in_run = false;
+            } else {
+                pieces.push(Piece::Entity(String::new()));
+                if !in_run {
+                    runs.push(Vec::new());
+                }
+                runs.last_mut().unwrap().push(index);
+                in_run = true;
+            }
+        }
+
+        let separator = if lang == Language::En { " " } else { "" };
+        let mut ranges = Vec::new();
+        let mut pos = 0;
+        for span in report::align_dual(text, &pieces, separator) {
+            if span.normalized {
+                ranges.push(pos..pos + span.written.len());
+            }
+            pos += span.written.len();
+        }
+        if ranges.len() != runs.len() {
+            ranges = vec![0..text.len(); runs.len()];
+        }
+
+        let mut tokens: Vec<Option<Token>> = tokens.into_iter().map(Some).collect();
+        let mut entities = Vec::new();
+        for (run, range) in runs.iter().zip(ranges) {
+            for &index in run {
+                if let Some(token) = tokens[index].take() {
+                    entities.push((token, range.clone()));
+                }
+            }
+        }
+        Ok(entities)
+    }
+
+    /// Normalize text, marking every rewritten span in the output
+    ///
+    /// Useful for auditing what the normalizer changed in a document.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let output = normalizer
+    ///     .normalize_annotated("今天是2024年", &Annotation::default())
+    ///     .unwrap();
+    /// // "今天是[[2024年→二零二四年]]"
+    /// ```
+    pub fn normalize_annotated(&mut self, text: &str, annotation: &Annotation) -> Result<String> {
+        let spans = self.normalize_dual(text)?;
+        Ok(report::annotate(&spans, annotation))
+    }
+
+    /// Pre-compose the tagger and verbalizer for the configured language
+    ///
+    /// Writes a single FST next to the tagger (e.g.
+    /// `zh/tn/precomposed_tagger_verbalizer.fst`) that `use_precomposed` picks
+    /// up at runtime, replacing two compose and shortest-path passes with one.
+    /// The reorder stage is skipped in that mode, so this is only valid for
+    /// grammars whose tagger already emits fields in verbalizer order.
+    ///
+    /// # Returns
+    /// Path of the written FST
+    pub fn precompose(&mut self) -> Result<PathBuf> {
+        let config = self.config.clone();
+        if config.lang == Language::Auto {
+            return Err(WeTextError::InvalidLanguage(
+                "precompose requires an explicit language".to_string(),
+            ));
+        }
+        let lang = Self::pipeline_lang(config.lang, config.operator);
+
+        let tagger_path = self.tagger_fst(lang, &config)?;
+        let verbalizer_path = self.verbalizer_fst(lang, &config)?;
+        let tagger = FstTextNormalizer::from_file(self.cache.fst_dir.join(tagger_path))?;
+        let verbalizer = FstTextNormalizer::from_file(self.cache.fst_dir.join(verbalizer_path))?;
+
+        let output = self
+            .cache
+            .fst_dir
+            .join(Self::precomposed_path(lang, &config)?);
+        tagger.compose_with(&verbalizer)?.write(&output)?;
+        Ok(output)
+    }
+
+    /// Run only the contractions stage
+    ///
+    /// Expands English contractions as `normalize` does with
+    /// `fix_contractions` enabled, whatever the configuration.
+    #[cfg(feature = "contractions")]
+    pub fn fix_contractions(&self, text: &str) -> String {
+        fix_contractions(text)
+    }
+
+    /// Run only the tagger stage
+    ///
+    /// Returns the tagged token string (e.g. `date { year: "2024" }`) for the
+    /// configured language and operator. With `Language::Auto` the language is
+    /// detected from `text`. No preprocessing is applied.
+    pub fn tag(&mut self, text: &str) -> Result<String> {
+        let config = self.config.clone();
+        let lang = match config.lang {
+            Language::Auto => Self::detect_language(text, &config),
+            lang => lang,
+        };
+        let lang = Self::pipeline_lang(lang, config.operator);
+        self.tag_with(text, lang, &config)
+    }
+
+    /// Export the tagger's best path for `text` as a Graphviz DOT graph
+    ///
+    /// The text is preprocessed as in `normalize`. See
+    /// `FstTextNormalizer::path_dot` for `neighborhood`. Returns `None` if
+    /// the tagger does not accept the text.
+    pub fn tagger_dot(&mut self, text: &str, neighborhood: usize) -> Result<Option<String>> {
+        let config = self.config.clone();
+        let (text, lang) = self.prepare(text, &config)?;
+        let lang = Self::pipeline_lang(lang, config.operator);
+        let path = self.tagger_fst(lang, &config)?;
+        let fst = self.cache.get_or_load(path)?;
+        fst.path_dot(&text, neighborhood)
+    }
+
+    /// Run only the reorder stage on a tagged token string
+    ///
+    /// With `Language::Auto` the language is detected from the token values.
+    pub fn reorder(&self, tagged: &str) -> Result<String> {
+        let lang = self.tagged_language(tagged);
+        self.reorder_with(tagged, lang, &self.config)
+    }
+
+    /// Token parser with the field orders this normalizer uses for `lang`
+    ///
+    /// Includes the English variant, `token_orders` and strictness of the
+    /// configuration. Keep it to reorder many tagged strings without the
+    /// per-call setup of `reorder`.
+    pub fn token_parser_for(&self, lang: Language) -> TokenParser {
+        Self::token_parser(lang, &self.config)
+    }
+
+    /// Run only the verbalizer stage on a (reordered) token string
+    ///
+    /// With `Language::Auto` the language is detected from the token values.
+    /// No postprocessing is applied.
+    pub fn verbalize(&mut self, tagged: &str) -> Result<String> {
+        let config = self.config.clone();
+        let lang = self.tagged_language(tagged);
+        self.verbalize_with(tagged, lang, &config)
+    }
+
+    /// Language of a tagged token string, for the stage-level API
+    fn tagged_language(&self, tagged: &str) -> Language {
+        let lang = match self.config.lang {
+            Language::Auto => {
+                let parser = TokenParser::new(Language::Auto, self.config.operator);
+                let values: String = parser
+                    .parse(tagged)
+                    .unwrap_or_default()
+                    .iter()
+                    .flat_map(|token| token.members.values())
+                    .map(String::as_str)
+                    .collect();
+                Self::detect_language(&values, &self.config)
+            }
+            lang => lang,
+        };
+        Self::pipeline_lang(lang, self.config.operator)
+    }
+
+    /// Fix contractions, preprocess and detect the language of the input
+    fn prepare(&mut self, text: &str, config: &NormalizerConfig) -> Result<(String, Language)> {
+        let mut text = text.to_string();
+
+        // 1. Fix English contractions
+        #[cfg(feature = "contractions")]
+        if config.fix_contractions && text.contains('\'') {
+            text = fix_contractions(&text);
+        }
+
+        // 2. Preprocessing
+        text = self.preprocess(&text, config)?;
+
+        // 3. Detect language
+        let lang = if config.lang == Language::Auto {
+            Self::detect_language(&text, config)
+        } else {
+            config.lang
+        };
+        if !lang.is_enabled() {
+            return Err(WeTextError::InvalidLanguage(format!(
+                "{:?} support is not compiled in",
+                lang
+            )));
+        }
+
+        // 4. Rewrite number glyphs, ambiguous dates and mixed numerals, and spell out numbers
+        // in English compounds
+        if config.fold_number_glyphs && config.operator == Operator::Tn {
+            text = glyphs::fold_number_glyphs(&text, lang);
+        }
+        if config.operator == Operator::Tn {
+            text = dates::resolve_slash_dates(&text, config.date_order);
+        }
+        if config.merge_mixed_numerals && lang == Language::Zh {
+            text = mixed::merge_mixed_numerals(&text);
+        }
+        #[cfg(feature = "lang-en")]
+        if config.en_compounds && lang == Language::En && config.operator == Operator::Tn {
+            text = english::expand_compounds(&text);
+        }
+
+        // 5. Japanese era years
+        #[cfg(feature = "lang-ja")]
+        if lang == Language::Ja && config.operator == Operator::Tn {
+            text = japanese::convert_era(&text, config.era_style);
+        }
+
+        Ok((text, lang))
+    }
+
+    /// Normalize a stream line by line
+    ///
+    /// Lines are read, normalized and written one at a time. Each output line
+    /// is terminated with `\n`. With `deduplicate_lines`, the outputs of up
+    /// to `DEDUP_CAPACITY` distinct lines are remembered so that repeated
+    /// lines are normalized only once; memory use is otherwise independent
+    /// of the input size.
+    ///
+    /// # Returns
+    /// Number of lines processed
+    pub fn normalize_lines<R: BufRead, W: Write>(
+        &mut self,
+        reader: R,
+        mut writer: W,
+    ) -> Result<usize> {
+        let mut seen: HashMap<String, String> = HashMap::new();
+        let mut count = 0;
+        for line in reader.lines() {
+            let line = line?;
+            let normalized = match seen.get(&line) {
+                Some(normalized) => normalized.clone(),
+                None => {
+                    let normalized = self.normalize(&line)?;
+                    if self.config.deduplicate_lines && seen.len() < DEDUP_CAPACITY {
+                        seen.insert(line, normalized.clone());
+                    }
+                    normalized
+                }
+            };
+            writeln!(writer, "{}", normalized)?;
+            count += 1;
+        }
+        writer.flush()?;
+        Ok(count)
+    }
+
+    /// A normalizer with this one's directory, configuration and hooks, and
+    /// an empty FST cache
+    fn worker(&self) -> Normalizer {
+        let mut normalizer = Normalizer::new(&self.cache.fst_dir, self.config.clone());
+        normalizer.predicate = self.predicate.clone();
+        normalizer.punctuation = self.punctuation.clone();
+        normalizer.segmenter = self.segmenter.clone();
+        normalizer
+    }
+
+    /// Normalize a stream line by line using several worker threads
+    ///
+    /// Each worker owns its own `Normalizer` (and loads its own FSTs) with
+    /// this normalizer's directory, configuration, predicate, punctuation
+    /// restorer and segmenter. At most
+    /// `workers * LINES_PER_WORKER` lines are in flight at a time, and output
+    /// lines are written in input order. With `deduplicate_lines`, a line
+    /// seen before is not sent to a worker; the output of its first
+    /// occurrence is written again.
+    ///
+    /// # Returns
+    /// Number of lines processed
+    pub fn normalize_lines_parallel<R: BufRead, W: Write>(
+        &self,
+        reader: R,
+        mut writer: W,
+        workers: usize,
+    ) -> Result<usize> {
+        let workers = workers.max(1);
+        let capacity = workers * LINES_PER_WORKER;
+
+        thread::scope(|scope| {
+            let (job_tx, job_rx) = mpsc::sync_channel::<(usize, String)>(capacity);
+            let (result_tx, result_rx) = mpsc::channel::<(usize, Result<String>)>();
+            let job_rx = Arc::new(Mutex::new(job_rx));
+
+            for _ in 0..workers {
+                let job_rx = Arc::clone(&job_rx);
+                let result_tx = result_tx.clone();
+                let mut normalizer = self.worker();
+                scope.spawn(move || loop {
+                    let job = job_rx.lock().map(|rx| rx.recv());
+                    let Ok(Ok((index, line))) = job else {
+                        break;
+                    };
+                    if result_tx
+                        .send((index, normalizer.normalize(&line)))
+                        .is_err()
+                    {
+                        break;
+                    }
+                });
+            }
+            drop(result_tx);
+
+            // Index of the first occurrence of each remembered line, the
+            // outputs of those first occurrences once written, and the
+            // first occurrence each unwritten repeat refers to
+            let mut seen: HashMap<String, usize> = HashMap::new();
+            let mut outputs: HashMap<usize, Option<String>> = HashMap::new();
+            let mut repeats: HashMap<usize, usize> = HashMap::new();
+
+            let mut pending = BTreeMap::new();
+            let mut next_write = 0;
+            let mut write_ready = |pending: &mut BTreeMap<usize, Result<String>>,
+                                   outputs: &mut HashMap<usize, Option<String>>,
+                                   repeats: &mut HashMap<usize, usize>,
+                                   next_write: &mut usize|
+             -> Result<()> {
+                loop {
+                    if let Some(first) = repeats.remove(next_write) {
+                        // The first occurrence comes earlier, so it was already written
+                        let normalized = outputs[&first].as_deref().unwrap_or_default();
+                        writeln!(writer, "{}", normalized)?;
+                    } else if let Some(result) = pending.remove(next_write) {
+                        let normalized = result?;
+                        writeln!(writer, "{}", normalized)?;
+                        if let Some(slot) = outputs.get_mut(next_write) {
+                            *slot = Some(normalized);
+                        }
+                    } else {
+                        break;
+                    }
+                    *next_write += 1;
+                }
+                Ok(())
+            };
+
+            let mut count = 0;
+            for line in reader.lines() {
+                let line = line?;
+                while count - next_write >= capacity {
+                    let (index, result) = result_rx.recv().map_err(worker_error)?;
+                    pending.insert(index, result);
+                    write_ready(&mut pending, &mut outputs, &mut repeats, &mut next_write)?;
+                }
+                if let Some(&first) = seen.get(&line) {
+                    repeats.insert(count, first);
                 } else {
-                    "zh/itn/tagger.fst"
+                    if self.config.deduplicate_lines && seen.len() < DEDUP_CAPACITY {
+                        seen.insert(line.clone(), count);
+                        outputs.insert(count, None);
+                    }
+                    job_tx.send((count, line)).map_err(worker_error)?;
                 }
+                count += 1;
+                write_ready(&mut pending, &mut outputs, &mut repeats, &mut next_write)?;
+            }
+            drop(job_tx);
+
+            while next_write < count {
+                let (index, result) = result_rx.recv().map_err(worker_error)?;
+                pending.insert(index, result);
+                write_ready(&mut pending, &mut outputs, &mut repeats, &mut next_write)?;
+            }
+            writer.flush()?;
+            Ok(count)
+        })
+    }
+
+    /// Normalize text and return a structured report alongside the output
+    ///
+    /// When `tag_oov` is enabled, the report lists the OOV spans marked in the
+    /// output, so callers don't have to parse the markers themselves.
+    pub fn normalize_with_report(&mut self, text: &str) -> Result<NormalizeReport> {
+        let config = self.config.clone();
+        let confidence = if config.report_confidence {
+            self.tagger_confidence(text, &config)?
+        } else {
+            None
+        };
+        let text = self.normalize_with_config(text, &config)?;
+        let oov = if config.tag_oov {
+            report::find_oov_spans(&text)
+        } else {
+            Vec::new()
+        };
+        Ok(NormalizeReport {
+            text,
+            oov,
+            confidence,
+            unmatched: self.unmatched.clone(),
+        })
+    }
+
+    /// Find spans of the output that have other plausible readings
+    ///
+    /// Intended for ITN, where e.g. "十一二个" can be read as "11-12个" or
+    /// left as "十一、二个". The `rescore_nbest` best tagger paths within
+    /// `max_margin` of the best one are verbalized, and the spans where their
+    /// outputs differ from the chosen output are returned with the other
+    /// readings, so post-editors can review them. Offsets refer to the output
+    /// of the FST stages, before postprocessing.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let ambiguities = normalizer.find_ambiguities("十一二个", 5.0).unwrap();
+    /// // [("11-12", ["十一、二"])]
+    /// ```
+    pub fn find_ambiguities(&mut self, text: &str, max_margin: f32) -> Result<Vec<Ambiguity>> {
+        let config = self.config.clone();
+        let (text, lang) = self.prepare(text, &config)?;
+        if !self.should_normalize(&text, &config) {
+            return Ok(Vec::new());
+        }
+
+        let lang = Self::pipeline_lang(lang, config.operator);
+        let path = self.tagger_fst(lang, &config)?;
+        let fst = self.cache.get_or_load(path)?;
+        let taggings = fst.normalize_nbest(&text, config.rescore_nbest)?;
+        let Some(&(_, best)) = taggings.first() else {
+            return Ok(Vec::new());
+        };
+
+        let mut readings: Vec<String> = Vec::new();
+        for (tagged, weight) in taggings {
+            if weight - best > max_margin {
+                break;
             }
-            (Language::Ja, Operator::Tn) => "ja/tn/tagger.fst",
+            let reordered = self.reorder_with(tagged.trim(), lang, &config)?;
+            let spoken = self.verbalize_with(&reordered, lang, &config)?;
+            let spoken = Self::english_pass(spoken, lang, &config);
+            if !readings.contains(&spoken) {
+                readings.push(spoken);
+            }
+        }
+        Ok(report::find_ambiguities(&readings))
+    }
+
+    /// Score the tagger's path for the prepared text
+    fn tagger_confidence(
+        &mut self,
+        text: &str,
+        config: &NormalizerConfig,
+    ) -> Result<Option<Confidence>> {
+        let (text, lang) = self.prepare(text, config)?;
+        if !self.should_normalize(&text, config) {
+            return Ok(None);
+        }
+        let lang = Self::pipeline_lang(lang, config.operator);
+        let path = self.tagger_fst(lang, config)?;
+        let fst = self.cache.get_or_load(path)?;
+        Ok(fst.normalize_scored(&text, config.rescore_nbest)?.1)
+    }
+
+    /// Run the FST pipeline (tagger, reorder, verbalizer) if normalization is needed
+    fn run_fst(&mut self, text: &str, lang: Language, config: &NormalizerConfig) -> Result<String> {
+        if !self.should_normalize(text, config) {
+            return Ok(text.to_string());
+        }
+
+        // Copy emoji sequences and combining clusters through unchanged
+        let segments = graphemes::split_protected(text);
+        if segments.iter().any(|(protected, _)| *protected) {
+            let mut output = String::new();
+            for (protected, segment) in segments {
+                if protected {
+                    output.push_str(segment);
+                } else {
+                    output.push_str(&self.run_fst_gap(segment, lang, config)?);
+                }
+            }
+            return Ok(output);
+        }
+
+        let lang = Self::pipeline_lang(lang, config.operator);
+        if config.en_case_insensitive && lang == Language::En && casefold::has_uppercase(text) {
+            let output = self.run_pipeline(&text.to_lowercase(), lang, config)?;
+            return Ok(casefold::restore_case(text, &output));
+        }
+        self.run_pipeline(text, lang, config)
+    }
+
+    /// Run the FST stages, from the pre-tag extra FSTs to the English pass
+    fn run_pipeline(
+        &mut self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        let text = self.run_extra_fsts(text, Stage::PreTag, config)?;
+
+        // Pre-composed tagger+verbalizer: one pass instead of three stages
+        if config.use_precomposed
+            && Self::verbalizer_lang(lang, config) == lang
+            && config.token_weights.is_empty()
+            && config.verbalizer_routes.is_empty()
+            && !rewrite::is_active(config)
+        {
+            let path = Self::precomposed_path(lang, config)?;
+            if self.cache.fst_dir.join(&path).exists() {
+                let fst = self.cache.get_or_load(&path)?;
+                let output = fst.try_normalize(&text)?;
+                // Without a match, the stages below normalize the parts that match
+                if output.is_some() || !config.partial_match {
+                    let result = self.on_no_match(output, &text, &path, config)?;
+                    let result = result.trim().to_string();
+                    let result = self.run_extra_fsts(&result, Stage::PostVerbalize, config)?;
+                    return Ok(Self::english_pass(result, lang, config));
+                }
+            }
+        }
+
+        // Tagger: tag entities
+        let text = match self.try_tag(&text, lang, config)? {
+            None if config.partial_match => {
+                let result = self.run_partial(&text, lang, config)?;
+                let result = self.run_extra_fsts(&result, Stage::PostVerbalize, config)?;
+                return Ok(Self::english_pass(result, lang, config));
+            }
+            output => {
+                let path = self.tagger_fst(lang, config)?;
+                self.on_no_match(output, &text, path, config)?
+                    .trim()
+                    .to_string()
+            }
+        };
+
+        // Reorder: reorder token fields
+        let text = self.reorder_with(&text, lang, config)?;
+
+        // Verbalizer: convert to spoken form
+        let result = self.verbalize_with(&text, lang, config)?;
+        let result = self.run_extra_fsts(&result, Stage::PostVerbalize, config)?;
+        Ok(Self::english_pass(result, lang, config))
+    }
+
+    /// Run the FST stages on each sentence of `text`, and on each span of a
+    /// sentence the tagger accepts no path for
+    ///
+    /// Spans the tagger accepts no path for on their own are handled by
+    /// `no_match` and recorded in `unmatched`.
+    fn run_partial(
+        &mut self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        let sentences = stream::split_sentences(text);
+        let mut output = String::new();
+        for sentence in &sentences {
+            if sentences.len() > 1 {
+                if let Some(result) = self.run_piece(sentence, lang, config)? {
+                    output.push_str(&result);
+                    continue;
+                }
+            }
+            let spans = stream::split_spans(sentence);
+            for span in &spans {
+                if spans.len() > 1 {
+                    if let Some(result) = self.run_piece(span, lang, config)? {
+                        output.push_str(&result);
+                        continue;
+                    }
+                }
+                let path = self.tagger_fst(lang, config)?;
+                let result = self.on_no_match(None, span.trim(), path, config)?;
+                output.push_str(&Self::rewrap(span, &result));
+                self.unmatched.push(span.trim().to_string());
+            }
+        }
+        Ok(output)
+    }
+
+    /// Tag, reorder and verbalize one piece of text, `None` if the tagger
+    /// accepts no path for it
+    fn run_piece(
+        &mut self,
+        piece: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<Option<String>> {
+        let body = piece.trim();
+        if body.is_empty() {
+            return Ok(Some(piece.to_string()));
+        }
+        let Some(tagged) = self.try_tag(body, lang, config)? else {
+            return Ok(None);
+        };
+        let text = self.reorder_with(tagged.trim(), lang, config)?;
+        let result = self.verbalize_with(&text, lang, config)?;
+        Ok(Some(Self::rewrap(piece, &result)))
+    }
+
+    /// `output` surrounded by the leading and trailing whitespace of `piece`
+    fn rewrap(piece: &str, output: &str) -> String {
+        let leading = &piece[..piece.len() - piece.trim_start().len()];
+        let trailing = &piece[piece.trim_end().len()..];
+        format!("{}{}{}", leading, output, trailing)
+    }
+
+    /// Apply the extra FSTs registered for a stage, in registration order
+    fn run_extra_fsts(
+        &mut self,
+        text: &str,
+        stage: Stage,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        let mut result = text.to_string();
+        for (_, path) in config.extra_fsts.iter().filter(|(s, _)| *s == stage) {
+            let fst = self.cache.get_or_load(path)?;
+            result = fst.normalize(&result)?.trim().to_string();
+        }
+        Ok(result)
+    }
+
+    /// Unit agreement and variant wording for English TN output
+    fn english_pass(text: String, lang: Language, config: &NormalizerConfig) -> String {
+        let lang = Self::verbalizer_lang(lang, config);
+        #[cfg(feature = "lang-en")]
+        if lang == Language::En && config.operator == Operator::Tn {
+            return english::apply_variant(&english::fix_unit_agreement(&text), config.en_variant);
+        }
+        #[cfg(not(feature = "lang-en"))]
+        let _ = (lang, config);
+        text
+    }
+
+    /// Language whose verbalizer reads text tagged in `lang`
+    fn verbalizer_lang(lang: Language, config: &NormalizerConfig) -> Language {
+        match config.verbalizer_lang {
+            Some(verbalizer) if verbalizer != Language::Auto => {
+                Self::pipeline_lang(verbalizer, config.operator)
+            }
+            _ => lang,
+        }
+    }
+
+    /// Language whose FSTs are used for the given operator
+    fn pipeline_lang(lang: Language, operator: Operator) -> Language {
+        // English ITN is not supported in Python wetext (raises NotImplementedError).
+        // Fallback to Chinese ITN as a workaround, matching Python behavior.
+        if lang == Language::En && operator == Operator::Itn {
+            Language::Zh
+        } else {
+            lang
+        }
+    }
+
+    /// Normalize prepared text, keeping whole the segmenter's words an entity would split
+    fn run_segmented(
+        &mut self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        let words = match &self.segmenter {
+            Some(segmenter) if self.should_normalize(text, config) => segmenter.segment(text),
+            _ => Vec::new(),
+        };
+        let words: Vec<Range<usize>> = words
+            .into_iter()
+            .filter(|word| word.start < word.end && text.get(word.clone()).is_some())
+            .collect();
+        let split = if words.is_empty() {
+            Vec::new()
+        } else {
+            let pipeline_lang = Self::pipeline_lang(lang, config.operator);
+            let entities: Vec<Range<usize>> = self
+                .locate_entities(text, pipeline_lang, config)?
+                .into_iter()
+                .map(|(_, range)| range)
+                .collect();
+            segmenter::split_words(&words, &entities)
+        };
+        if split.is_empty() {
+            return self.run_stages(text, lang, config);
+        }
+
+        // Words are copied as written; the gaps between them are normalized
+        let mut output = String::new();
+        let mut last = 0;
+        for word in split
+            .into_iter()
+            .chain(std::iter::once(text.len()..text.len()))
+        {
+            let gap = &text[last..word.start];
+            let trimmed = gap.trim();
+            if trimmed.is_empty() {
+                output.push_str(gap);
+            } else {
+                output.push_str(&gap[..gap.len() - gap.trim_start().len()]);
+                output.push_str(&self.run_stages(trimmed, lang, config)?);
+                output.push_str(&gap[gap.trim_end().len()..]);
+            }
+            output.push_str(&text[word.clone()]);
+            last = word.end;
+        }
+        Ok(output)
+    }
+
+    /// Tag, reorder and verbalize, routing rule-handled spans around the FST
+    fn run_stages(
+        &mut self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        if hybrid::is_active(config) {
+            self.run_hybrid(text, lang, config)
+        } else {
+            self.run_fst(text, lang, config)
+        }
+    }
+
+    /// Verbalize spans matched by built-in rules and run the FST on the gaps
+    ///
+    /// Whitespace around each gap is preserved, since the FST stages trim their output.
+    fn run_hybrid(
+        &mut self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        let spans = hybrid::find_spans(text, lang, config);
+        let mut output = String::new();
+        let mut last = 0;
+
+        for span in spans.iter() {
+            output.push_str(&self.run_fst_gap(&text[last..span.start], lang, config)?);
+            output.push_str(&span.spoken);
+            last = span.end;
+        }
+        output.push_str(&self.run_fst_gap(&text[last..], lang, config)?);
+
+        Ok(output)
+    }
+
+    fn run_fst_gap(
+        &mut self,
+        gap: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        let trimmed = gap.trim();
+        if trimmed.is_empty() {
+            return Ok(gap.to_string());
+        }
+        let leading = &gap[..gap.len() - gap.trim_start().len()];
+        let trailing = &gap[gap.trim_end().len()..];
+        let normalized = self.run_fst(trimmed, lang, config)?;
+        Ok(format!("{}{}{}", leading, normalized, trailing))
+    }
+
+    /// Detect text language
+    ///
+    /// **Note:** This implementation extends the original Python version with Japanese detection.
+    /// Python wetext only detects Chinese vs English. This Rust version adds Japanese support
+    /// by detecting Hiragana/Katakana characters.
+    ///
+    /// Detection priority:
+    /// 1. Japanese (Hiragana/Katakana) - Rust extension, not in Python version
+    /// 2. Chinese (CJK Unified Ideographs)
+    /// 3. Numeric-only text (digits, punctuation, symbols) - treated as Chinese
+    /// 4. Default to English
+    ///
+    /// Kanji-only text and text without letters or ideographs can't be
+    /// decided from the script; `language_hint` picks their language, after
+    /// the `kanji_disambiguation` strategy for kanji-only text.
+    pub(crate) fn detect_language(text: &str, config: &NormalizerConfig) -> Language {
+        let hint = config.language_hint;
+        let mut has_cjk = false;
+        let mut has_alpha = false;
+
+        for ch in text.chars() {
+            // [Rust Extension] Japanese detection via Hiragana/Katakana
+            // Japanese Hiragana: U+3040 - U+309F
+            // Japanese Katakana: U+30A0 - U+30FF
+            // Note: Python wetext does NOT have this detection - it would return "zh" for Japanese text
+            if ('\u{3040}'..='\u{309f}').contains(&ch) || ('\u{30a0}'..='\u{30ff}').contains(&ch) {
+                return Language::Ja;
+            }
+
+            // CJK Unified Ideographs: U+4E00 - U+9FFF
+            // Note: These are shared between Chinese and Japanese
+            // If we find hiragana/katakana, it's Japanese; otherwise treat as Chinese
+            if ('\u{4e00}'..='\u{9fff}').contains(&ch) {
+                has_cjk = true;
+            }
+
+            // Track if there are any ASCII alphabetic characters
+            if ch.is_ascii_alphabetic() {
+                has_alpha = true;
+            }
+        }
+
+        // If contains CJK but no Japanese-specific characters, treat as Chinese
+        // unless Japanese is preferred
+        if has_cjk {
+            if config.kanji_disambiguation == KanjiDisambiguation::Dictionary {
+                if let Some(lang) = kanji::guess_language(text) {
+                    return lang;
+                }
+            }
+            return match hint {
+                LanguageHint::PreferJa => Language::Ja,
+                _ => Language::Zh,
+            };
+        }
+
+        // Numeric-only text (no alphabetic characters) treated as Chinese
+        // This covers cases like "123", "3/4", "1.5", "2024年" (when year char is not present)
+        if !text.is_empty() && !has_alpha {
+            return match hint {
+                LanguageHint::PreferJa => Language::Ja,
+                LanguageHint::PreferEn => Language::En,
+                _ => Language::Zh,
+            };
+        }
+
+        Language::En
+    }
+
+    /// Check if normalization is needed
+    fn should_normalize(&self, text: &str, config: &NormalizerConfig) -> bool {
+        if let Some(predicate) = &self.predicate {
+            return predicate(text, config.operator);
+        }
+        if config.operator == Operator::Tn {
+            // TN: needs normalization if contains digits
+            if text
+                .chars()
+                .any(|c| Self::is_tn_trigger(c, config.tn_trigger))
+            {
+                return true;
+            }
+            // Or if need to remove erhua
+            if config.remove_erhua && (text.contains('儿') || text.contains('兒')) {
+                return true;
+            }
+            false
+        } else {
+            // ITN: non-empty text needs processing
+            !text.is_empty()
+        }
+    }
+
+    /// Whether a character makes TN run the FST pipeline
+    fn is_tn_trigger(c: char, trigger: TnTrigger) -> bool {
+        match trigger {
+            TnTrigger::AsciiDigits => c.is_ascii_digit(),
+            TnTrigger::Numeric => {
+                c.is_numeric()
+                    || matches!(
+                        c,
+                        '$' | '¢'
+                            | '£'
+                            | '¥'
+                            | '€'
+                            | '₩'
+                            | '₹'
+                            | '₽'
+                            | '＄'
+                            | '￠'
+                            | '￡'
+                            | '￥'
+                            | '%'
+                            | '％'
+                            | '‰'
+                    )
+            }
+        }
+    }
+
+    /// Preprocessing step
+    fn preprocess(&mut self, text: &str, config: &NormalizerConfig) -> Result<String> {
+        let mut result = text.trim().to_string();
+
+        if config.traditional_to_simple {
+            let fst = self.cache.get_or_load("traditional_to_simple.fst")?;
+            result = fst.normalize(&result)?;
+        }
+
+        // The ja FSTs only match full-width katakana
+        #[cfg(feature = "lang-ja")]
+        if config.katakana_to_full_width {
+            result = japanese::katakana_to_full_width(&result);
+        }
+
+        Ok(result)
+    }
+
+    /// Postprocessing step
+    fn postprocess(&mut self, text: &str, config: &NormalizerConfig) -> Result<String> {
+        let mut result = text.to_string();
+
+        if config.full_to_half {
+            let fst = self.cache.get_or_load("full_to_half.fst")?;
+            result = fst.normalize(&result)?;
+        }
+
+        if config.remove_interjections {
+            let fst = self.cache.get_or_load("remove_interjections.fst")?;
+            result = fst.normalize(&result)?;
+        }
+
+        if config.remove_puncts {
+            let fst = self.cache.get_or_load("remove_puncts.fst")?;
+            result = Self::remove_puncts_keeping(fst, &result, &config.keep_puncts)?;
+        }
+
+        if config.tag_oov {
+            let fst = self.cache.get_or_load("tag_oov.fst")?;
+            result = fst.normalize(&result)?;
+        }
+
+        if let Some(hints) = config
+            .pause_hints
+            .as_ref()
+            .filter(|_| config.operator == Operator::Tn)
+        {
+            result = prosody::insert_pause_hints(result.trim(), hints);
+        }
+
+        Ok(result.trim().to_string())
+    }
+
+    /// Remove punctuation, retaining the marks in `keep`
+    ///
+    /// The text is split at kept marks and the FST is applied to each piece
+    /// separately, so kept marks never reach the FST.
+    fn remove_puncts_keeping(fst: &FstTextNormalizer, text: &str, keep: &[char]) -> Result<String> {
+        if keep.is_empty() {
+            return fst.normalize(text);
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for (index, mark) in text.match_indices(keep) {
+            result.push_str(&fst.normalize(&text[last..index])?);
+            result.push_str(mark);
+            last = index + mark.len();
+        }
+        result.push_str(&fst.normalize(&text[last..])?);
+        Ok(result)
+    }
+
+    /// Tagger FST path for a language and configuration
+    fn tagger_path(lang: Language, config: &NormalizerConfig) -> Result<&'static str> {
+        match (lang, config.operator) {
+            #[cfg(feature = "lang-en")]
+            (Language::En, Operator::Tn) => Ok("en/tn/tagger.fst"),
+            #[cfg(feature = "lang-zh")]
+            (Language::Zh, Operator::Tn) => Ok("zh/tn/tagger.fst"),
+            #[cfg(feature = "lang-zh")]
+            (Language::Zh, Operator::Itn) => {
+                if config.enable_0_to_9 {
+                    Ok("zh/itn/tagger_enable_0_to_9.fst")
+                } else {
+                    Ok("zh/itn/tagger.fst")
+                }
+            }
+            #[cfg(feature = "lang-ja")]
+            (Language::Ja, Operator::Tn) => Ok("ja/tn/tagger.fst"),
+            #[cfg(feature = "lang-ja")]
             (Language::Ja, Operator::Itn) => {
                 if config.enable_0_to_9 {
-                    "ja/itn/tagger_enable_0_to_9.fst"
+                    Ok("ja/itn/tagger_enable_0_to_9.fst")
                 } else {
-                    "ja/itn/tagger.fst"
+                    Ok("ja/itn/tagger.fst")
                 }
             }
-            _ => return Err(WeTextError::InvalidLanguage(format!("{:?}", lang))),
+            _ => Err(WeTextError::InvalidLanguage(format!("{:?}", lang))),
+        }
+    }
+
+    /// Verbalizer FST path for a language and configuration
+    fn verbalizer_path(lang: Language, config: &NormalizerConfig) -> Result<&'static str> {
+        match (lang, config.operator) {
+            #[cfg(feature = "lang-en")]
+            (Language::En, Operator::Tn) => Ok("en/tn/verbalizer.fst"),
+            #[cfg(feature = "lang-zh")]
+            (Language::Zh, Operator::Tn) => {
+                if config.remove_erhua {
+                    Ok("zh/tn/verbalizer_remove_erhua.fst")
+                } else {
+                    Ok("zh/tn/verbalizer.fst")
+                }
+            }
+            #[cfg(feature = "lang-zh")]
+            (Language::Zh, Operator::Itn) => Ok("zh/itn/verbalizer.fst"),
+            #[cfg(feature = "lang-ja")]
+            (Language::Ja, Operator::Tn) => Ok("ja/tn/verbalizer.fst"),
+            #[cfg(feature = "lang-ja")]
+            (Language::Ja, Operator::Itn) => Ok("ja/itn/verbalizer.fst"),
+            _ => Err(WeTextError::InvalidLanguage(format!("{:?}", lang))),
+        }
+    }
+
+    /// Closest available FST to use when `path` is missing
+    fn fallback_path(path: &str) -> Option<&'static str> {
+        match path {
+            "zh/itn/tagger_enable_0_to_9.fst" => Some("zh/itn/tagger.fst"),
+            "ja/itn/tagger_enable_0_to_9.fst" => Some("ja/itn/tagger.fst"),
+            "zh/tn/verbalizer_remove_erhua.fst" => Some("zh/tn/verbalizer.fst"),
+            _ => None,
+        }
+    }
+
+    /// Replace a missing FST with its fallback if `fst_fallback` is enabled
+    fn resolve_fst(&mut self, path: &'static str, config: &NormalizerConfig) -> &'static str {
+        if !config.fst_fallback
+            || self.cache.fsts.contains_key(path)
+            || self.cache.fst_dir.join(path).exists()
+        {
+            return path;
+        }
+        let Some(fallback) = Self::fallback_path(path) else {
+            return path;
         };
+        if !self.cache.fst_dir.join(fallback).exists() {
+            return path;
+        }
+        let warning = format!("{} not found, using {}", path, fallback);
+        self.cache.warn(warning);
+        fallback
+    }
 
-        let fst = self.cache.get_or_load(fst_path)?;
-        let result = fst.normalize(text)?;
+    /// Tagger FST to load, after fallback
+    fn tagger_fst(&mut self, lang: Language, config: &NormalizerConfig) -> Result<&'static str> {
+        let path = Self::tagger_path(lang, config)?;
+        Ok(self.resolve_fst(path, config))
+    }
+
+    /// Verbalizer FST to load, after fallback
+    fn verbalizer_fst(
+        &mut self,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<&'static str> {
+        let path = Self::verbalizer_path(lang, config)?;
+        Ok(self.resolve_fst(path, config))
+    }
+
+    /// Path of the pre-composed tagger+verbalizer FST for a language and configuration
+    ///
+    /// For example `zh/tn/tagger.fst` and `zh/tn/verbalizer.fst` give
+    /// `zh/tn/precomposed_tagger_verbalizer.fst`.
+    fn precomposed_path(lang: Language, config: &NormalizerConfig) -> Result<String> {
+        let tagger = Path::new(Self::tagger_path(lang, config)?);
+        let verbalizer = Path::new(Self::verbalizer_path(lang, config)?);
+        let stem = |path: &Path| {
+            path.file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        let name = format!("precomposed_{}_{}.fst", stem(tagger), stem(verbalizer));
+        let dir = tagger.parent().unwrap_or_else(|| Path::new(""));
+        Ok(dir.join(name).to_string_lossy().into_owned())
+    }
+
+    /// Tag entities using tagger FST
+    fn tag_with(
+        &mut self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        let output = self.try_tag(text, lang, config)?;
+        let fst_path = self.tagger_fst(lang, config)?;
+        let result = self.on_no_match(output, text, fst_path, config)?;
         Ok(result.trim().to_string())
     }
 
-    /// Reorder token fields
-    fn reorder(&self, text: &str, lang: Language, operator: Operator) -> Result<String> {
-        let parser = TokenParser::new(lang, operator);
-        parser.reorder(text)
+    /// Tag entities using tagger FST, `None` if it accepts no path for `text`
+    fn try_tag(
+        &mut self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<Option<String>> {
+        let fst_path = self.tagger_fst(lang, config)?;
+
+        let fst = self.cache.get_or_load(fst_path)?;
+        if config.token_weights.is_empty() {
+            fst.try_normalize(text)
+        } else {
+            // Rescore the best taggings with the per-token-type penalties
+            let parser = TokenParser::new(lang, config.operator);
+            fst.try_normalize_rescored(text, config.rescore_nbest, |tagged| {
+                parser.parse(tagged).map_or(0.0, |tokens| {
+                    tokens
+                        .iter()
+                        .filter_map(|token| config.token_weights.get(&token.name))
+                        .sum()
+                })
+            })
+        }
+    }
+
+    /// Reorder token fields
+    fn reorder_with(
+        &self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        let parser = Self::token_parser(lang, config);
+        if !rewrite::is_active(config) || !text.contains('{') {
+            return parser.reorder(text);
+        }
+        match parser.parse(text) {
+            Ok(mut tokens) => {
+                rewrite::rewrite_tokens(&mut tokens, lang, config);
+                let output: Vec<String> = tokens.iter().map(|t| parser.serialize(t)).collect();
+                Ok(output.join(" "))
+            }
+            Err(_) => parser.reorder(text),
+        }
+    }
+
+    /// Token parser with the built-in orders plus those registered in `config`
+    ///
+    /// Field orders are those the verbalizer expects.
+    fn token_parser(lang: Language, config: &NormalizerConfig) -> TokenParser {
+        let lang = Self::verbalizer_lang(lang, config);
+        config.token_orders.iter().fold(
+            TokenParser::new(lang, config.operator)
+                .with_en_variant(config.en_variant)
+                .with_strict(config.error_policy == ErrorPolicy::Strict),
+            |parser, (name, order)| {
+                let order: Vec<&str> = order.iter().map(String::as_str).collect();
+                parser.with_order(name, &order)
+            },
+        )
     }
 
     /// Verbalize using verbalizer FST
-    fn verbalize(
+    fn verbalize_with(
         &mut self,
         text: &str,
         lang: Language,
         config: &NormalizerConfig,
     ) -> Result<String> {
-        let fst_path = match (lang, config.operator) {
-            (Language::En, Operator::Tn) => "en/tn/verbalizer.fst",
-            (Language::Zh, Operator::Tn) => {
-                if config.remove_erhua {
-                    "zh/tn/verbalizer_remove_erhua.fst"
-                } else {
-                    "zh/tn/verbalizer.fst"
-                }
-            }
-            (Language::Zh, Operator::Itn) => "zh/itn/verbalizer.fst",
-            (Language::Ja, Operator::Tn) => "ja/tn/verbalizer.fst",
-            (Language::Ja, Operator::Itn) => "ja/itn/verbalizer.fst",
-            _ => return Err(WeTextError::InvalidLanguage(format!("{:?}", lang))),
-        };
+        let lang = Self::verbalizer_lang(lang, config);
+        let fst_path = self.verbalizer_fst(lang, config)?;
+
+        if !config.verbalizer_routes.is_empty() {
+            return self.verbalize_routed(text, lang, config, fst_path);
+        }
 
         let fst = self.cache.get_or_load(fst_path)?;
-        let result = fst.normalize(text)?;
+        let output = fst.try_normalize(text)?;
+        let result = self.on_no_match(output, text, fst_path, config)?;
         Ok(result.trim().to_string())
     }
+
+    /// Verbalize with per-token-type verbalizer FSTs
+    ///
+    /// The token string is split into runs of consecutive tokens that share a
+    /// verbalizer; each run is verbalized separately and the pieces are joined.
+    fn verbalize_routed(
+        &mut self,
+        text: &str,
+        lang: Language,
+        config: &NormalizerConfig,
+        default_path: &str,
+    ) -> Result<String> {
+        let parser = Self::token_parser(lang, config);
+        let tokens = match parser.parse(text) {
+            Ok(tokens) if !tokens.is_empty() => tokens,
+            _ => {
+                let fst = self.cache.get_or_load(default_path)?;
+                let output = fst.try_normalize(text)?;
+                let result = self.on_no_match(output, text, default_path, config)?;
+                return Ok(result.trim().to_string());
+            }
+        };
+
+        let mut runs: Vec<(&str, Vec<String>)> = Vec::new();
+        for token in &tokens {
+            let path = config
+                .verbalizer_routes
+                .get(&token.name)
+                .map_or(default_path, String::as_str);
+            match runs.last_mut() {
+                Some((run_path, run)) if *run_path == path => run.push(parser.serialize(token)),
+                _ => runs.push((path, vec![parser.serialize(token)])),
+            }
+        }
+
+        let separator = if lang == Language::En { " " } else { "" };
+        let mut pieces = Vec::with_capacity(runs.len());
+        for (path, run) in runs {
+            let run = run.join(" ");
+            let fst = self.cache.get_or_load(path)?;
+            let output = fst.try_normalize(&run)?;
+            let result = self.on_no_match(output, &run, path, config)?;
+            pieces.push(result.trim().to_string());
+        }
+        Ok(pieces.join(separator))
+    }
+
+    /// Apply `config.no_match` to the output of the FST at `path` for `text`
+    fn on_no_match(
+        &mut self,
+        output: Option<String>,
+        text: &str,
+        path: &str,
+        config: &NormalizerConfig,
+    ) -> Result<String> {
+        if let Some(output) = output {
+            return Ok(output);
+        }
+        match config.no_match {
+            NoMatchPolicy::Passthrough => {}
+            NoMatchPolicy::Error => {
+                return Err(WeTextError::NoMatch(format!(
+                    "{} accepts no path for {:?}",
+                    path, text
+                )));
+            }
+            NoMatchPolicy::PassthroughWithWarning => {
+                self.no_matches += 1;
+                let warning = format!("{} accepted no path; input passed through", path);
+                self.cache.warn(warning);
+            }
+        }
+        Ok(text.to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rustfst::prelude::*;
+
+    /// Write a single-arc FST mapping byte `from` to byte `to`
+    fn write_byte_fst(path: &Path, from: u8, to: u8) {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0).unwrap();
+        fst.set_final(s1, TropicalWeight::one()).unwrap();
+        fst.add_tr(
+            s0,
+            Tr::new(from as Label, to as Label, TropicalWeight::one(), s1),
+        )
+        .unwrap();
+        fst.write(path).unwrap();
+    }
+
+    /// Bump a file's modification time so change detection sees it
+    fn touch_later(path: &Path) {
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        file.set_modified(later).unwrap();
+    }
+
+    #[test]
+    fn test_reload_changed_fst() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stage.fst");
+        write_byte_fst(&path, b'a', b'b');
+
+        let mut cache = FstCache::new(dir.path());
+        assert_eq!(
+            cache
+                .get_or_load("stage.fst")
+                .unwrap()
+                .normalize("a")
+                .unwrap(),
+            "b"
+        );
+        assert!(cache.reload_changed().unwrap().is_empty());
+
+        write_byte_fst(&path, b'a', b'c');
+        touch_later(&path);
+        assert_eq!(
+            cache.reload_changed().unwrap(),
+            vec!["stage.fst".to_string()]
+        );
+        assert_eq!(
+            cache
+                .get_or_load("stage.fst")
+                .unwrap()
+                .normalize("a")
+                .unwrap(),
+            "c"
+        );
+    }
+
+    #[test]
+    fn test_compact_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        write_byte_fst(&dir.path().join("stage.fst"), b'a', b'b');
+
+        let mut cache = FstCache::new(dir.path());
+        cache.compact = true;
+        let fst = cache.get_or_load("stage.fst").unwrap();
+        assert!(fst.is_compact());
+        assert_eq!(fst.normalize("a").unwrap(), "b");
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stage.fst");
+        write_byte_fst(&path, b'a', b'b');
+        let mut cache = FstCache::new(dir.path());
+        cache.get_or_load("stage.fst").unwrap();
+        let snapshot = dir.path().join("cache.snap");
+        cache.save_snapshot(&snapshot).unwrap();
+
+        // The snapshot is used instead of the file, and keeps its mtime
+        let mut restored = FstCache::new(dir.path());
+        restored.compact = true;
+        assert_eq!(
+            restored.load_snapshot(&snapshot).unwrap(),
+            vec!["stage.fst".to_string()]
+        );
+        assert!(restored.reload_changed().unwrap().is_empty());
+        std::fs::remove_file(&path).unwrap();
+        let fst = restored.get_or_load("stage.fst").unwrap();
+        assert!(fst.is_compact());
+        assert_eq!(fst.normalize("a").unwrap(), "b");
+    }
+
+    #[test]
+    fn test_watch_mode_reloads_on_access() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stage.fst");
+        write_byte_fst(&path, b'a', b'b');
+
+        let mut cache = FstCache::new(dir.path());
+        cache.watch = true;
+        assert_eq!(
+            cache
+                .get_or_load("stage.fst")
+                .unwrap()
+                .normalize("a")
+                .unwrap(),
+            "b"
+        );
+
+        write_byte_fst(&path, b'a', b'c');
+        touch_later(&path);
+        assert_eq!(
+            cache
+                .get_or_load("stage.fst")
+                .unwrap()
+                .normalize("a")
+                .unwrap(),
+            "c"
+        );
+    }
+
+    #[test]
+    fn test_watch_mode_keeps_fst_on_failed_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        let tagger = dir.path().join("zh/tn/tagger.fst");
+        crate::Rule::replace("1", r#"cardinal { value: "1" }"#)
+            .to_fst()
+            .unwrap()
+            .write(&tagger)
+            .unwrap();
+        crate::Rule::replace(r#"cardinal { value: "1" }"#, "一")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        normalizer.set_watch(true);
+        assert_eq!(normalizer.normalize("1").unwrap(), "一");
+
+        // A half-written tagger keeps the loaded one in use
+        std::fs::write(&tagger, b"garbage").unwrap();
+        touch_later(&tagger);
+        assert_eq!(normalizer.normalize("1").unwrap(), "一");
+        assert_eq!(normalizer.normalize("1").unwrap(), "一");
+        assert_eq!(normalizer.warnings().len(), 1);
+        assert!(normalizer.warnings()[0].starts_with("failed to reload zh/tn/tagger.fst"));
+
+        // and the reload is retried once the file is complete
+        crate::Rule::replace("2", r#"cardinal { value: "1" }"#)
+            .to_fst()
+            .unwrap()
+            .write(&tagger)
+            .unwrap();
+        touch_later(&tagger);
+        assert_eq!(normalizer.normalize("2").unwrap(), "一");
+    }
+
+    #[test]
+    fn test_remove_puncts_keeping() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stage.fst");
+        // Stand-in for remove_puncts.fst: deletes "!" and nothing else
+        write_byte_fst(&path, b'!', 0);
+        let fst = FstTextNormalizer::from_file(&path).unwrap();
+
+        assert_eq!(
+            Normalizer::remove_puncts_keeping(&fst, "!?!", &['?']).unwrap(),
+            "?"
+        );
+        assert_eq!(
+            Normalizer::remove_puncts_keeping(&fst, "!", &[]).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_normalize_lines() {
+        // Lines without digits need no FST, so no FST directory is required
+        let input = "  hello \nworld\n\n你好\n";
+        let mut normalizer = Normalizer::with_defaults("missing");
+
+        let mut output = Vec::new();
+        let count = normalizer
+            .normalize_lines(input.as_bytes(), &mut output)
+            .unwrap();
+        assert_eq!(count, 4);
+        assert_eq!(String::from_utf8(output).unwrap(), "hello\nworld\n\n你好\n");
+
+        let input: String = (0..500)
+            .map(|i| format!("line{}\n", "a".repeat(i % 7)))
+            .collect();
+        let mut output = Vec::new();
+        let count = normalizer
+            .normalize_lines_parallel(input.as_bytes(), &mut output, 3)
+            .unwrap();
+        assert_eq!(count, 500);
+        assert_eq!(String::from_utf8(output).unwrap(), input);
+
+        // Same output with every line sent to a worker
+        let normalizer = Normalizer::new(
+            "missing",
+            NormalizerConfig::new().with_deduplicate_lines(false),
+        );
+        let mut output = Vec::new();
+        normalizer
+            .normalize_lines_parallel(input.as_bytes(), &mut output, 3)
+            .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), input);
+    }
+
+    #[test]
+    fn test_normalize_lines_parallel_hooks() {
+        // The workers honour the predicate, so digits need no FST either
+        let input: String = (0..50).map(|i| format!("room {}\n", i)).collect();
+        let mut normalizer = Normalizer::new(
+            "missing",
+            NormalizerConfig::new().with_deduplicate_lines(false),
+        );
+        normalizer.set_should_normalize(|_, _| false);
+        let mut output = Vec::new();
+        normalizer
+            .normalize_lines_parallel(input.as_bytes(), &mut output, 3)
+            .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), input);
+    }
+
+    #[test]
+    fn test_verbalizer_routes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace(r#"char { value: "a" }"#, "A")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"money { value: "5" }"#, "five")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("money.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_verbalizer_route("money", "money.fst");
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        let result = normalizer
+            .verbalize(r#"char { value: "a" } money { value: "5" }"#)
+            .unwrap();
+        assert_eq!(result, "Afive");
+    }
+
+    #[test]
+    fn test_verbalizer_lang() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        std::fs::create_dir_all(dir.path().join("ja/tn")).unwrap();
+        crate::Rule::replace("5", r#"cardinal { value: "5" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        for (lang, spoken) in [("zh", "五"), ("ja", "ご")] {
+            crate::Rule::replace(r#"cardinal { value: "5" }"#, spoken)
+                .to_fst()
+                .unwrap()
+                .write(dir.path().join(lang).join("tn/verbalizer.fst"))
+                .unwrap();
+        }
+
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut normalizer = Normalizer::new(dir.path(), config.clone());
+        assert_eq!(normalizer.normalize("5").unwrap(), "五");
+        let mut normalizer = Normalizer::new(dir.path(), config.with_verbalizer_lang(Language::Ja));
+        assert_eq!(normalizer.normalize("5").unwrap(), "ご");
+    }
+
+    #[test]
+    fn test_segmenter() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/itn")).unwrap();
+        let tagged = r#"char { value: "等" } cardinal { value: "1" } char { value: "会" } char { value: "儿" }"#;
+        crate::Rule::union([
+            crate::Rule::replace("等一会儿", tagged),
+            crate::Rule::replace("等", r#"char { value: "等" }"#),
+        ])
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/itn/tagger.fst"))
+        .unwrap();
+        crate::Rule::union([
+            crate::Rule::replace(tagged, "等1会儿"),
+            crate::Rule::replace(r#"char { value: "等" }"#, "等"),
+        ])
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/itn/verbalizer.fst"))
+        .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_operator(Operator::Itn);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        assert_eq!(normalizer.normalize("等一会儿").unwrap(), "等1会儿");
+        normalizer.set_segmenter(|_: &str| vec![0..3, 3..12]);
+        assert_eq!(normalizer.normalize("等一会儿").unwrap(), "等一会儿");
+    }
+
+    #[test]
+    fn test_merge_mixed_numerals() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace("35000元", r#"money { value: "35000" currency: "元" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"money { value: "35000" currency: "元" }"#, "三万五千元")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut normalizer = Normalizer::new(dir.path(), config.clone());
+        // Off by default, so the digit runs reach the tagger unchanged
+        assert_eq!(normalizer.normalize("3万5千元").unwrap(), "3万5千元");
+        let mut normalizer = Normalizer::new(dir.path(), config.with_merge_mixed_numerals(true));
+        assert_eq!(normalizer.normalize("3万5千元").unwrap(), "三万五千元");
+    }
+
+    #[test]
+    fn test_date_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace(
+            "2024-04-03",
+            r#"date { year: "二零二四" month: "四" day: "三" }"#,
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/tn/tagger.fst"))
+        .unwrap();
+        crate::Rule::replace(
+            r#"date { year: "二零二四" month: "四" day: "三" }"#,
+            "二零二四年四月三日",
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/tn/verbalizer.fst"))
+        .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_date_order(crate::DateOrderPreference::Dmy);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        assert_eq!(
+            normalizer.normalize("3/4/2024").unwrap(),
+            "二零二四年四月三日"
+        );
+    }
+
+    #[test]
+    fn test_normalize_segmented() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace("共5", r#"char { value: "共" } money { value: "5" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"money { value: "5" }"#, "五元")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        let segments = normalizer.normalize_segmented("共5").unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                Segment {
+                    text: "共".to_string(),
+                    label: None,
+                },
+                Segment {
+                    text: "五元".to_string(),
+                    label: Some("money".to_string()),
+                },
+            ]
+        );
+
+        let segments = normalizer.normalize_tokens("共5").unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                NormalizedSegment {
+                    text: "共".to_string(),
+                    category: None,
+                    original: "共".to_string(),
+                },
+                NormalizedSegment {
+                    text: "五元".to_string(),
+                    category: Some("money".to_string()),
+                    original: "5".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_document() {
+        // Text without digits passes through TN without loading any FST
+        let mut normalizer = Normalizer::with_defaults("missing");
+        let document = normalizer
+            .normalize_document("你好。 Hello there! こんにちは")
+            .unwrap();
+        let langs: Vec<Language> = document.segments.iter().map(|s| s.lang).collect();
+        assert_eq!(langs, vec![Language::Zh, Language::En, Language::Ja]);
+        assert_eq!(document.segments[1].text, "Hello there!");
+        assert_eq!(document.segments[1].normalized, "Hello there!");
+    }
+
+    #[test]
+    fn test_renormalize_edit() {
+        // Text without digits passes through TN without loading any FST
+        let dir = tempfile::tempdir().unwrap();
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+
+        // Unchanged sentences are copied from the previous output
+        let (input, output) = normalizer
+            .renormalize_edit("你好。再见。", "甲。乙。", 12..15, "会")
+            .unwrap();
+        assert_eq!(input, "你好。再会。");
+        assert_eq!(output, "甲。再会。");
+
+        // Removing a terminator merges sentences
+        let (_, output) = normalizer
+            .renormalize_edit("你好。再见。好", "甲。乙。丙", 15..18, "")
+            .unwrap();
+        assert_eq!(output, "甲。再见好");
+        let (_, output) = normalizer
+            .renormalize_edit("你好。再见", "甲。乙", 6..9, "")
+            .unwrap();
+        assert_eq!(output, "你好再见");
+
+        assert!(normalizer
+            .renormalize_edit("你好", "你好", 1..2, "")
+            .is_err());
+    }
+
+    #[test]
+    fn test_find_ambiguities() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/itn")).unwrap();
+        let range = r#"range { value: "11-12" } char { value: "个" }"#;
+        let plain = r#"char { value: "十一、二个" }"#;
+        crate::Rule::replace("十一二个", range)
+            .weight(1.0)
+            .or(crate::Rule::replace("十一二个", plain).weight(2.0))
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/itn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(range, "11-12个")
+            .or(crate::Rule::replace(plain, "十一、二个"))
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/itn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_operator(Operator::Itn);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        let ambiguities = normalizer.find_ambiguities("十一二个", 5.0).unwrap();
+        assert_eq!(ambiguities.len(), 1);
+        assert_eq!(ambiguities[0].text, "11-12");
+        assert_eq!(ambiguities[0].alternatives, vec!["十一、二"]);
+
+        // Readings beyond the margin are not plausible
+        assert!(normalizer
+            .find_ambiguities("十一二个", 0.5)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_extra_fsts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        std::fs::create_dir_all(dir.path().join("site")).unwrap();
+        crate::Rule::replace("1", r#"cardinal { value: "1" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"cardinal { value: "1" }"#, "一")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+        crate::Rule::replace("7", "1")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("site/pre.fst"))
+            .unwrap();
+        crate::Rule::replace("一", "幺")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("site/post.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_extra_fst(Stage::PreTag, "site/pre.fst")
+            .with_extra_fst(Stage::PostVerbalize, "site/post.fst");
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        assert_eq!(normalizer.normalize("7").unwrap(), "幺");
+        let loaded: Vec<String> = normalizer
+            .loaded_fsts()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(
+            loaded,
+            [
+                "site/post.fst",
+                "site/pre.fst",
+                "zh/tn/tagger.fst",
+                "zh/tn/verbalizer.fst"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/itn")).unwrap();
+        std::fs::create_dir_all(dir.path().join("en/tn")).unwrap();
+        let fst = crate::Rule::replace("a", "b").to_fst().unwrap();
+        for path in [
+            "zh/itn/tagger.fst",
+            "zh/itn/tagger_enable_0_to_9.fst",
+            "zh/itn/verbalizer.fst",
+            "en/tn/tagger.fst",
+            "full_to_half.fst",
+        ] {
+            fst.write(dir.path().join(path)).unwrap();
+        }
+
+        let capabilities = Normalizer::with_defaults(dir.path()).capabilities();
+        assert!(capabilities.supports(Language::Zh, Operator::Itn));
+        assert!(capabilities.supports_option(Language::Zh, Operator::Itn, Some("enable_0_to_9")));
+        // en/tn has no verbalizer
+        assert!(!capabilities.supports(Language::En, Operator::Tn));
+        assert_eq!(capabilities.pipelines.len(), 2);
+        assert_eq!(capabilities.languages(Operator::Itn), [Language::Zh]);
+        assert_eq!(capabilities.processing, ["full_to_half"]);
+    }
+
+    #[test]
+    fn test_fst_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace("1", r#"cardinal { value: "1" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"cardinal { value: "1" }"#, "一")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        // verbalizer_remove_erhua.fst is missing
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_remove_erhua(true);
+        let mut normalizer = Normalizer::new(dir.path(), config.clone());
+        assert!(normalizer.normalize("1").is_err());
+        assert!(normalizer.warnings().is_empty());
+
+        let mut normalizer = Normalizer::new(dir.path(), config.with_fst_fallback(true));
+        assert_eq!(normalizer.normalize("1").unwrap(), "一");
+        assert_eq!(normalizer.normalize("1").unwrap(), "一");
+        assert_eq!(
+            normalizer.warnings(),
+            ["zh/tn/verbalizer_remove_erhua.fst not found, using zh/tn/verbalizer.fst"]
+        );
+    }
+
+    #[test]
+    fn test_no_match_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace("1", r#"cardinal { value: "1" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"cardinal { value: "1" }"#, "一")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut normalizer = Normalizer::new(dir.path(), config.clone());
+        assert_eq!(normalizer.normalize("2").unwrap(), "2");
+        assert!(normalizer.warnings().is_empty());
+
+        let mut normalizer = Normalizer::new(
+            dir.path(),
+            config.clone().with_no_match(NoMatchPolicy::Error),
+        );
+        assert_eq!(normalizer.normalize("1").unwrap(), "一");
+        assert!(matches!(
+            normalizer.normalize("2"),
+            Err(WeTextError::NoMatch(_))
+        ));
+
+        let mut normalizer = Normalizer::new(
+            dir.path(),
+            config.with_no_match(NoMatchPolicy::PassthroughWithWarning),
+        );
+        assert_eq!(normalizer.normalize("2").unwrap(), "2");
+        assert_eq!(normalizer.normalize("2").unwrap(), "2");
+        // The tagger and the verbalizer each miss both times
+        assert_eq!(normalizer.no_match_count(), 4);
+        assert_eq!(
+            normalizer.warnings(),
+            [
+                "zh/tn/tagger.fst accepted no path; input passed through",
+                "zh/tn/verbalizer.fst accepted no path; input passed through",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_match_policy_with_token_weights() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace("1", r#"cardinal { value: "1" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"cardinal { value: "1" }"#, "一")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        // The rescored tagger search reports a miss like the plain one
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_token_weight("cardinal", 1.0)
+            .with_no_match(NoMatchPolicy::Error);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        assert_eq!(normalizer.normalize("1").unwrap(), "一");
+        assert!(matches!(
+            normalizer.normalize("2"),
+            Err(WeTextError::NoMatch(message)) if message.starts_with("zh/tn/tagger.fst")
+        ));
+    }
+
+    #[test]
+    fn test_partial_match() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace("1", r#"cardinal { value: "1" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"cardinal { value: "1" }"#, "一")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut normalizer = Normalizer::new(dir.path(), config.clone());
+        assert_eq!(normalizer.normalize("1 2 1").unwrap(), "1 2 1");
+
+        let config = config.with_partial_match(true);
+        let mut normalizer = Normalizer::new(dir.path(), config.clone());
+        let report = normalizer.normalize_with_report("1 2  1").unwrap();
+        assert_eq!(report.text, "一 2  一");
+        assert_eq!(report.unmatched, ["2"]);
+        assert_eq!(normalizer.normalize("1").unwrap(), "一");
+        assert!(normalizer.unmatched_spans().is_empty());
+
+        let mut normalizer =
+            Normalizer::new(dir.path(), config.with_no_match(NoMatchPolicy::Error));
+        assert!(matches!(
+            normalizer.normalize("1 2"),
+            Err(WeTextError::NoMatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_en_case_insensitive() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("en/tn")).unwrap();
+        crate::Rule::replace("20 dollars", r#"money { integer_part: "20" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("en/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"money { integer_part: "20" }"#, "twenty dollars")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("en/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new().with_lang(Language::En);
+        let mut normalizer = Normalizer::new(dir.path(), config.clone());
+        assert_eq!(normalizer.normalize("20 DOLLARS").unwrap(), "20 DOLLARS");
+
+        let mut normalizer = Normalizer::new(dir.path(), config.with_en_case_insensitive(true));
+        assert_eq!(
+            normalizer.normalize("20 DOLLARS").unwrap(),
+            "twenty DOLLARS"
+        );
+        assert_eq!(
+            normalizer.normalize("20 dollars").unwrap(),
+            "twenty dollars"
+        );
+    }
+
+    #[test]
+    fn test_en_compounds() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("en/tn")).unwrap();
+        crate::Rule::replace("1", r#"cardinal { integer: "1" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("en/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"cardinal { integer: "1" }"#, "one")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("en/tn/verbalizer.fst"))
+            .unwrap();
+
+        // Compounds reach the tagger as written unless enabled
+        let config = NormalizerConfig::new().with_lang(Language::En);
+        let mut normalizer = Normalizer::new(dir.path(), config.clone());
+        assert_eq!(normalizer.normalize("6-year-old").unwrap(), "6-year-old");
+
+        let mut normalizer = Normalizer::new(dir.path(), config.with_en_compounds(true));
+        assert_eq!(normalizer.normalize("6-year-old").unwrap(), "six-year-old");
+        assert_eq!(normalizer.normalize("24/7/2024").unwrap(), "24/7/2024");
+    }
+
+    #[test]
+    fn test_precompose() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        let tagger = dir.path().join("zh/tn/tagger.fst");
+        crate::Rule::replace("1", r#"cardinal { value: "1" }"#)
+            .to_fst()
+            .unwrap()
+            .write(&tagger)
+            .unwrap();
+        crate::Rule::replace(r#"cardinal { value: "1" }"#, "一")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_use_precomposed(true);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        let output = normalizer.precompose().unwrap();
+        assert!(output.ends_with("zh/tn/precomposed_tagger_verbalizer.fst"));
+
+        // The separate tagger is no longer needed
+        std::fs::remove_file(&tagger).unwrap();
+        assert_eq!(normalizer.normalize("1").unwrap(), "一");
+    }
+
+    #[test]
+    fn test_reload_failure_keeps_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stage.fst");
+        write_byte_fst(&path, b'a', b'b');
+
+        let mut cache = FstCache::new(dir.path());
+        cache.get_or_load("stage.fst").unwrap();
+
+        std::fs::write(&path, b"not an fst").unwrap();
+        assert!(cache.reload_all().is_err());
+        assert_eq!(
+            cache
+                .get_or_load("stage.fst")
+                .unwrap()
+                .normalize("a")
+                .unwrap(),
+            "b"
+        );
+    }
+
+    #[test]
+    fn test_normalize_cow() {
+        let mut normalizer = Normalizer::with_defaults("missing");
+        let output = normalizer.normalize_cow("Hello, world!").unwrap();
+        assert!(matches!(output, Cow::Borrowed("Hello, world!")));
+
+        // Trimming and non-ASCII text take the full pipeline
+        let output = normalizer.normalize_cow(" Hello ").unwrap();
+        assert!(matches!(output, Cow::Owned(ref text) if text == "Hello"));
+        assert!(matches!(
+            normalizer.normalize_cow("你好").unwrap(),
+            Cow::Owned(_)
+        ));
+        // Digits need the FSTs, which are missing
+        assert!(normalizer.normalize_cow("route 66").is_err());
+
+        let config = NormalizerConfig::new().with_remove_puncts(true);
+        assert!(!normalizer.is_passthrough("Hello, world!", &config));
+        let config = NormalizerConfig::new().with_operator(Operator::Itn);
+        assert!(!normalizer.is_passthrough("Hello", &config));
+    }
+
+    #[test]
+    fn test_normalize_into() {
+        let config = NormalizerConfig::new().with_remove_erhua(true);
+        let mut normalizer = Normalizer::new("missing", config);
+        let mut output = String::from("> ");
+        normalizer.normalize_into(&mut output, "Hello").unwrap();
+        normalizer.normalize_into(&mut output, " 你好 ").unwrap();
+        assert_eq!(output, "> Hello你好");
+        // Digits need the FSTs, which are missing
+        assert!(normalizer.normalize_into(&mut output, "route 66").is_err());
+        assert_eq!(output, "> Hello你好");
+        assert!(normalizer.config.remove_erhua);
+
+        let mut outputs = vec!["stale".to_string(); 3];
+        normalizer
+            .normalize_batch_into(&["Hello", "你好"], &mut outputs)
+            .unwrap();
+        assert_eq!(outputs, ["Hello", "你好"]);
+    }
+
+    #[test]
+    fn test_token_parser_for() {
+        let config = NormalizerConfig::new().with_token_order("measure", &["value", "unit"]);
+        let normalizer = Normalizer::new("missing", config);
+        let parser = normalizer.token_parser_for(Language::Zh);
+        assert_eq!(
+            parser
+                .reorder(r#"measure { unit: "千克" value: "5" }"#)
+                .unwrap(),
+            r#"measure { value: "5" unit: "千克" }"#
+        );
+    }
+
+    #[test]
+    fn test_tn_trigger() {
+        let normalizer = Normalizer::with_defaults("missing");
+        let config = NormalizerConfig::new();
+        for text in ["１２３", "½", "Ⅷ", "$", "５０％"] {
+            assert!(!normalizer.should_normalize(text, &config), "{}", text);
+        }
+        assert!(normalizer.should_normalize("3", &config));
+
+        let config = config.with_tn_trigger(TnTrigger::Numeric);
+        for text in ["１２３", "½", "Ⅷ", "$", "５０％", "3"] {
+            assert!(normalizer.should_normalize(text, &config), "{}", text);
+        }
+        assert!(!normalizer.should_normalize("三点五", &config));
+        assert!(!normalizer.is_passthrough("$", &config));
+    }
+
+    #[test]
+    fn test_beautify() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/itn")).unwrap();
+        crate::Rule::replace(
+            "一万两千三百元",
+            r#"money { value: "12300" currency: "元" }"#,
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/itn/tagger.fst"))
+        .unwrap();
+        crate::Rule::replace(r#"money { currency: "元" value: "12300" }"#, "12300元")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/itn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_operator(Operator::Itn);
+        let mut normalizer = Normalizer::new(dir.path(), config.clone());
+        assert_eq!(normalizer.normalize("一万两千三百元").unwrap(), "12300元");
+        let mut normalizer =
+            Normalizer::new(dir.path(), config.with_beautify(crate::Beautify::default()));
+        assert_eq!(normalizer.normalize("一万两千三百元").unwrap(), "12,300元");
+    }
+
+    #[test]
+    fn test_truecase_itn() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/itn")).unwrap();
+        crate::Rule::replace("ok", "ok")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/itn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace("ok", "ok")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/itn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_operator(Operator::Itn)
+            .with_truecase(true);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        assert_eq!(normalizer.normalize("ok").unwrap(), "OK");
+    }
+
+    #[test]
+    fn test_punctuation_restorer() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/itn")).unwrap();
+        let number = r#"cardinal { value: "123" }"#;
+        crate::Rule::replace("一百二十三", number)
+            .or(crate::Rule::replace(
+                "好的",
+                r#"char { value: "好" } char { value: "的" }"#,
+            ))
+            .or(crate::Rule::replace("，", r#"char { value: "，" }"#))
+            .plus()
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/itn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(number, "123")
+            .or(crate::Rule::replace(r#"char { value: "好" }"#, "好"))
+            .or(crate::Rule::replace(r#"char { value: "的" }"#, "的"))
+            .or(crate::Rule::replace(r#"char { value: "，" }"#, "，"))
+            .or(crate::Rule::delete(" "))
+            .plus()
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/itn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_operator(Operator::Itn);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        normalizer.set_punctuation_restorer(|_: &str| Ok("一百，二十三好的".to_string()));
+        assert_eq!(normalizer.normalize("一百二十三好的").unwrap(), "123，好的");
+
+        normalizer.set_punctuation_restorer(|_: &str| Ok("一百好的".to_string()));
+        assert!(matches!(
+            normalizer.normalize("一百二十三好的"),
+            Err(WeTextError::PunctuationError(_))
+        ));
+        normalizer.clear_punctuation_restorer();
+        assert_eq!(normalizer.normalize("一百二十三好的").unwrap(), "123好的");
+    }
+
+    #[test]
+    fn test_masking() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace("共5", r#"char { value: "共" } money { value: "5" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"money { value: "5" }"#, "五元")
+            .or(crate::Rule::replace(r#"char { value: "共" }"#, "共"))
+            .or(crate::Rule::delete(" "))
+            .plus()
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_masking(crate::Masking::new(["共五"]).with_token("[beep]"));
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        assert_eq!(normalizer.normalize("共5").unwrap(), "[beep]元");
+        let spoken: Vec<String> = normalizer
+            .normalize_dual("共5")
+            .unwrap()
+            .into_iter()
+            .map(|span| span.spoken)
+            .collect();
+        assert_eq!(spoken, ["[beep]", "元"]);
+        // Digit-free text is masked too
+        assert_eq!(normalizer.normalize("共五人").unwrap(), "[beep]人");
+    }
+
+    #[test]
+    fn test_dual_time_format() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace("15:30", r#"time { hour: "十五" minute: "三十" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"time { hour: "十五" minute: "三十" }"#, "十五点三十分")
+            .or(crate::Rule::replace(
+                r#"time { noon: "下午" hour: "三" minute: "三十" }"#,
+                "下午三点三十分",
+            ))
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("zh/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new()
+            .with_lang(Language::Zh)
+            .with_time_format(crate::TimeFormat::Hour12);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        assert_eq!(normalizer.normalize("15:30").unwrap(), "下午三点三十分");
+        let spoken: Vec<String> = normalizer
+            .normalize_dual("15:30")
+            .unwrap()
+            .into_iter()
+            .map(|span| span.spoken)
+            .collect();
+        assert_eq!(spoken, ["下午三点三十分"]);
+    }
+
+    #[test]
+    fn test_dual_english_pass() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("en/tn")).unwrap();
+        crate::Rule::replace("1m", r#"measure { value: "1m" }"#)
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("en/tn/tagger.fst"))
+            .unwrap();
+        crate::Rule::replace(r#"measure { value: "1m" }"#, "one meters")
+            .to_fst()
+            .unwrap()
+            .write(dir.path().join("en/tn/verbalizer.fst"))
+            .unwrap();
+
+        let config = NormalizerConfig::new().with_lang(Language::En);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        assert_eq!(normalizer.normalize("1m").unwrap(), "one meter");
+        let spoken: Vec<String> = normalizer
+            .normalize_dual("1m")
+            .unwrap()
+            .into_iter()
+            .map(|span| span.spoken)
+            .collect();
+        assert_eq!(spoken, ["one meter"]);
+    }
+
+    #[test]
+    fn test_verify_readback() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/itn")).unwrap();
+        crate::Rule::replace(
+            "共100元",
+            r#"char { value: "共" } money { value: "100" currency: "元" }"#,
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/tn/tagger.fst"))
+        .unwrap();
+        crate::Rule::replace(
+            "共一百元",
+            r#"char { value: "共" } money { currency: "元" value: "100" }"#,
+        )
+        .or(crate::Rule::replace(
+            "共一千元",
+            r#"char { value: "共" } money { currency: "元" value: "1000" }"#,
+        ))
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/itn/tagger.fst"))
+        .unwrap();
+
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut normalizer = Normalizer::new(dir.path(), config);
+        assert!(normalizer
+            .verify_readback("共100元", "共一百元")
+            .unwrap()
+            .is_empty());
+        let mismatches = normalizer.verify_readback("共100元", "共一千元").unwrap();
+        assert_eq!(mismatches.len(), 1);
+        let found = mismatches[0].found.as_ref().unwrap();
+        assert_eq!(found.value, crate::Number::Integer(1000));
+        assert_eq!(found.span, 3..12);
+    }
+
+    #[test]
+    fn test_find_normalizable_spans() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace(
+            "共100元5%",
+            r#"char { value: "共" } money { value: "100" currency: "元" } measure { value: "5%" }"#,
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/tn/tagger.fst"))
+        .unwrap();
+
+        // No verbalizer is needed
+        let mut normalizer = Normalizer::with_defaults(dir.path());
+        assert_eq!(
+            normalizer.find_normalizable_spans("共100元5%").unwrap(),
+            [CandidateSpan {
+                span: 3..11,
+                category: "money+measure".to_string(),
+                original: "100元5%".to_string(),
+            }]
+        );
+        assert!(normalizer
+            .find_normalizable_spans("你好")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_extract_numbers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace(
+            "共100元",
+            r#"char { value: "共" } money { value: "100" currency: "元" }"#,
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/tn/tagger.fst"))
+        .unwrap();
+
+        let mut normalizer = Normalizer::with_defaults(dir.path());
+        let numbers = normalizer
+            .extract_numbers(" 共100元", Language::Zh)
+            .unwrap();
+        assert_eq!(
+            numbers,
+            [ExtractedNumber {
+                span: 4..10,
+                value: crate::Number::Integer(100),
+                kind: crate::NumberKind::Money,
+            }]
+        );
+        assert!(normalizer
+            .extract_numbers("你好", Language::Zh)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_money() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace(
+            "共100元",
+            r#"char { value: "共" } money { value: "100" currency: "元" }"#,
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/tn/tagger.fst"))
+        .unwrap();
+
+        let mut normalizer = Normalizer::with_defaults(dir.path());
+        assert_eq!(
+            normalizer.parse_money("共100元", Language::Zh).unwrap(),
+            [ExtractedMoney {
+                span: 3..9,
+                currency: Some("CNY"),
+                amount: crate::Number::Integer(100),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_times() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace(
+            "下午3点30分",
+            r#"time { noon: "下午" hour: "3" minute: "30" }"#,
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/tn/tagger.fst"))
+        .unwrap();
+
+        let mut normalizer = Normalizer::with_defaults(dir.path());
+        let times = normalizer.parse_times("下午3点30分", Language::Zh).unwrap();
+        assert_eq!(times.len(), 1);
+        assert_eq!(times[0].0, 0..15);
+        assert_eq!(times[0].1.to_string(), "15:30:00");
+    }
+
+    #[test]
+    fn test_parse_measures() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace(
+            "重5kg",
+            r#"char { value: "重" } measure { value: "五千克" }"#,
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/tn/tagger.fst"))
+        .unwrap();
+
+        let mut normalizer = Normalizer::with_defaults(dir.path());
+        let measures = normalizer.parse_measures("重5kg", Language::Zh).unwrap();
+        assert_eq!(measures.len(), 1);
+        assert_eq!(measures[0].span, 3..6);
+        assert_eq!(measures[0].value, crate::Number::Integer(5));
+        assert_eq!(measures[0].quantity, Some(crate::Quantity::Mass));
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("zh/tn")).unwrap();
+        crate::Rule::replace(
+            "于2024年1月15日",
+            r#"char { value: "于" } date { year: "2024" month: "1" day: "15" }"#,
+        )
+        .to_fst()
+        .unwrap()
+        .write(dir.path().join("zh/tn/tagger.fst"))
+        .unwrap();
+
+        let mut normalizer = Normalizer::with_defaults(dir.path());
+        let dates = normalizer
+            .parse_dates("于2024年1月15日", Language::Zh)
+            .unwrap();
+        assert_eq!(dates, [(3..19, Date::new(2024, 1, 15).unwrap())]);
+    }
+
+    #[test]
+    fn test_normalize_with() {
+        let config = NormalizerConfig::new().with_operator(Operator::Itn);
+        let mut normalizer = Normalizer::new("missing", config);
+        // ITN runs the FSTs on any text, TN skips text without digits
+        assert!(normalizer.normalize("hello").is_err());
+        let overrides = Overrides {
+            operator: Some(Operator::Tn),
+            ..Default::default()
+        };
+        assert_eq!(
+            normalizer.normalize_with("hello", &overrides).unwrap(),
+            "hello"
+        );
+        // The configuration is restored
+        assert!(normalizer.normalize("hello").is_err());
+        assert_eq!(normalizer.config.operator, Operator::Itn);
+    }
+
+    #[test]
+    fn test_set_should_normalize() {
+        let mut normalizer = Normalizer::with_defaults("missing");
+        normalizer.set_should_normalize(|text, _| text.contains("SKU"));
+        // No FST is loaded for text the predicate rejects, even with digits
+        assert_eq!(normalizer.normalize("route 66").unwrap(), "route 66");
+        assert!(normalizer.normalize("SKU").is_err());
+
+        normalizer.clear_should_normalize();
+        assert!(normalizer.normalize("route 66").is_err());
+        assert_eq!(normalizer.normalize("SKU").unwrap(), "SKU");
+    }
 
     #[test]
     fn test_detect_language() {
+        let config = NormalizerConfig::new();
+
         // English
-        assert_eq!(Normalizer::detect_language("hello world"), Language::En);
-        assert_eq!(Normalizer::detect_language("Hello, World!"), Language::En);
+        assert_eq!(
+            Normalizer::detect_language("hello world", &config),
+            Language::En
+        );
+        assert_eq!(
+            Normalizer::detect_language("Hello, World!", &config),
+            Language::En
+        );
 
         // Chinese
-        assert_eq!(Normalizer::detect_language("你好世界"), Language::Zh);
-        assert_eq!(Normalizer::detect_language("今天是2024年"), Language::Zh);
+        assert_eq!(
+            Normalizer::detect_language("你好世界", &config),
+            Language::Zh
+        );
+        assert_eq!(
+            Normalizer::detect_language("今天是2024年", &config),
+            Language::Zh
+        );
 
         // Japanese (Hiragana/Katakana triggers Japanese detection)
-        assert_eq!(Normalizer::detect_language("こんにちは"), Language::Ja); // Hiragana
-        assert_eq!(Normalizer::detect_language("カタカナ"), Language::Ja); // Katakana
-        assert_eq!(Normalizer::detect_language("東京タワー"), Language::Ja); // Mixed Kanji + Katakana
+        assert_eq!(
+            Normalizer::detect_language("こんにちは", &config),
+            Language::Ja
+        ); // Hiragana
+        assert_eq!(
+            Normalizer::detect_language("カタカナ", &config),
+            Language::Ja
+        ); // Katakana
+        assert_eq!(
+            Normalizer::detect_language("東京タワー", &config),
+            Language::Ja
+        ); // Mixed Kanji + Katakana
 
         // Pure digits treated as Chinese (common TTS use case)
-        assert_eq!(Normalizer::detect_language("123"), Language::Zh);
-        assert_eq!(Normalizer::detect_language("2024"), Language::Zh);
+        assert_eq!(Normalizer::detect_language("123", &config), Language::Zh);
+        assert_eq!(Normalizer::detect_language("2024", &config), Language::Zh);
 
         // Edge cases
-        assert_eq!(Normalizer::detect_language(""), Language::En); // Empty defaults to English
+        assert_eq!(Normalizer::detect_language("", &config), Language::En); // Empty defaults to English
+    }
+
+    #[test]
+    fn test_kanji_disambiguation() {
+        let config = NormalizerConfig::new();
+        assert_eq!(
+            Normalizer::detect_language("東京都123番地", &config),
+            Language::Zh
+        );
+        let config = config.with_kanji_disambiguation(KanjiDisambiguation::Dictionary);
+        assert_eq!(
+            Normalizer::detect_language("東京都123番地", &config),
+            Language::Ja
+        );
+        assert_eq!(
+            Normalizer::detect_language("这个100元", &config),
+            Language::Zh
+        );
+        // Undecided text falls back to the hint
+        assert_eq!(Normalizer::detect_language("100元", &config), Language::Zh);
+        let config = config.with_language_hint(LanguageHint::PreferJa);
+        assert_eq!(Normalizer::detect_language("100元", &config), Language::Ja);
+        assert_eq!(
+            Normalizer::detect_language("这个100元", &config),
+            Language::Zh
+        );
+    }
+
+    #[test]
+    fn test_language_hint() {
+        let detect = |text: &str, hint| {
+            Normalizer::detect_language(text, &NormalizerConfig::new().with_language_hint(hint))
+        };
+        assert_eq!(detect("東京都", LanguageHint::PreferJa), Language::Ja);
+        assert_eq!(detect("東京都", LanguageHint::PreferEn), Language::Zh);
+        assert_eq!(detect("123", LanguageHint::PreferEn), Language::En);
+        // Kana and Latin letters decide regardless of the hint
+        assert_eq!(detect("ひらがな", LanguageHint::PreferZh), Language::Ja);
+        assert_eq!(detect("hello", LanguageHint::PreferJa), Language::En);
+
+        let dir = tempfile::tempdir().unwrap();
+        for (lang, spoken) in [("zh", "五"), ("ja", "ご")] {
+            std::fs::create_dir_all(dir.path().join(lang).join("tn")).unwrap();
+            crate::Rule::replace("5", r#"cardinal { value: "5" }"#)
+                .to_fst()
+                .unwrap()
+                .write(dir.path().join(lang).join("tn/tagger.fst"))
+                .unwrap();
+            crate::Rule::replace(r#"cardinal { value: "5" }"#, spoken)
+                .to_fst()
+                .unwrap()
+                .write(dir.path().join(lang).join("tn/verbalizer.fst"))
+                .unwrap();
+        }
+        let mut normalizer = Normalizer::new(dir.path(), NormalizerConfig::new());
+        assert_eq!(
+            normalizer
+                .normalize_with_hint("5", LanguageHint::PreferJa)
+                .unwrap(),
+            "ご"
+        );
+        assert_eq!(normalizer.normalize("5").unwrap(), "五");
     }
 }
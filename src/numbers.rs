@@ -0,0 +1,750 @@
+//! Standalone number verbalization and parsing
+//!
+//! This module converts numbers to their spoken form and back with plain Rust
+//! code, without loading any FST. It is meant for callers that only need
+//! number reading or numeric extraction, and for built-in rules that need to
+//! read digits.
+
+use crate::config::Language;
+use crate::error::{Result, WeTextError};
+
+/// Number reading style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Style {
+    /// Read as a quantity (e.g., 123 → "一百二十三")
+    #[default]
+    Cardinal,
+    /// Read as a position (e.g., 3 → "第三", "third")
+    Ordinal,
+    /// Read digit by digit (e.g., 123 → "一二三")
+    Digits,
+}
+
+const EN_DIGITS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const ZH_DIGITS: [&str; 10] = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+const JA_DIGITS: [&str; 10] = ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+const EN_TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const EN_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const EN_SCALES: [&str; 8] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+];
+
+/// Whether `word` is part of an English number reading ("twenty-five", "point", "million")
+pub(crate) fn is_en_number_word(word: &str) -> bool {
+    let word = word.to_lowercase();
+    word.split('-').all(|part| {
+        matches!(part, "hundred" | "point" | "oh" | "minus")
+            || EN_DIGITS.contains(&part)
+            || EN_TEENS.contains(&part)
+            || EN_TENS.iter().any(|t| !t.is_empty() && *t == part)
+            || EN_SCALES.iter().any(|s| !s.is_empty() && *s == part)
+            || matches!(part, "milliard" | "lakh" | "crore")
+    })
+}
+
+/// Spoken form of a single decimal digit
+pub(crate) fn digit_word(c: char, lang: Language) -> &'static str {
+    let index = c.to_digit(10).unwrap_or(0) as usize;
+    match lang {
+        Language::En => EN_DIGITS[index],
+        Language::Ja => JA_DIGITS[index],
+        Language::Zh | Language::Auto => ZH_DIGITS[index],
+    }
+}
+
+/// Convert a number to its spoken form
+///
+/// Accepts anything that formats as a decimal number: integers, floats, or
+/// strings such as `"-3.14"`. Long digit strings are read exactly, without
+/// going through floating point. `Language::Auto` reads as Chinese.
+///
+/// # Arguments
+/// * `number` - Number to verbalize
+/// * `lang` - Output language
+/// * `style` - Reading style
+///
+/// # Example
+/// ```rust
+/// use wetext_rs::{num_to_words, Language, Style};
+///
+/// assert_eq!(num_to_words(12345, Language::Zh, Style::Cardinal).unwrap(), "一万二千三百四十五");
+/// assert_eq!(num_to_words(21, Language::En, Style::Ordinal).unwrap(), "twenty first");
+/// assert_eq!(num_to_words("3.14", Language::Ja, Style::Cardinal).unwrap(), "三点一四");
+/// ```
+pub fn num_to_words<N: std::fmt::Display>(
+    number: N,
+    lang: Language,
+    style: Style,
+) -> Result<String> {
+    let text = number.to_string();
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.as_str()),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+
+    let valid = !integer.is_empty()
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.is_none_or(|f| !f.is_empty() && f.chars().all(|c| c.is_ascii_digit()));
+    if !valid {
+        return Err(WeTextError::InvalidNumber(text));
+    }
+
+    if style == Style::Digits {
+        let words = read_digits(unsigned, lang);
+        return Ok(if negative { signed(words, lang) } else { words });
+    }
+
+    let value: u128 = integer
+        .parse()
+        .map_err(|_| WeTextError::InvalidNumber(text.clone()))?;
+
+    let mut words = match lang {
+        Language::En => en_cardinal(value),
+        Language::Ja => ja_cardinal(value),
+        Language::Zh | Language::Auto => zh_cardinal(value),
+    };
+
+    if let Some(fraction) = fraction {
+        if style == Style::Ordinal {
+            return Err(WeTextError::InvalidNumber(text));
+        }
+        let digits: Vec<&str> = fraction.chars().map(|c| digit_word(c, lang)).collect();
+        words = match lang {
+            Language::En => format!("{} point {}", words, digits.join(" ")),
+            _ => format!("{}点{}", words, digits.concat()),
+        };
+    }
+
+    if style == Style::Ordinal {
+        if negative {
+            return Err(WeTextError::InvalidNumber(text));
+        }
+        words = match lang {
+            Language::En => en_ordinal(&words),
+            Language::Ja => format!("{}番目", words),
+            Language::Zh | Language::Auto => format!("第{}", words),
+        };
+    }
+
+    if negative {
+        words = signed(words, lang);
+    }
+
+    Ok(words)
+}
+
+/// Put the minus word in front of a reading
+fn signed(words: String, lang: Language) -> String {
+    match lang {
+        Language::En => format!("minus {}", words),
+        Language::Ja => format!("マイナス{}", words),
+        Language::Zh | Language::Auto => format!("负{}", words),
+    }
+}
+
+/// Read every character of a number, including the decimal point
+fn read_digits(text: &str, lang: Language) -> String {
+    let words: Vec<&str> = text
+        .chars()
+        .map(|c| match (c, lang) {
+            ('.', Language::En) => "point",
+            ('.', _) => "点",
+            _ => digit_word(c, lang),
+        })
+        .collect();
+    match lang {
+        Language::En => words.join(" "),
+        _ => words.concat(),
+    }
+}
+
+/// Chinese cardinal reading with 万/亿 grouping and 零 for skipped places
+fn zh_cardinal(n: u128) -> String {
+    zh_cardinal_with(n, &[(100_000_000, "亿"), (10_000, "万")])
+}
+
+/// Chinese cardinal reading with 兆 for 10^12 ("一兆五千亿")
+pub(crate) fn zh_zhao_cardinal(n: u128) -> String {
+    zh_cardinal_with(
+        n,
+        &[
+            (1_000_000_000_000, "兆"),
+            (100_000_000, "亿"),
+            (10_000, "万"),
+        ],
+    )
+}
+
+/// Chinese cardinal reading grouped by the given scales, largest first
+fn zh_cardinal_with(n: u128, scales: &[(u128, &str)]) -> String {
+    if n == 0 {
+        return ZH_DIGITS[0].to_string();
+    }
+    let mut out = zh_cardinal_inner(n, scales);
+    // 10-19 at the start are read "十X" rather than "一十X"
+    if out.starts_with("一十") {
+        out = out["一".len()..].to_string();
+    }
+    out
+}
+
+fn zh_cardinal_inner(n: u128, scales: &[(u128, &str)]) -> String {
+    for &(divisor, unit) in scales {
+        if n >= divisor {
+            let high = zh_cardinal_inner(n / divisor, scales);
+            let rest = n % divisor;
+            if rest == 0 {
+                return format!("{}{}", high, unit);
+            }
+            let zero = if rest < divisor / 10 { "零" } else { "" };
+            return format!(
+                "{}{}{}{}",
+                high,
+                unit,
+                zero,
+                zh_cardinal_inner(rest, scales)
+            );
+        }
+    }
+    zh_below_10000(n as usize)
+}
+
+fn zh_below_10000(n: usize) -> String {
+    let places = [(1000, "千"), (100, "百"), (10, "十"), (1, "")];
+    let mut out = String::new();
+    let mut pending_zero = false;
+    for (value, unit) in places {
+        let digit = n / value % 10;
+        if digit == 0 {
+            pending_zero = !out.is_empty();
+            continue;
+        }
+        if pending_zero {
+            out.push_str(ZH_DIGITS[0]);
+            pending_zero = false;
+        }
+        out.push_str(ZH_DIGITS[digit]);
+        out.push_str(unit);
+    }
+    out
+}
+
+/// Japanese cardinal reading with 万/億/兆/京 grouping
+fn ja_cardinal(n: u128) -> String {
+    if n == 0 {
+        return "零".to_string();
+    }
+    let units = ["", "万", "億", "兆", "京", "垓"];
+    let mut groups = Vec::new();
+    let mut rest = n;
+    while rest > 0 {
+        groups.push((rest % 10_000) as usize);
+        rest /= 10_000;
+    }
+    if groups.len() > units.len() {
+        return read_digits(&n.to_string(), Language::Ja);
+    }
+
+    let mut out = String::new();
+    for (i, group) in groups.iter().enumerate().rev() {
+        if *group > 0 {
+            out.push_str(&ja_below_10000(*group));
+            out.push_str(units[i]);
+        }
+    }
+    out
+}
+
+fn ja_below_10000(n: usize) -> String {
+    let places = [(1000, "千"), (100, "百"), (10, "十")];
+    let mut out = String::new();
+    for (value, unit) in places {
+        let digit = n / value % 10;
+        if digit > 1 {
+            out.push_str(JA_DIGITS[digit]);
+        }
+        if digit > 0 {
+            out.push_str(unit);
+        }
+    }
+    if !n.is_multiple_of(10) {
+        out.push_str(JA_DIGITS[n % 10]);
+    }
+    out
+}
+
+/// English cardinal reading, words separated by spaces
+fn en_cardinal(n: u128) -> String {
+    if n == 0 {
+        return EN_DIGITS[0].to_string();
+    }
+    let mut groups = Vec::new();
+    let mut rest = n;
+    while rest > 0 {
+        groups.push((rest % 1000) as usize);
+        rest /= 1000;
+    }
+    if groups.len() > EN_SCALES.len() {
+        return read_digits(&n.to_string(), Language::En);
+    }
+
+    let mut words: Vec<String> = Vec::new();
+    for (i, group) in groups.iter().enumerate().rev() {
+        if *group == 0 {
+            continue;
+        }
+        words.push(en_below_1000(*group));
+        if !EN_SCALES[i].is_empty() {
+            words.push(EN_SCALES[i].to_string());
+        }
+    }
+    words.join(" ")
+}
+
+/// English cardinal reading with Indian lakh (10^5) and crore (10^7)
+pub(crate) fn en_indian_cardinal(n: u128) -> String {
+    if n < 100_000 {
+        return en_cardinal(n);
+    }
+    let mut words = Vec::new();
+    let (crore, rest) = (n / 10_000_000, n % 10_000_000);
+    if crore > 0 {
+        words.push(en_indian_cardinal(crore));
+        words.push("crore".to_string());
+    }
+    let (lakh, rest) = (rest / 100_000, rest % 100_000);
+    if lakh > 0 {
+        words.push(en_cardinal(lakh));
+        words.push("lakh".to_string());
+    }
+    if rest > 0 {
+        words.push(en_cardinal(rest));
+    }
+    words.join(" ")
+}
+
+fn en_below_1000(n: usize) -> String {
+    let mut words = Vec::new();
+    if n >= 100 {
+        words.push(EN_DIGITS[n / 100]);
+        words.push("hundred");
+    }
+    let rest = n % 100;
+    if rest >= 20 {
+        words.push(EN_TENS[rest / 10]);
+        if !rest.is_multiple_of(10) {
+            words.push(EN_DIGITS[rest % 10]);
+        }
+    } else if rest >= 10 {
+        words.push(EN_TEENS[rest - 10]);
+    } else if rest > 0 {
+        words.push(EN_DIGITS[rest]);
+    }
+    words.join(" ")
+}
+
+/// Turn an English cardinal reading into an ordinal by inflecting the last word
+fn en_ordinal(cardinal: &str) -> String {
+    let (head, last) = match cardinal.rsplit_once(' ') {
+        Some((head, last)) => (format!("{} ", head), last),
+        None => (String::new(), cardinal),
+    };
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        word if word.ends_with('y') => format!("{}ieth", &word[..word.len() - 1]),
+        word => format!("{}th", word),
+    };
+    format!("{}{}", head, last)
+}
+
+/// Number parsed from its spoken form
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    /// A whole number
+    Integer(i64),
+    /// A number with a fractional part
+    Decimal(f64),
+}
+
+impl Number {
+    /// Get the value as a float
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Number::Integer(n) => n as f64,
+            Number::Decimal(n) => n,
+        }
+    }
+
+    /// Get the value as an integer, if it has no fractional part
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Number::Integer(n) => Some(n),
+            Number::Decimal(_) => None,
+        }
+    }
+}
+
+/// Parse a spoken number back to its value
+///
+/// This is a lightweight parser independent of the FST pipeline. It accepts a
+/// single number reading such as "三千零二十一", "二零二四" (digit by digit),
+/// "マイナス三点五" or "two hundred and five". `Language::Auto` parses as
+/// Chinese, which also covers Japanese kanji numerals.
+///
+/// # Example
+/// ```rust
+/// use wetext_rs::{words_to_num, Language, Number};
+///
+/// assert_eq!(words_to_num("三千零二十一", Language::Zh).unwrap(), Number::Integer(3021));
+/// assert_eq!(words_to_num("one point five", Language::En).unwrap(), Number::Decimal(1.5));
+/// ```
+pub fn words_to_num(text: &str, lang: Language) -> Result<Number> {
+    let text = text.trim();
+    let parsed = match lang {
+        Language::En => parse_en(text),
+        Language::Zh | Language::Ja | Language::Auto => parse_cjk(text),
+    };
+    let (negative, integer, fraction) =
+        parsed.ok_or_else(|| WeTextError::InvalidNumber(text.to_string()))?;
+
+    let sign = if negative { -1 } else { 1 };
+    match fraction {
+        None => Ok(Number::Integer(sign * integer)),
+        Some(fraction) => {
+            let value: f64 = format!("{}.{}", integer, fraction)
+                .parse()
+                .map_err(|_| WeTextError::InvalidNumber(text.to_string()))?;
+            Ok(Number::Decimal(sign as f64 * value))
+        }
+    }
+}
+
+/// Parsed parts of a number: sign, integer part and fractional digits
+type NumberParts = (bool, i64, Option<String>);
+
+/// Value of a single CJK digit character
+pub(crate) fn cjk_digit(c: char) -> Option<i64> {
+    let digit = match c {
+        '零' | '〇' => 0,
+        '一' | '幺' | '壹' => 1,
+        '二' | '两' | '貳' | '贰' => 2,
+        '三' | '叁' => 3,
+        '四' | '肆' => 4,
+        '五' | '伍' => 5,
+        '六' | '陆' => 6,
+        '七' | '柒' => 7,
+        '八' | '捌' => 8,
+        '九' | '玖' => 9,
+        _ => return None,
+    };
+    Some(digit)
+}
+
+fn parse_cjk(text: &str) -> Option<NumberParts> {
+    let (negative, text) = ["负", "負", "マイナス"]
+        .iter()
+        .find_map(|sign| text.strip_prefix(sign).map(|rest| (true, rest)))
+        .unwrap_or((false, text));
+    let (integer, fraction) = match text.split_once(['点', '點']) {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (text, None),
+    };
+
+    let fraction = match fraction {
+        Some(f) => {
+            let digits: Option<String> = f
+                .chars()
+                .map(|c| cjk_digit(c).and_then(|d| char::from_digit(d as u32, 10)))
+                .collect();
+            Some(digits.filter(|d| !d.is_empty())?)
+        }
+        None => None,
+    };
+
+    Some((negative, parse_cjk_integer(integer)?, fraction))
+}
+
+fn parse_cjk_integer(text: &str) -> Option<i64> {
+    if text.is_empty() {
+        return None;
+    }
+
+    // Digit-by-digit reading without any unit characters (e.g. "二零二四")
+    if text.chars().all(|c| cjk_digit(c).is_some()) {
+        return text.chars().try_fold(0i64, |acc, c| {
+            acc.checked_mul(10)?.checked_add(cjk_digit(c)?)
+        });
+    }
+
+    let mut total: i64 = 0;
+    // Last of 亿 and 兆 seen
+    let mut big_unit: i64 = i64::MAX;
+    let mut section: i64 = 0;
+    let mut number: i64 = 0;
+    for c in text.chars() {
+        if let Some(digit) = cjk_digit(c) {
+            number = digit;
+            continue;
+        }
+        match c {
+            '十' | '拾' | '百' | '佰' | '千' | '仟' => {
+                let unit = match c {
+                    '十' | '拾' => 10,
+                    '百' | '佰' => 100,
+                    _ => 1000,
+                };
+                // A bare unit such as "十" or "百" counts as one
+                let multiplier = if number == 0 { 1 } else { number };
+                section += multiplier * unit;
+                number = 0;
+            }
+            '万' | '萬' => {
+                section = (section + number).checked_mul(10_000)?;
+                number = 0;
+            }
+            '亿' | '億' | '兆' => {
+                let unit: i64 = if c == '兆' {
+                    1_000_000_000_000
+                } else {
+                    100_000_000
+                };
+                // A smaller unit after a larger one starts a new group
+                // ("一兆五千亿"); otherwise it multiplies everything before it
+                total = if unit < big_unit {
+                    total.checked_add((section + number).checked_mul(unit)?)?
+                } else {
+                    (total + section + number).checked_mul(unit)?
+                };
+                big_unit = unit;
+                section = 0;
+                number = 0;
+            }
+            _ => return None,
+        }
+    }
+
+    total.checked_add(section)?.checked_add(number)
+}
+
+fn en_word_value(word: &str) -> Option<i64> {
+    if let Some(i) = EN_DIGITS.iter().position(|w| *w == word) {
+        return Some(i as i64);
+    }
+    if let Some(i) = EN_TEENS.iter().position(|w| *w == word) {
+        return Some(10 + i as i64);
+    }
+    if let Some(i) = EN_TENS.iter().position(|w| !w.is_empty() && *w == word) {
+        return Some(10 * i as i64);
+    }
+    None
+}
+
+fn parse_en(text: &str) -> Option<NumberParts> {
+    let lower = text.to_lowercase();
+    let mut words: Vec<&str> = lower
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|w| !w.is_empty() && *w != "and")
+        .collect();
+
+    let negative = matches!(words.first(), Some(&"minus") | Some(&"negative"));
+    if negative {
+        words.remove(0);
+    }
+
+    let (integer_words, fraction) = match words.iter().position(|w| *w == "point") {
+        Some(i) => {
+            let digits: Option<String> = words[i + 1..]
+                .iter()
+                .map(|w| EN_DIGITS.iter().position(|d| d == w))
+                .map(|d| d.and_then(|d| char::from_digit(d as u32, 10)))
+                .collect();
+            (&words[..i], Some(digits.filter(|d| !d.is_empty())?))
+        }
+        None => (&words[..], None),
+    };
+
+    if integer_words.is_empty() {
+        return None;
+    }
+
+    let mut total: i64 = 0;
+    let mut current: i64 = 0;
+    for word in integer_words {
+        if let Some(value) = en_word_value(word) {
+            current += value;
+        } else if *word == "hundred" {
+            current = current.max(1) * 100;
+        } else if *word == "lakh" || *word == "crore" {
+            let unit = if *word == "lakh" { 100_000 } else { 10_000_000 };
+            // "one lakh crore": a crore multiplies everything before it
+            if *word == "crore" {
+                current = total.checked_add(current)?.max(1);
+                total = 0;
+            }
+            total = total.checked_add(current.max(1).checked_mul(unit)?)?;
+            current = 0;
+        } else if let Some(scale) = EN_SCALES.iter().position(|s| !s.is_empty() && s == word) {
+            let unit = 1000i64.checked_pow(scale as u32)?;
+            total = total.checked_add(current.max(1).checked_mul(unit)?)?;
+            current = 0;
+        } else {
+            return None;
+        }
+    }
+
+    Some((negative, total.checked_add(current)?, fraction))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zh_cardinal() {
+        let zh = |n: u64| num_to_words(n, Language::Zh, Style::Cardinal).unwrap();
+        assert_eq!(zh(0), "零");
+        assert_eq!(zh(10), "十");
+        assert_eq!(zh(15), "十五");
+        assert_eq!(zh(105), "一百零五");
+        assert_eq!(zh(1010), "一千零一十");
+        assert_eq!(zh(12345), "一万二千三百四十五");
+        assert_eq!(zh(100_005), "十万零五");
+        assert_eq!(zh(300_000_000), "三亿");
+        assert_eq!(zh_zhao_cardinal(1_500_000_000_000), "一兆五千亿");
+        assert_eq!(zh_zhao_cardinal(12_000_000), "一千二百万");
+    }
+
+    #[test]
+    fn test_ja_cardinal() {
+        let ja = |n: u64| num_to_words(n, Language::Ja, Style::Cardinal).unwrap();
+        assert_eq!(ja(100), "百");
+        assert_eq!(ja(2024), "二千二十四");
+        assert_eq!(ja(10_000), "一万");
+        assert_eq!(ja(1_100_000_000), "十一億");
+    }
+
+    #[test]
+    fn test_en_cardinal_and_ordinal() {
+        assert_eq!(
+            num_to_words(1_000_021, Language::En, Style::Cardinal).unwrap(),
+            "one million twenty one"
+        );
+        assert_eq!(
+            num_to_words(40, Language::En, Style::Ordinal).unwrap(),
+            "fortieth"
+        );
+        assert_eq!(
+            num_to_words(-2.5, Language::En, Style::Cardinal).unwrap(),
+            "minus two point five"
+        );
+    }
+
+    #[test]
+    fn test_en_indian_cardinal() {
+        assert_eq!(en_indian_cardinal(99_999), en_cardinal(99_999));
+        assert_eq!(
+            en_indian_cardinal(12_345_678),
+            "one crore twenty three lakh forty five thousand six hundred seventy eight"
+        );
+        assert_eq!(en_indian_cardinal(100_000 * 10_000_000), "one lakh crore");
+        assert_eq!(
+            words_to_num("one crore twenty three lakh", Language::En).unwrap(),
+            Number::Integer(12_300_000)
+        );
+        assert_eq!(
+            words_to_num("one lakh crore", Language::En).unwrap(),
+            Number::Integer(1_000_000_000_000)
+        );
+    }
+
+    #[test]
+    fn test_digits_and_errors() {
+        assert_eq!(
+            num_to_words("0101", Language::Zh, Style::Digits).unwrap(),
+            "零一零一"
+        );
+        assert_eq!(
+            num_to_words(-12, Language::Zh, Style::Digits).unwrap(),
+            "负一二"
+        );
+        assert_eq!(
+            num_to_words("-1.5", Language::En, Style::Digits).unwrap(),
+            "minus one point five"
+        );
+        assert_eq!(
+            num_to_words(3, Language::Zh, Style::Ordinal).unwrap(),
+            "第三"
+        );
+        assert!(num_to_words("12a", Language::Zh, Style::Cardinal).is_err());
+        assert!(num_to_words("1.5", Language::Zh, Style::Ordinal).is_err());
+    }
+
+    #[test]
+    fn test_words_to_num() {
+        let zh = |s: &str| words_to_num(s, Language::Zh).unwrap();
+        assert_eq!(zh("三千零二十一"), Number::Integer(3021));
+        assert_eq!(zh("十五"), Number::Integer(15));
+        assert_eq!(zh("二零二四"), Number::Integer(2024));
+        assert_eq!(zh("一亿二千万"), Number::Integer(120_000_000));
+        assert_eq!(zh("一兆五千亿"), Number::Integer(1_500_000_000_000));
+        assert_eq!(zh("负一点五"), Number::Decimal(-1.5));
+        assert_eq!(
+            words_to_num("二千二十四", Language::Ja).unwrap(),
+            Number::Integer(2024)
+        );
+        assert_eq!(
+            words_to_num("two hundred and five thousand", Language::En).unwrap(),
+            Number::Integer(205_000)
+        );
+        assert!(words_to_num("你好", Language::Zh).is_err());
+        assert!(words_to_num("one potato", Language::En).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for n in [0u64, 7, 10, 19, 105, 3021, 100_005, 12_345_678, 900_000_001] {
+            for lang in [Language::Zh, Language::Ja, Language::En] {
+                let words = num_to_words(n, lang, Style::Cardinal).unwrap();
+                assert_eq!(
+                    words_to_num(&words, lang).unwrap(),
+                    Number::Integer(n as i64),
+                    "{}",
+                    words
+                );
+            }
+        }
+    }
+}
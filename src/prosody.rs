@@ -0,0 +1,120 @@
+//! Pause hints for TTS engines
+//!
+//! Normalized long numbers run together when synthesized. This stage marks
+//! pauses after them and, for SSML output, at clause boundaries.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::config::PauseHints;
+use crate::numbers::is_en_number_word;
+
+/// Chinese and Japanese number readings of at least five characters
+static CJK_NUMBER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[零〇一二两三四五六七八九十百千万亿幺点]{5,}").unwrap());
+
+/// Minimum number of words in an English number reading that gets a pause
+const EN_MIN_WORDS: usize = 4;
+
+/// Clause-ending punctuation
+fn is_clause_mark(c: char) -> bool {
+    matches!(
+        c,
+        '，' | '。' | '、' | '；' | '：' | '！' | '？' | ',' | '.' | ';' | ':' | '!' | '?'
+    )
+}
+
+/// Insert pause hints into normalized text
+pub(crate) fn insert_pause_hints(text: &str, hints: &PauseHints) -> String {
+    // Byte offsets after which a pause follows a long number
+    let mut numbers: Vec<usize> = CJK_NUMBER.find_iter(text).map(|m| m.end()).collect();
+    numbers.extend(en_number_ends(text));
+    numbers.sort_unstable();
+    numbers.dedup();
+    // A number followed by punctuation already has a pause
+    numbers.retain(|&end| !text[end..].starts_with(is_clause_mark));
+
+    let mut output = String::with_capacity(text.len());
+    let mut numbers = numbers.into_iter().peekable();
+    for (i, c) in text.char_indices() {
+        if numbers.next_if_eq(&i).is_some() {
+            output.push_str(&number_pause(hints, c.is_ascii()));
+        }
+        output.push(c);
+        if let PauseHints::Ssml { clause_ms, .. } = hints {
+            let end = i + c.len_utf8();
+            if is_clause_mark(c) && end < text.len() && !text[end..].starts_with(is_clause_mark) {
+                output.push_str(&format!("<break time=\"{}ms\"/>", clause_ms));
+            }
+        }
+    }
+    // A number at the end of the text gets no pause: nothing follows it
+    output
+}
+
+/// Pause inserted after a long number
+fn number_pause(hints: &PauseHints, ascii_context: bool) -> String {
+    match hints {
+        PauseHints::Punctuation if ascii_context => ",".to_string(),
+        PauseHints::Punctuation => "，".to_string(),
+        PauseHints::Ssml { number_ms, .. } => format!("<break time=\"{}ms\"/>", number_ms),
+    }
+}
+
+/// End offsets of English number readings of at least `EN_MIN_WORDS` words
+fn en_number_ends(text: &str) -> Vec<usize> {
+    let mut ends = Vec::new();
+    let mut run = 0;
+    let mut run_end = 0;
+    let mut offset = 0;
+    for word in text.split(' ') {
+        if !word.is_empty() && is_en_number_word(word) {
+            run += 1;
+            run_end = offset + word.len();
+        } else {
+            if run >= EN_MIN_WORDS {
+                ends.push(run_end);
+            }
+            run = 0;
+        }
+        offset += word.len() + 1;
+    }
+    if run >= EN_MIN_WORDS && run_end < text.len() {
+        ends.push(run_end);
+    }
+    ends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_punctuation_hints() {
+        assert_eq!(
+            insert_pause_hints("共一万二千三百元整", &PauseHints::Punctuation),
+            "共一万二千三百，元整"
+        );
+        assert_eq!(
+            insert_pause_hints("call one two three four now", &PauseHints::Punctuation),
+            "call one two three four, now"
+        );
+        // Short numbers and numbers before punctuation are left alone
+        assert_eq!(
+            insert_pause_hints("三点，一万二千三百。", &PauseHints::Punctuation),
+            "三点，一万二千三百。"
+        );
+    }
+
+    #[test]
+    fn test_ssml_hints() {
+        let hints = PauseHints::Ssml {
+            clause_ms: 300,
+            number_ms: 150,
+        };
+        assert_eq!(
+            insert_pause_hints("你好，一万二千三百元", &hints),
+            "你好，<break time=\"300ms\"/>一万二千三百<break time=\"150ms\"/>元"
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Punctuation restoration before ITN
+//!
+//! ASR transcripts come without punctuation, and punctuation models are
+//! trained on spoken text, so they run before ITN. A model may put a mark
+//! inside a spoken number ("一百，二十三"), which would split it for the
+//! tagger. The restorer's output is therefore read back as a list of edits,
+//! and marks that fall inside an entity are moved to the end of the entity.
+
+use std::ops::Range;
+
+use crate::error::{Result, WeTextError};
+
+/// External punctuation model run on spoken text before ITN
+///
+/// Implemented for closures `Fn(&str) -> Result<String>`.
+pub trait PunctuationRestorer: Send + Sync {
+    /// Return `text` with punctuation added
+    ///
+    /// Only punctuation and whitespace may be added, and only whitespace
+    /// removed; any other change is reported as a `PunctuationError`.
+    fn restore(&self, text: &str) -> Result<String>;
+}
+
+impl<F> PunctuationRestorer for F
+where
+    F: Fn(&str) -> Result<String> + Send + Sync,
+{
+    fn restore(&self, text: &str) -> Result<String> {
+        self(text)
+    }
+}
+
+/// A change made by the restorer: `range` of the original is replaced by `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Edit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+/// Punctuation marks a restorer may insert
+fn is_mark(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || matches!(
+            c,
+            '，' | '。'
+                | '、'
+                | '；'
+                | '：'
+                | '！'
+                | '？'
+                | '…'
+                | '—'
+                | '“'
+                | '”'
+                | '‘'
+                | '’'
+                | '《'
+                | '》'
+                | '（'
+                | '）'
+                | '「'
+                | '」'
+        )
+}
+
+/// Append an edit, merging it with the previous one if they touch
+fn push(edits: &mut Vec<Edit>, range: Range<usize>, text: &str) {
+    match edits.last_mut() {
+        Some(last) if last.range.end == range.start => {
+            last.range.end = range.end;
+            last.text.push_str(text);
+        }
+        _ => edits.push(Edit {
+            range,
+            text: text.to_string(),
+        }),
+    }
+}
+
+/// Edits that turn `original` into `restored`
+pub(crate) fn edits(original: &str, restored: &str) -> Result<Vec<Edit>> {
+    let mut edits = Vec::new();
+    let mut pos = 0;
+    for c in restored.chars() {
+        let rest = &original[pos..];
+        if rest.starts_with(c) {
+            pos += c.len_utf8();
+        } else if is_mark(c) || c.is_whitespace() {
+            push(&mut edits, pos..pos, c.encode_utf8(&mut [0; 4]));
+        } else {
+            // The restorer may drop whitespace
+            let skipped = rest.len() - rest.trim_start().len();
+            if skipped == 0 || !rest[skipped..].starts_with(c) {
+                return Err(WeTextError::PunctuationError(format!(
+                    "restorer changed the text at byte {}: {:?}",
+                    pos, restored
+                )));
+            }
+            push(&mut edits, pos..pos + skipped, "");
+            pos += skipped + c.len_utf8();
+        }
+    }
+    let rest = &original[pos..];
+    if !rest.trim().is_empty() {
+        return Err(WeTextError::PunctuationError(format!(
+            "restorer dropped {:?}",
+            rest
+        )));
+    }
+    if !rest.is_empty() {
+        push(&mut edits, pos..original.len(), "");
+    }
+    Ok(edits)
+}
+
+/// Apply edits to `text`, moving marks out of `entities`
+///
+/// An edit inside an entity keeps its marks, without whitespace, at the end
+/// of the entity, unless the restorer already put a mark there; whitespace
+/// inside entities is left as it is.
+pub(crate) fn apply(text: &str, edits: Vec<Edit>, entities: &[Range<usize>]) -> String {
+    let marked_at = |pos: usize| {
+        edits
+            .iter()
+            .any(|edit| edit.range.start == pos && edit.text.contains(is_mark))
+    };
+    let mut moved = Vec::new();
+    for edit in &edits {
+        let entity = entities
+            .iter()
+            .find(|entity| entity.start < edit.range.start && edit.range.start < entity.end);
+        match entity {
+            Some(entity) if marked_at(entity.end) => {}
+            Some(entity) => moved.push(Edit {
+                range: entity.end..entity.end,
+                text: edit.text.split_whitespace().collect(),
+            }),
+            None => moved.push(edit.clone()),
+        }
+    }
+    let mut edits: Vec<Edit> = moved
+        .into_iter()
+        .filter(|edit| !edit.range.is_empty() || !edit.text.is_empty())
+        .collect();
+    // Stable, so marks moved to an entity end stay before later edits there
+    edits.sort_by_key(|edit| edit.range.start);
+
+    let mut output = String::with_capacity(text.len() + edits.len());
+    let mut last = 0;
+    for edit in edits {
+        let start = edit.range.start.max(last);
+        output.push_str(&text[last..start]);
+        output.push_str(&edit.text);
+        last = edit.range.end.max(start);
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edits() {
+        assert!(edits("yes i paid one hundred", "Yes, i paid one hundred.").is_err());
+
+        let found = edits("yes i paid  one hundred", "yes, i paid one hundred.").unwrap();
+        assert_eq!(
+            found,
+            [
+                Edit {
+                    range: 3..3,
+                    text: ",".to_string(),
+                },
+                Edit {
+                    range: 11..12,
+                    text: String::new(),
+                },
+                Edit {
+                    range: 23..23,
+                    text: ".".to_string(),
+                },
+            ]
+        );
+        assert!(edits("好的", "好").is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        let text = "一百二十三好的";
+        let found = edits(text, "一百，二十三，好的。").unwrap();
+        assert_eq!(apply(text, found.clone(), &[]), "一百，二十三，好的。");
+        assert_eq!(
+            apply(text, found, std::slice::from_ref(&(0..15))),
+            "一百二十三，好的。"
+        );
+
+        let text = "one hundred ok";
+        let found = edits(text, "one, hundred ok.").unwrap();
+        assert_eq!(
+            apply(text, found, std::slice::from_ref(&(0..11))),
+            "one hundred, ok."
+        );
+    }
+}
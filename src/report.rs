@@ -0,0 +1,609 @@
+//! Structured normalization reports
+//!
+//! This module extracts structured information from normalizer output, such
+//! as the out-of-vocabulary spans marked by the `tag_oov` stage, and aligns
+//! tagged tokens with the input they were tagged from.
+
+use std::ops::Range;
+
+use crate::config::{Language, Operator};
+
+/// Opening marker inserted by the tag_oov FST
+pub(crate) const OOV_OPEN: &str = "<oov>";
+/// Closing marker inserted by the tag_oov FST
+pub(crate) const OOV_CLOSE: &str = "</oov>";
+
+/// An out-of-vocabulary span in the normalized output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OovSpan {
+    /// The OOV text, without markers
+    pub text: String,
+    /// Byte offset of the span start in the normalized text
+    pub start: usize,
+    /// Byte offset of the span end (exclusive) in the normalized text
+    pub end: usize,
+}
+
+/// Score of the path an FST chose for an input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Confidence {
+    /// Tropical weight of the chosen path; lower is more certain
+    pub weight: f32,
+    /// Weight difference to the best path with a different output, when
+    /// more than one path was considered and one exists
+    pub margin: Option<f32>,
+}
+
+/// Normalization result with additional structured information
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NormalizeReport {
+    /// Normalized text, exactly as returned by `Normalizer::normalize`
+    pub text: String,
+    /// OOV spans tagged in `text` (empty unless `tag_oov` is enabled)
+    pub oov: Vec<OovSpan>,
+    /// Score of the tagger's path (`None` unless `report_confidence` is
+    /// enabled and the text went through the tagger)
+    pub confidence: Option<Confidence>,
+    /// Spans of the preprocessed input the tagger accepted no path for,
+    /// even on their own (empty unless `partial_match` is enabled)
+    pub unmatched: Vec<String>,
+}
+
+/// A span of input paired with its spoken form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DualSpan {
+    /// Original written form
+    pub written: String,
+    /// Spoken form produced by the normalizer
+    pub spoken: String,
+    /// Whether this span was tagged as an entity (false for plain text)
+    pub normalized: bool,
+}
+
+impl DualSpan {
+    pub(crate) fn unchanged(text: &str) -> Self {
+        Self {
+            written: text.to_string(),
+            spoken: text.to_string(),
+            normalized: false,
+        }
+    }
+
+    pub(crate) fn normalized(written: &str, spoken: &str) -> Self {
+        Self {
+            written: written.to_string(),
+            spoken: spoken.to_string(),
+            normalized: true,
+        }
+    }
+}
+
+/// A token of normalized output, as returned by `Normalizer::normalize_segmented`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// Spoken form of the token
+    pub text: String,
+    /// Token type for entities (e.g., "date", "money"); `None` for plain text
+    pub label: Option<String>,
+}
+
+/// A span of normalized output with its source, as returned by `Normalizer::normalize_tokens`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedSegment {
+    /// Spoken form of the span
+    pub text: String,
+    /// Token type for entities (e.g., "date", "money"); `None` for untouched text
+    pub category: Option<String>,
+    /// Original written form of the span
+    pub original: String,
+}
+
+/// A span the normalizer would rewrite, as returned by `Normalizer::find_normalizable_spans`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandidateSpan {
+    /// Byte range of the span in the input
+    pub span: Range<usize>,
+    /// Token type the span was tagged as (e.g., "date", "money"); a run of
+    /// entities of mixed types has the types joined by `+`
+    pub category: String,
+    /// Written form of the span
+    pub original: String,
+}
+
+/// A sentence of a document, as returned by `Normalizer::normalize_document`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentSegment {
+    /// Original text of the sentence
+    pub text: String,
+    /// Language the sentence was normalized as
+    pub lang: Language,
+    /// Normalized text
+    pub normalized: String,
+}
+
+/// A normalized document, split into sentences
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Document {
+    /// Sentences in document order
+    pub segments: Vec<DocumentSegment>,
+}
+
+/// A span of normalized output that has other plausible readings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ambiguity {
+    /// The chosen reading of the span
+    pub text: String,
+    /// Byte offset of the span start in the chosen output
+    pub start: usize,
+    /// Byte offset of the span end (exclusive) in the chosen output
+    pub end: usize,
+    /// Other readings of the span, most plausible first
+    pub alternatives: Vec<String>,
+}
+
+/// A language and operator whose tagger and verbalizer FSTs are installed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capability {
+    /// Language of the pipeline
+    pub lang: Language,
+    /// Operation of the pipeline
+    pub operator: Operator,
+    /// Config option selecting FST variants (e.g. `"enable_0_to_9"`), or
+    /// `None` for the default FSTs
+    pub option: Option<&'static str>,
+}
+
+/// What an FST directory supports, as found by `Normalizer::capabilities`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Capabilities {
+    /// Installed pipelines, including option variants
+    pub pipelines: Vec<Capability>,
+    /// Config options whose preprocessing or postprocessing FST is installed
+    /// (e.g. `"traditional_to_simple"`)
+    pub processing: Vec<&'static str>,
+}
+
+impl Capabilities {
+    /// Whether the default FSTs for a language and operator are installed
+    pub fn supports(&self, lang: Language, operator: Operator) -> bool {
+        self.supports_option(lang, operator, None)
+    }
+
+    /// Whether the FSTs for a language, operator and option are installed
+    pub fn supports_option(
+        &self,
+        lang: Language,
+        operator: Operator,
+        option: Option<&str>,
+    ) -> bool {
+        self.pipelines
+            .iter()
+            .any(|c| c.lang == lang && c.operator == operator && c.option == option)
+    }
+
+    /// Languages with at least one installed pipeline for `operator`
+    pub fn languages(&self, operator: Operator) -> Vec<Language> {
+        let mut langs = Vec::new();
+        for c in &self.pipelines {
+            if c.operator == operator && !langs.contains(&c.lang) {
+                langs.push(c.lang);
+            }
+        }
+        langs
+    }
+}
+
+/// A verbalized token, as input to `align_dual`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Piece {
+    /// A `char` token copied through unchanged
+    Char(String),
+    /// An entity token with its spoken form
+    Entity(String),
+}
+
+/// Align verbalized tokens with the text they were tagged from
+///
+/// Tokens don't carry their source offsets, so entities are located by the
+/// plain characters around them: an entity extends up to the next occurrence
+/// of the characters that follow it. Consecutive entities are merged into a
+/// single span, joined with `separator`. If the tokens cannot be aligned,
+/// the whole text is returned as one span.
+pub(crate) fn align_dual(text: &str, pieces: &[Piece], separator: &str) -> Vec<DualSpan> {
+    let fallback = || {
+        let spoken: Vec<&str> = pieces
+            .iter()
+            .map(|p| match p {
+                Piece::Char(s) | Piece::Entity(s) => s.as_str(),
+            })
+            .collect();
+        vec![DualSpan::normalized(text, &spoken.join(separator))]
+    };
+
+    let mut spans: Vec<DualSpan> = Vec::new();
+    let mut pos = 0;
+    let mut i = 0;
+
+    while i < pieces.len() {
+        match &pieces[i] {
+            Piece::Char(value) => {
+                // Skip whitespace the tagger dropped between tokens
+                let rest = &text[pos..];
+                let skipped = if rest.starts_with(value.as_str()) {
+                    0
+                } else {
+                    rest.len() - rest.trim_start().len()
+                };
+                if !text[pos + skipped..].starts_with(value.as_str()) {
+                    return fallback();
+                }
+                let end = pos + skipped + value.len();
+                push_unchanged(&mut spans, &text[pos..end]);
+                pos = end;
+                i += 1;
+            }
+            Piece::Entity(_) => {
+                let mut spoken = Vec::new();
+                while let Some(Piece::Entity(s)) = pieces.get(i) {
+                    spoken.push(s.as_str());
+                    i += 1;
+                }
+                let rest = &pieces[i..];
+                let end = if rest.iter().all(|p| matches!(p, Piece::Char(_))) {
+                    // Entity at the end of the text: everything up to the trailing characters
+                    suffix_start(&text[pos..], rest).map(|offset| pos + offset)
+                } else if let Some(Piece::Char(next)) = rest.first() {
+                    // Otherwise up to the next occurrence of the following character,
+                    // covering at least one character
+                    let first = text[pos..].chars().next().map_or(0, char::len_utf8);
+                    text[pos + first..]
+                        .find(next.as_str())
+                        .map(|offset| pos + first + offset)
+                } else {
+                    None
+                };
+                let Some(end) = end else {
+                    return fallback();
+                };
+                if end <= pos || !text.is_char_boundary(end) {
+                    return fallback();
+                }
+
+                // Whitespace around the entity stays with the plain text
+                let written = &text[pos..end];
+                let entity = written.trim();
+                let lead = written.len() - written.trim_start().len();
+                if lead > 0 {
+                    push_unchanged(&mut spans, &written[..lead]);
+                }
+                spans.push(DualSpan::normalized(entity, &spoken.join(separator)));
+                if lead + entity.len() < written.len() {
+                    push_unchanged(&mut spans, &written[lead + entity.len()..]);
+                }
+                pos = end;
+            }
+        }
+    }
+
+    if pos < text.len() {
+        push_unchanged(&mut spans, &text[pos..]);
+    }
+    spans
+}
+
+/// Attach token types to aligned spans
+///
+/// `runs` holds the token types of each run of consecutive entities, in the
+/// order `align_dual` merged them into normalized spans. A run of mixed types
+/// is labeled with the types joined by `+`. If the spans don't line up with
+/// the runs (alignment fell back to a single span), every type is attached to
+/// each normalized span.
+pub(crate) fn label_spans(spans: Vec<DualSpan>, runs: &[Vec<String>]) -> Vec<NormalizedSegment> {
+    let join = |labels: &[String]| {
+        let mut unique: Vec<&str> = Vec::new();
+        for label in labels {
+            if !unique.contains(&label.as_str()) {
+                unique.push(label);
+            }
+        }
+        unique.join("+")
+    };
+    let aligned = spans.iter().filter(|s| s.normalized).count() == runs.len();
+    let all = runs.concat();
+    let mut runs = runs.iter();
+
+    spans
+        .into_iter()
+        .map(|span| {
+            let category = span.normalized.then(|| match runs.next() {
+                Some(run) if aligned => join(run),
+                _ => join(&all),
+            });
+            NormalizedSegment {
+                text: span.spoken,
+                category,
+                original: span.written,
+            }
+        })
+        .collect()
+}
+
+/// Locate where alternative readings differ from the chosen one
+///
+/// `readings[0]` is the chosen output. Each other reading is compared with
+/// it character by character; the part between their common prefix and
+/// suffix becomes an ambiguous span. Alternatives for the same span are
+/// grouped, and spans are returned in output order.
+pub(crate) fn find_ambiguities(readings: &[String]) -> Vec<Ambiguity> {
+    let Some((chosen, others)) = readings.split_first() else {
+        return Vec::new();
+    };
+    let mut ambiguities: Vec<Ambiguity> = Vec::new();
+    for other in others {
+        let prefix: usize = chosen
+            .chars()
+            .zip(other.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        let suffix: usize = chosen[prefix..]
+            .chars()
+            .rev()
+            .zip(other[prefix..].chars().rev())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        let (start, end) = (prefix, chosen.len() - suffix);
+        let alternative = other[prefix..other.len() - suffix].to_string();
+        match ambiguities
+            .iter_mut()
+            .find(|a| a.start == start && a.end == end)
+        {
+            Some(ambiguity) => ambiguity.alternatives.push(alternative),
+            None => ambiguities.push(Ambiguity {
+                text: chosen[start..end].to_string(),
+                start,
+                end,
+                alternatives: vec![alternative],
+            }),
+        }
+    }
+    ambiguities.sort_by_key(|a| (a.start, a.end));
+    ambiguities
+}
+
+/// Find where the trailing `Char` pieces start in `text`, allowing whitespace between them
+fn suffix_start(text: &str, pieces: &[Piece]) -> Option<usize> {
+    let mut end = text.len();
+    for piece in pieces.iter().rev() {
+        let Piece::Char(value) = piece else {
+            return None;
+        };
+        let rest = text[..end].trim_end();
+        if !rest.ends_with(value.as_str()) {
+            return None;
+        }
+        end = rest.len() - value.len();
+    }
+    Some(end)
+}
+
+/// Append plain text, merging it into a preceding plain span
+fn push_unchanged(spans: &mut Vec<DualSpan>, text: &str) {
+    match spans.last_mut() {
+        Some(last) if !last.normalized => {
+            last.written.push_str(text);
+            last.spoken.push_str(text);
+        }
+        _ => spans.push(DualSpan::unchanged(text)),
+    }
+}
+
+/// How changed spans are marked in annotated output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Annotation {
+    /// Wrap each change as `{open}{written}{separator}{spoken}{close}`
+    Markers {
+        /// Text inserted before the change
+        open: String,
+        /// Text inserted between the written and spoken forms
+        separator: String,
+        /// Text inserted after the change
+        close: String,
+    },
+    /// Replace each change with an inline JSON object
+    /// `{"written":"...","spoken":"..."}`
+    Json,
+}
+
+impl Default for Annotation {
+    /// `[[2024年→二零二四年]]`
+    fn default() -> Self {
+        Annotation::Markers {
+            open: "[[".to_string(),
+            separator: "→".to_string(),
+            close: "]]".to_string(),
+        }
+    }
+}
+
+/// Render aligned spans, marking every span whose spoken form differs
+pub(crate) fn annotate(spans: &[DualSpan], annotation: &Annotation) -> String {
+    let mut output = String::new();
+    for span in spans {
+        if span.written == span.spoken {
+            output.push_str(&span.written);
+            continue;
+        }
+        match annotation {
+            Annotation::Markers {
+                open,
+                separator,
+                close,
+            } => {
+                output.push_str(open);
+                output.push_str(&span.written);
+                output.push_str(separator);
+                output.push_str(&span.spoken);
+                output.push_str(close);
+            }
+            Annotation::Json => {
+                // Serializing a str cannot fail
+                output.push_str(&format!(
+                    r#"{{"written":{},"spoken":{}}}"#,
+                    serde_json::to_string(&span.written).unwrap_or_default(),
+                    serde_json::to_string(&span.spoken).unwrap_or_default(),
+                ));
+            }
+        }
+    }
+    output
+}
+
+/// Find the OOV spans marked in `text`
+///
+/// Offsets refer to the text between the markers.
+pub(crate) fn find_oov_spans(text: &str) -> Vec<OovSpan> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+
+    while let Some(open) = text[pos..].find(OOV_OPEN) {
+        let start = pos + open + OOV_OPEN.len();
+        let Some(close) = text[start..].find(OOV_CLOSE) else {
+            break;
+        };
+        let end = start + close;
+        pos = end + OOV_CLOSE.len();
+
+        spans.push(OovSpan {
+            text: text[start..end].to_string(),
+            start,
+            end,
+        });
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_oov_spans() {
+        let text = "你好<oov>𠮷</oov>世界<oov>A</oov>";
+        let spans = find_oov_spans(text);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].text, "𠮷");
+        assert_eq!(&text[spans[0].start..spans[0].end], "𠮷");
+        assert_eq!(spans[1].text, "A");
+
+        // Unterminated markers are ignored
+        assert!(find_oov_spans("<oov>abc").is_empty());
+    }
+
+    #[test]
+    fn test_align_dual() {
+        let pieces = [
+            Piece::Char("今".into()),
+            Piece::Char("天".into()),
+            Piece::Entity("二零二四年".into()),
+            Piece::Char("好".into()),
+        ];
+        let spans = align_dual("今天2024年好", &pieces, "");
+        assert_eq!(
+            spans,
+            vec![
+                DualSpan::unchanged("今天"),
+                DualSpan::normalized("2024年", "二零二四年"),
+                DualSpan::unchanged("好"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_ambiguities() {
+        let readings = ["11-12个".to_string(), "十一、二个".to_string()];
+        let ambiguities = find_ambiguities(&readings);
+        assert_eq!(
+            ambiguities,
+            vec![Ambiguity {
+                text: "11-12".to_string(),
+                start: 0,
+                end: 5,
+                alternatives: vec!["十一、二".to_string()],
+            }]
+        );
+
+        let readings = [
+            "共5人".to_string(),
+            "共五人".to_string(),
+            "共伍人".to_string(),
+        ];
+        let ambiguities = find_ambiguities(&readings);
+        assert_eq!(ambiguities.len(), 1);
+        assert_eq!(ambiguities[0].text, "5");
+        assert_eq!(ambiguities[0].alternatives, vec!["五", "伍"]);
+        assert!(find_ambiguities(&readings[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_label_spans() {
+        let spans = vec![
+            DualSpan::unchanged("共"),
+            DualSpan::normalized("100元", "一百元"),
+            DualSpan::unchanged("，"),
+            DualSpan::normalized("3点5", "三点五"),
+        ];
+        let runs = vec![
+            vec!["money".to_string()],
+            vec!["cardinal".to_string(), "decimal".to_string()],
+        ];
+        let segments = label_spans(spans, &runs);
+        assert_eq!(segments[0].category, None);
+        assert_eq!(segments[0].original, "共");
+        assert_eq!(segments[1].category.as_deref(), Some("money"));
+        assert_eq!(segments[1].text, "一百元");
+        assert_eq!(segments[1].original, "100元");
+        assert_eq!(segments[3].category.as_deref(), Some("cardinal+decimal"));
+
+        // Fallback span carries every type
+        let segments = label_spans(vec![DualSpan::normalized("100元", "一百元")], &runs);
+        assert_eq!(
+            segments[0].category.as_deref(),
+            Some("money+cardinal+decimal")
+        );
+    }
+
+    #[test]
+    fn test_align_dual_with_spaces() {
+        let pieces = [
+            Piece::Char("I".into()),
+            Piece::Char("have".into()),
+            Piece::Entity("one hundred dollars".into()),
+        ];
+        let spans = align_dual("I have $100", &pieces, " ");
+        let written: String = spans.iter().map(|s| s.written.as_str()).collect();
+        assert_eq!(written, "I have $100");
+        assert_eq!(
+            spans.last().unwrap(),
+            &DualSpan::normalized("$100", "one hundred dollars")
+        );
+    }
+
+    #[test]
+    fn test_annotate() {
+        let spans = vec![
+            DualSpan::unchanged("今天是"),
+            DualSpan::normalized("2024年", "二零二四年"),
+        ];
+        assert_eq!(
+            annotate(&spans, &Annotation::default()),
+            "今天是[[2024年→二零二四年]]"
+        );
+        assert_eq!(
+            annotate(&spans, &Annotation::Json),
+            r#"今天是{"written":"2024年","spoken":"二零二四年"}"#
+        );
+    }
+}
@@ -0,0 +1,647 @@
+//! Token rewrites applied between tagging and reordering
+//!
+//! Reading preferences that the FSTs don't expose are applied by editing the
+//! tagged tokens before they reach the verbalizer.
+
+use crate::config::{
+    DecimalReading, Language, MoneyReading, NormalizerConfig, NumberScale, Operator, TimeFormat,
+    YearStyle,
+};
+use crate::numbers::{
+    cjk_digit, digit_word, en_indian_cardinal, num_to_words, words_to_num, zh_zhao_cardinal, Style,
+};
+use crate::token_parser::Token;
+
+/// Whether any rewrite is enabled in `config`
+pub(crate) fn is_active(config: &NormalizerConfig) -> bool {
+    config.operator == Operator::Tn
+        && (config.time_format != TimeFormat::AsTagged
+            || config.year_style != YearStyle::AsTagged
+            || config.decimal_reading != DecimalReading::AsTagged
+            || config.decimal_separator.is_some()
+            || config.money_reading != MoneyReading::AsTagged
+            || config.number_scale != NumberScale::AsTagged)
+}
+
+/// Apply the enabled rewrites to `tokens`
+pub(crate) fn rewrite_tokens(tokens: &mut [Token], lang: Language, config: &NormalizerConfig) {
+    for token in tokens {
+        if config.number_scale != NumberScale::AsTagged
+            && matches!(
+                token.name.as_str(),
+                "cardinal" | "decimal" | "money" | "measure"
+            )
+        {
+            rewrite_scale(token, lang, config.number_scale);
+        }
+        match token.name.as_str() {
+            "time" => rewrite_time(token, lang, config.time_format),
+            "date" => rewrite_year(token, lang, config.year_style),
+            "money" if config.money_reading != MoneyReading::AsTagged => {
+                rewrite_money(token, lang, config.money_reading)
+            }
+            "cardinal" | "decimal" | "money" | "measure" => rewrite_decimal(
+                token,
+                lang,
+                config.decimal_reading,
+                config.decimal_separator.as_deref(),
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Read a numeric field, remembering whether it was written in digits
+fn read_number(value: &str, lang: Language) -> Option<(i64, bool)> {
+    let value = value.trim();
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+        return value.parse().ok().map(|n| (n, true));
+    }
+    words_to_num(value, lang)
+        .ok()
+        .and_then(|n| n.as_i64())
+        .map(|n| (n, false))
+}
+
+/// Write a number back in the form it was read in
+fn write_number(n: i64, digits: bool, lang: Language) -> String {
+    if digits {
+        n.to_string()
+    } else {
+        num_to_words(n, lang, Style::Cardinal).unwrap_or_else(|_| n.to_string())
+    }
+}
+
+/// Convert a time token between 12-hour and 24-hour readings
+///
+/// Chinese and Japanese tokens carry the period of day in `noon` and the
+/// hour in `hour`; English tokens use `suffix` ("a m"/"p m") and `hours`.
+fn rewrite_time(token: &mut Token, lang: Language, format: TimeFormat) {
+    let (hour_key, period_key) = match lang {
+        Language::En => ("hours", "suffix"),
+        _ => ("hour", "noon"),
+    };
+    let Some((hour, digits)) = token.get(hour_key).and_then(|h| read_number(h, lang)) else {
+        return;
+    };
+    if !(0..=24).contains(&hour) {
+        return;
+    }
+
+    match format {
+        TimeFormat::AsTagged => {}
+        TimeFormat::Hour12 => {
+            if token.get(period_key).is_some() {
+                return;
+            }
+            let (period, hour) = period_of_day(hour % 24, lang);
+            token.set(hour_key, &write_number(hour, digits, lang));
+            token.set(period_key, period);
+        }
+        TimeFormat::Hour24 => {
+            let Some(period) = token.remove(period_key) else {
+                return;
+            };
+            let hour = match period_half(&period) {
+                Some(Half::Pm) if hour < 12 => hour + 12,
+                Some(Half::Am) if hour == 12 => 0,
+                _ => hour,
+            };
+            token.set(hour_key, &write_number(hour, digits, lang));
+        }
+    }
+}
+
+/// Re-read the `year` field of a date token in the given style
+fn rewrite_year(token: &mut Token, lang: Language, style: YearStyle) {
+    if style == YearStyle::AsTagged {
+        return;
+    }
+    let Some(value) = token.get("year") else {
+        return;
+    };
+    let year = match lang {
+        Language::En => read_en_year(value),
+        _ => read_number(value, lang).map(|(n, _)| n),
+    };
+    let Some(year) = year.filter(|y| *y >= 0) else {
+        return;
+    };
+
+    let spoken = match (lang, style) {
+        (Language::En, YearStyle::Digits) => en_paired_year(year),
+        (_, YearStyle::Digits) => num_to_words(year, lang, Style::Digits).ok(),
+        (Language::Zh | Language::Auto, _) => {
+            // Colloquial 两 for a leading 2 in the thousands place
+            num_to_words(year, lang, Style::Cardinal)
+                .ok()
+                .map(|s| match s.strip_prefix("二千") {
+                    Some(rest) => format!("两千{}", rest),
+                    None => s,
+                })
+        }
+        _ => num_to_words(year, lang, Style::Cardinal).ok(),
+    };
+    if let Some(spoken) = spoken {
+        token.set("year", &spoken);
+    }
+}
+
+/// Read an English year in digits, as a cardinal, or in pairs ("nineteen oh five")
+pub(crate) fn read_en_year(value: &str) -> Option<i64> {
+    if let Some((n, _)) = read_number(value, Language::En) {
+        if n >= 100 || value.trim().chars().all(|c| c.is_ascii_digit()) {
+            return Some(n);
+        }
+    }
+    let words: Vec<&str> = value
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|w| !w.is_empty())
+        .collect();
+    (1..words.len()).find_map(|split| {
+        let high = words_to_num(&words[..split].join(" "), Language::En)
+            .ok()?
+            .as_i64()?;
+        let low = match &words[split..] {
+            ["hundred"] => 0,
+            ["oh", rest @ ..] => words_to_num(&rest.join(" "), Language::En).ok()?.as_i64()?,
+            rest => words_to_num(&rest.join(" "), Language::En).ok()?.as_i64()?,
+        };
+        ((10..100).contains(&high) && (0..100).contains(&low)).then_some(high * 100 + low)
+    })
+}
+
+/// English paired year reading: 1905 -> "nineteen oh five", 2024 -> "twenty twenty four"
+///
+/// Round thousands and years outside 1000-9999 are read as cardinals.
+fn en_paired_year(year: i64) -> Option<String> {
+    let cardinal = |n: i64| num_to_words(n, Language::En, Style::Cardinal).ok();
+    let (high, low) = (year / 100, year % 100);
+    if !(1000..10000).contains(&year) || year % 1000 == 0 {
+        return cardinal(year);
+    }
+    let low = match low {
+        0 => "hundred".to_string(),
+        1..=9 => format!("oh {}", cardinal(low)?),
+        _ => cardinal(low)?,
+    };
+    Some(format!("{} {}", cardinal(high)?, low))
+}
+
+/// Re-read the decimal parts of a numeric token
+///
+/// Chinese and Japanese tokens carry decimals inside a field value
+/// ("十二点五"); English tokens split them into `integer_part` and
+/// `fractional_part`. The English verbalizer inserts "point" itself, so a
+/// custom separator is read by folding the fraction into `integer_part`.
+fn rewrite_decimal(
+    token: &mut Token,
+    lang: Language,
+    reading: DecimalReading,
+    separator: Option<&str>,
+) {
+    if lang == Language::En {
+        let Some(fraction) = token.get("fractional_part") else {
+            return;
+        };
+        let digits: Option<String> = fraction
+            .split_whitespace()
+            .map(|w| match w {
+                "oh" | "o" => Some('0'),
+                _ => words_to_num(w, lang)
+                    .ok()?
+                    .as_i64()
+                    .and_then(|d| char::from_digit(u32::try_from(d).ok()?, 10)),
+            })
+            .collect();
+        let fraction = match digits {
+            Some(digits) if !digits.is_empty() => read_fraction(&digits, lang, reading),
+            _ => None,
+        }
+        .unwrap_or_else(|| fraction.to_string());
+
+        match separator {
+            Some(separator) => {
+                let integer = token.get("integer_part").unwrap_or_default();
+                let spoken = format!("{} {} {}", integer, separator, fraction);
+                token.remove("fractional_part");
+                token.set("integer_part", spoken.trim());
+            }
+            None => token.set("fractional_part", &fraction),
+        }
+        return;
+    }
+
+    for key in token.order.clone() {
+        let Some((integer, fraction)) = token.get(&key).and_then(|v| v.split_once(['点', '點']))
+        else {
+            continue;
+        };
+        let digits: Option<String> = fraction
+            .chars()
+            .map(|c| cjk_digit(c).and_then(|d| char::from_digit(d as u32, 10)))
+            .collect();
+        let Some(digits) = digits.filter(|d| !d.is_empty()) else {
+            continue;
+        };
+        let fraction =
+            read_fraction(&digits, lang, reading).unwrap_or_else(|| fraction.to_string());
+        let spoken = format!("{}{}{}", integer, separator.unwrap_or("点"), fraction);
+        token.set(&key, &spoken);
+    }
+}
+
+/// Reread the integer part of numeric fields with other scale words
+///
+/// Only fields the tagger has already read out are rewritten; digits are
+/// left to the verbalizer. Indian scales apply to English and 兆 to
+/// Chinese, from 10^5 and 10^12 respectively.
+fn rewrite_scale(token: &mut Token, lang: Language, scale: NumberScale) {
+    let (write, min): (fn(u128) -> String, i64) = match (scale, lang) {
+        (NumberScale::Indian, Language::En) => (en_indian_cardinal, 100_000),
+        (NumberScale::Zhao, Language::Zh) => (zh_zhao_cardinal, 1_000_000_000_000),
+        _ => return,
+    };
+    for key in token.order.clone() {
+        let Some(value) = token.get(&key) else {
+            continue;
+        };
+        // Chinese fields hold the whole decimal ("一万亿点五"), English
+        // ones only the integer part
+        let (integer, fraction) = match value.split_once(['点', '點']) {
+            Some((integer, fraction)) if lang != Language::En => (integer, Some(fraction)),
+            _ => (value, None),
+        };
+        let (sign, integer) = match integer.strip_prefix('负') {
+            Some(rest) => ("负", rest),
+            None => ("", integer),
+        };
+        if integer.is_empty() || integer.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        let Some(n) = words_to_num(integer, lang).ok().and_then(|n| n.as_i64()) else {
+            continue;
+        };
+        if n < min {
+            continue;
+        }
+        let mut spoken = format!("{}{}", sign, write(n as u128));
+        if let Some(fraction) = fraction {
+            spoken.push('点');
+            spoken.push_str(fraction);
+        }
+        token.set(&key, &spoken);
+    }
+}
+
+/// Minor unit of a major currency unit
+fn minor_unit(major: &str, plural: bool) -> Option<&'static str> {
+    let unit = match (major.trim(), plural) {
+        ("dollar" | "dollars" | "euro" | "euros", false) => "cent",
+        ("dollar" | "dollars" | "euro" | "euros", true) => "cents",
+        ("pound" | "pounds", false) => "penny",
+        ("pound" | "pounds", true) => "pence",
+        _ => return None,
+    };
+    Some(unit)
+}
+
+/// Fractional digits of an English `fractional_part` ("five", "five zero", "fifty")
+fn en_fraction_digits(fraction: &str) -> Option<String> {
+    let words: Vec<&str> = fraction.split_whitespace().collect();
+    let digit = |w: &str| match w {
+        "oh" | "o" => Some(0),
+        _ => words_to_num(w, Language::En)
+            .ok()?
+            .as_i64()
+            .filter(|d| *d < 10),
+    };
+    if let Some(digits) = words.iter().map(|w| digit(w)).collect::<Option<Vec<i64>>>() {
+        return Some(digits.iter().map(|d| d.to_string()).collect());
+    }
+    let value = words_to_num(fraction, Language::En).ok()?.as_i64()?;
+    (10..100).contains(&value).then(|| value.to_string())
+}
+
+/// Read money either in minor units or as a plain decimal
+///
+/// The stock verbalizers copy the value and currency fields through, so the
+/// minor units are folded into the currency field ("元五角",
+/// "dollars and fifty cents").
+fn rewrite_money(token: &mut Token, lang: Language, reading: MoneyReading) {
+    if lang == Language::En {
+        let (Some(fraction), Some(major)) =
+            (token.get("fractional_part"), token.get("currency_maj"))
+        else {
+            return;
+        };
+        if token.get("quantity").is_some() {
+            return;
+        }
+        let Some(digits) = en_fraction_digits(fraction) else {
+            return;
+        };
+        let integer = token.get("integer_part").unwrap_or("zero").to_string();
+        let major = major.to_string();
+
+        if reading == MoneyReading::Decimal {
+            let Ok(spoken) = num_to_words(&digits, lang, Style::Digits) else {
+                return;
+            };
+            token.remove("fractional_part");
+            token.remove("currency_min");
+            token.set("integer_part", &format!("{} point {}", integer, spoken));
+            return;
+        }
+
+        if digits.len() > 2 {
+            return;
+        }
+        let cents: i64 = format!("{:0<2}", digits).parse().unwrap_or(0);
+        let minor = match token.get("currency_min") {
+            Some(minor) => minor.to_string(),
+            None => match minor_unit(&major, cents != 1) {
+                Some(minor) => minor.to_string(),
+                None => return,
+            },
+        };
+        let Ok(cents_words) = num_to_words(cents, lang, Style::Cardinal) else {
+            return;
+        };
+        token.remove("fractional_part");
+        token.remove("currency_min");
+        if cents == 0 {
+            return;
+        }
+        if integer == "zero" {
+            token.set("integer_part", &cents_words);
+            token.set("currency_maj", &minor);
+        } else {
+            token.set("integer_part", &integer);
+            token.set(
+                "currency_maj",
+                &format!("{} and {} {}", major, cents_words, minor),
+            );
+        }
+        return;
+    }
+
+    // Chinese and Japanese values already read as decimals ("十二点五元")
+    if reading != MoneyReading::MinorUnits {
+        return;
+    }
+    let (Some(value), Some(currency)) = (token.get("value"), token.get("currency")) else {
+        return;
+    };
+    let jiao_unit = match currency {
+        "元" => "角",
+        "块" => "毛",
+        _ => return,
+    };
+    let Some((integer, fraction)) = value.split_once(['点', '點']) else {
+        return;
+    };
+    let digits: Option<Vec<i64>> = fraction.chars().map(cjk_digit).collect();
+    let Some(digits) = digits.filter(|d| !d.is_empty() && d.len() <= 2) else {
+        return;
+    };
+
+    let integer_zero = integer.is_empty() || integer == "零";
+    let mut minor = String::new();
+    let jiao = digits[0];
+    let fen = digits.get(1).copied().unwrap_or(0);
+    let word = |d: i64| digit_word(char::from_digit(d as u32, 10).unwrap_or('0'), lang);
+    if jiao > 0 {
+        minor.push_str(word(jiao));
+        minor.push_str(jiao_unit);
+    }
+    if fen > 0 {
+        if jiao == 0 && !integer_zero {
+            minor.push_str(word(0));
+        }
+        minor.push_str(word(fen));
+        minor.push('分');
+    }
+
+    if integer_zero {
+        if minor.is_empty() {
+            return;
+        }
+        token.set("value", &minor);
+        token.set("currency", "");
+    } else {
+        let currency = format!("{}{}", currency, minor);
+        let integer = integer.to_string();
+        token.set("value", &integer);
+        token.set("currency", &currency);
+    }
+}
+
+/// Read fractional digits in the given style; `None` keeps the tagged reading
+fn read_fraction(digits: &str, lang: Language, reading: DecimalReading) -> Option<String> {
+    match reading {
+        DecimalReading::AsTagged => None,
+        DecimalReading::Digits => num_to_words(digits, lang, Style::Digits).ok(),
+        DecimalReading::Grouped => {
+            let rest = digits.trim_start_matches('0');
+            if rest.is_empty() {
+                return num_to_words(digits, lang, Style::Digits).ok();
+            }
+            let mut words: Vec<String> = digits[..digits.len() - rest.len()]
+                .chars()
+                .map(|c| digit_word(c, lang).to_string())
+                .collect();
+            words.push(num_to_words(rest, lang, Style::Cardinal).ok()?);
+            Some(match lang {
+                Language::En => words.join(" "),
+                _ => words.concat(),
+            })
+        }
+    }
+}
+
+pub(crate) enum Half {
+    Am,
+    Pm,
+}
+
+/// Which half of the day a period word refers to
+pub(crate) fn period_half(period: &str) -> Option<Half> {
+    let period = period.to_lowercase().replace(['.', ' '], "");
+    match period.as_str() {
+        "am" | "午夜" | "凌晨" | "早上" | "上午" | "午前" => Some(Half::Am),
+        "pm" | "中午" | "下午" | "傍晚" | "晚上" | "午後" => Some(Half::Pm),
+        _ => None,
+    }
+}
+
+/// Period word and 12-hour clock hour for a 24-hour clock hour
+///
+/// Midnight and noon are read as 12 with their own period words in Chinese.
+fn period_of_day(hour: i64, lang: Language) -> (&'static str, i64) {
+    let hour12 = match hour % 12 {
+        0 if lang != Language::Ja => 12,
+        h => h,
+    };
+    let period = match lang {
+        Language::En if hour < 12 => "a m",
+        Language::En => "p m",
+        Language::Ja if hour < 12 => "午前",
+        Language::Ja => "午後",
+        _ => match hour {
+            0 => "午夜",
+            1..=5 => "凌晨",
+            6..=11 => "上午",
+            12 => "中午",
+            13..=17 => "下午",
+            _ => "晚上",
+        },
+    };
+    (period, hour12)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(fields: &[(&str, &str)]) -> Token {
+        let mut token = Token::new("time");
+        for (key, value) in fields {
+            token.append(key, value);
+        }
+        token
+    }
+
+    #[test]
+    fn test_time_hour12() {
+        let mut token = time(&[("hour", "十五"), ("minute", "三十")]);
+        rewrite_time(&mut token, Language::Zh, TimeFormat::Hour12);
+        assert_eq!(token.get("noon"), Some("下午"));
+        assert_eq!(token.get("hour"), Some("三"));
+
+        let mut token = time(&[("hour", "0"), ("minute", "05")]);
+        rewrite_time(&mut token, Language::Zh, TimeFormat::Hour12);
+        assert_eq!(token.get("noon"), Some("午夜"));
+        assert_eq!(token.get("hour"), Some("12"));
+
+        let mut token = time(&[("hours", "twelve"), ("minutes", "thirty")]);
+        rewrite_time(&mut token, Language::En, TimeFormat::Hour12);
+        assert_eq!(token.get("hours"), Some("twelve"));
+        assert_eq!(token.get("suffix"), Some("p m"));
+    }
+
+    #[test]
+    fn test_time_hour24() {
+        let mut token = time(&[("hours", "three"), ("minutes", "thirty"), ("suffix", "p m")]);
+        rewrite_time(&mut token, Language::En, TimeFormat::Hour24);
+        assert_eq!(token.get("hours"), Some("fifteen"));
+        assert_eq!(token.get("suffix"), None);
+
+        let mut token = time(&[("noon", "午夜"), ("hour", "十二")]);
+        rewrite_time(&mut token, Language::Zh, TimeFormat::Hour24);
+        assert_eq!(token.get("hour"), Some("零"));
+        assert_eq!(token.order, vec!["hour"]);
+    }
+
+    #[test]
+    fn test_year_style() {
+        let mut token = Token::new("date");
+        token.append("year", "二零二四");
+        rewrite_year(&mut token, Language::Zh, YearStyle::Cardinal);
+        assert_eq!(token.get("year"), Some("两千零二十四"));
+        rewrite_year(&mut token, Language::Zh, YearStyle::Digits);
+        assert_eq!(token.get("year"), Some("二零二四"));
+
+        let mut token = Token::new("date");
+        token.append("year", "twenty twenty four");
+        rewrite_year(&mut token, Language::En, YearStyle::Cardinal);
+        assert_eq!(token.get("year"), Some("two thousand twenty four"));
+        rewrite_year(&mut token, Language::En, YearStyle::Digits);
+        assert_eq!(token.get("year"), Some("twenty twenty four"));
+
+        assert_eq!(en_paired_year(1905).as_deref(), Some("nineteen oh five"));
+        assert_eq!(read_en_year("nineteen oh five"), Some(1905));
+        assert_eq!(en_paired_year(2000).as_deref(), Some("two thousand"));
+    }
+
+    #[test]
+    fn test_decimal_reading() {
+        let mut token = Token::new("money");
+        token.append("value", "十二点五零");
+        token.append("currency", "元");
+        rewrite_decimal(&mut token, Language::Zh, DecimalReading::Grouped, None);
+        assert_eq!(token.get("value"), Some("十二点五十"));
+        token.set("value", "十二点五零");
+        rewrite_decimal(&mut token, Language::Zh, DecimalReading::Digits, Some("點"));
+        assert_eq!(token.get("value"), Some("十二點五零"));
+        assert_eq!(token.get("currency"), Some("元"));
+
+        let mut token = Token::new("decimal");
+        token.append("integer_part", "three");
+        token.append("fractional_part", "zero five");
+        rewrite_decimal(&mut token, Language::En, DecimalReading::Grouped, None);
+        assert_eq!(token.get("fractional_part"), Some("zero five"));
+        rewrite_decimal(
+            &mut token,
+            Language::En,
+            DecimalReading::AsTagged,
+            Some("dot"),
+        );
+        assert_eq!(token.get("integer_part"), Some("three dot zero five"));
+        assert_eq!(token.get("fractional_part"), None);
+    }
+
+    #[test]
+    fn test_number_scale() {
+        let mut token = Token::new("cardinal");
+        token.append("integer", "one million two hundred thirty four thousand");
+        rewrite_scale(&mut token, Language::En, NumberScale::Indian);
+        assert_eq!(
+            token.get("integer"),
+            Some("twelve lakh thirty four thousand")
+        );
+
+        let mut token = Token::new("money");
+        token.append("value", "一万五千亿点五");
+        token.append("currency", "元");
+        rewrite_scale(&mut token, Language::Zh, NumberScale::Zhao);
+        assert_eq!(token.get("value"), Some("一兆五千亿点五"));
+        assert_eq!(token.get("currency"), Some("元"));
+
+        // Small numbers and other languages keep their reading
+        let mut token = Token::new("cardinal");
+        token.append("value", "五万");
+        rewrite_scale(&mut token, Language::Zh, NumberScale::Zhao);
+        rewrite_scale(&mut token, Language::Zh, NumberScale::Indian);
+        assert_eq!(token.get("value"), Some("五万"));
+    }
+
+    #[test]
+    fn test_money_minor_units() {
+        let mut token = Token::new("money");
+        token.append("value", "十二点五");
+        token.append("currency", "元");
+        rewrite_money(&mut token, Language::Zh, MoneyReading::MinorUnits);
+        assert_eq!(token.get("value"), Some("十二"));
+        assert_eq!(token.get("currency"), Some("元五角"));
+
+        let mut token = Token::new("money");
+        token.append("value", "三点零五");
+        token.append("currency", "元");
+        rewrite_money(&mut token, Language::Zh, MoneyReading::MinorUnits);
+        assert_eq!(token.get("currency"), Some("元零五分"));
+
+        let mut token = Token::new("money");
+        token.append("integer_part", "twelve");
+        token.append("fractional_part", "five");
+        token.append("currency_maj", "dollars");
+        let mut decimal = token.clone();
+        rewrite_money(&mut token, Language::En, MoneyReading::MinorUnits);
+        assert_eq!(token.get("integer_part"), Some("twelve"));
+        assert_eq!(token.get("currency_maj"), Some("dollars and fifty cents"));
+        assert_eq!(token.get("fractional_part"), None);
+
+        rewrite_money(&mut decimal, Language::En, MoneyReading::Decimal);
+        assert_eq!(decimal.get("integer_part"), Some("twelve point five"));
+    }
+}
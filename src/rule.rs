@@ -0,0 +1,329 @@
+//! Rule builder for constructing transducers
+//!
+//! This module provides a small, pynini-like builder API for writing rewrite
+//! rules in Rust. Rules are compiled to FSTs over UTF-8 byte labels, the same
+//! encoding used by the WeText FSTs, so they compose with the rest of the
+//! pipeline.
+//!
+//! # Example
+//! ```rust
+//! use wetext_rs::Rule;
+//!
+//! let rule = Rule::union([
+//!     Rule::replace("kg", "千克").insensitive(),
+//!     Rule::replace("km", "千米").insensitive(),
+//! ]);
+//! let rewriter = rule.to_rewriter().unwrap();
+//! assert_eq!(rewriter.normalize("5KG 3km").unwrap(), "5千克 3千米");
+//! ```
+
+use rustfst::algorithms::closure::{closure, ClosureType};
+use rustfst::algorithms::concat::concat;
+use rustfst::algorithms::tr_compares::ILabelCompare;
+use rustfst::algorithms::tr_sort;
+use rustfst::algorithms::union::union;
+use rustfst::fst_impls::VectorFst;
+use rustfst::prelude::*;
+use rustfst::semirings::TropicalWeight;
+use rustfst::{Label, EPS_LABEL};
+
+use crate::error::{Result, WeTextError};
+use crate::text_normalizer::FstTextNormalizer;
+
+/// Cost of copying a single input byte unchanged in a rewriter
+///
+/// Rule matches are preferred over copying as long as the rule weight is
+/// lower than this cost times the number of bytes it consumes.
+const PASSTHROUGH_WEIGHT: f32 = 1.0;
+
+/// Rule structure
+#[derive(Debug, Clone)]
+enum RuleKind {
+    /// Map an input string to an output string
+    Replace { from: String, to: String },
+    /// Any one of the rules
+    Union(Vec<Rule>),
+    /// The rules one after another
+    Concat(Vec<Rule>),
+    /// Kleene closure of a rule
+    Closure(Box<Rule>, ClosureType),
+    /// A rule or nothing
+    Optional(Box<Rule>),
+}
+
+/// A composable rewrite rule
+///
+/// Rules are built with constructors such as [`Rule::replace`] and combined
+/// with [`Rule::union`], [`Rule::concat`] and closure operators. They are
+/// compiled lazily with [`Rule::to_fst`] or [`Rule::to_rewriter`].
+#[derive(Debug, Clone)]
+pub struct Rule {
+    kind: RuleKind,
+    insensitive: bool,
+    weight: f32,
+}
+
+impl Rule {
+    fn from_kind(kind: RuleKind) -> Self {
+        Self {
+            kind,
+            insensitive: false,
+            weight: 0.0,
+        }
+    }
+
+    /// Rewrite `from` to `to`
+    pub fn replace(from: &str, to: &str) -> Self {
+        Self::from_kind(RuleKind::Replace {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    /// Accept `text` and output it unchanged
+    pub fn accept(text: &str) -> Self {
+        Self::replace(text, text)
+    }
+
+    /// Accept `text` and output nothing
+    pub fn delete(text: &str) -> Self {
+        Self::replace(text, "")
+    }
+
+    /// Consume nothing and output `text`
+    pub fn insert(text: &str) -> Self {
+        Self::replace("", text)
+    }
+
+    /// Match any one of `rules`
+    pub fn union<I: IntoIterator<Item = Rule>>(rules: I) -> Self {
+        Self::from_kind(RuleKind::Union(rules.into_iter().collect()))
+    }
+
+    /// Match `rules` one after another
+    pub fn concat<I: IntoIterator<Item = Rule>>(rules: I) -> Self {
+        Self::from_kind(RuleKind::Concat(rules.into_iter().collect()))
+    }
+
+    /// Match either this rule or `other`
+    pub fn or(self, other: Rule) -> Self {
+        Self::union([self, other])
+    }
+
+    /// Match this rule followed by `other`
+    pub fn then(self, other: Rule) -> Self {
+        Self::concat([self, other])
+    }
+
+    /// Match this rule zero or more times
+    pub fn star(self) -> Self {
+        Self::from_kind(RuleKind::Closure(Box::new(self), ClosureType::ClosureStar))
+    }
+
+    /// Match this rule one or more times
+    pub fn plus(self) -> Self {
+        Self::from_kind(RuleKind::Closure(Box::new(self), ClosureType::ClosurePlus))
+    }
+
+    /// Match this rule or nothing
+    pub fn optional(self) -> Self {
+        Self::from_kind(RuleKind::Optional(Box::new(self)))
+    }
+
+    /// Match ASCII letters in the input regardless of case
+    ///
+    /// Applies to this rule and every rule nested inside it. Output is unchanged.
+    pub fn insensitive(mut self) -> Self {
+        self.insensitive = true;
+        self
+    }
+
+    /// Set the weight (cost) of this rule
+    ///
+    /// Lower weights are preferred when several rules match the same input.
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Compile the rule to an FST matching exactly the rule's input language
+    pub fn to_fst(&self) -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = self.compile(false)?;
+        tr_sort(&mut fst, ILabelCompare {});
+        Ok(fst)
+    }
+
+    /// Compile the rule to a normalizer that rewrites every match in a text
+    ///
+    /// Text not matched by the rule is copied through unchanged, which makes
+    /// the result usable as an extra pipeline stage.
+    pub fn to_rewriter(&self) -> Result<FstTextNormalizer> {
+        let mut fst = self.compile(false)?;
+        union(&mut fst, &passthrough_fst()?).map_err(op_error)?;
+        closure(&mut fst, ClosureType::ClosureStar);
+        tr_sort(&mut fst, ILabelCompare {});
+        Ok(FstTextNormalizer::from_fst(fst))
+    }
+
+    fn compile(&self, insensitive: bool) -> Result<VectorFst<TropicalWeight>> {
+        let insensitive = insensitive || self.insensitive;
+        let mut fst = match &self.kind {
+            RuleKind::Replace { from, to } => string_fst(from, to, insensitive)?,
+            RuleKind::Union(rules) => {
+                let mut fst = empty_fst();
+                for rule in rules {
+                    union(&mut fst, &rule.compile(insensitive)?).map_err(op_error)?;
+                }
+                fst
+            }
+            RuleKind::Concat(rules) => {
+                let mut fst = string_fst("", "", false)?;
+                for rule in rules {
+                    concat(&mut fst, &rule.compile(insensitive)?).map_err(op_error)?;
+                }
+                fst
+            }
+            RuleKind::Closure(rule, closure_type) => {
+                let mut fst = rule.compile(insensitive)?;
+                closure(&mut fst, *closure_type);
+                fst
+            }
+            RuleKind::Optional(rule) => {
+                let mut fst = rule.compile(insensitive)?;
+                union(&mut fst, &string_fst("", "", false)?).map_err(op_error)?;
+                fst
+            }
+        };
+
+        if self.weight != 0.0 {
+            let finals: Vec<StateId> = fst.final_states_iter().collect();
+            for state in finals {
+                let weight = fst.final_weight(state).map_err(op_error)?.unwrap();
+                let weight = weight
+                    .times(TropicalWeight::new(self.weight))
+                    .map_err(op_error)?;
+                fst.set_final(state, weight).map_err(op_error)?;
+            }
+        }
+
+        Ok(fst)
+    }
+}
+
+fn op_error(e: anyhow::Error) -> WeTextError {
+    WeTextError::FstOperationError(e.to_string())
+}
+
+/// FST accepting nothing
+fn empty_fst() -> VectorFst<TropicalWeight> {
+    VectorFst::new()
+}
+
+/// Linear FST mapping the bytes of `from` to the bytes of `to`
+fn string_fst(from: &str, to: &str, insensitive: bool) -> Result<VectorFst<TropicalWeight>> {
+    let from = from.as_bytes();
+    let to = to.as_bytes();
+    let len = from.len().max(to.len());
+
+    let mut fst = VectorFst::<TropicalWeight>::new();
+    let mut state = fst.add_state();
+    fst.set_start(state).map_err(op_error)?;
+
+    for i in 0..len {
+        let ilabel = from.get(i).map_or(EPS_LABEL, |&b| b as Label);
+        let olabel = to.get(i).map_or(EPS_LABEL, |&b| b as Label);
+        let next = fst.add_state();
+        fst.add_tr(state, Tr::new(ilabel, olabel, TropicalWeight::one(), next))
+            .map_err(op_error)?;
+        if insensitive {
+            if let Some(&b) = from.get(i) {
+                let swapped = swap_ascii_case(b);
+                if swapped != b {
+                    fst.add_tr(
+                        state,
+                        Tr::new(swapped as Label, olabel, TropicalWeight::one(), next),
+                    )
+                    .map_err(op_error)?;
+                }
+            }
+        }
+        state = next;
+    }
+
+    fst.set_final(state, TropicalWeight::one())
+        .map_err(op_error)?;
+    Ok(fst)
+}
+
+/// FST copying a single byte unchanged at `PASSTHROUGH_WEIGHT` cost
+fn passthrough_fst() -> Result<VectorFst<TropicalWeight>> {
+    let mut fst = VectorFst::<TropicalWeight>::new();
+    let start = fst.add_state();
+    let end = fst.add_state();
+    fst.set_start(start).map_err(op_error)?;
+    fst.set_final(end, TropicalWeight::one())
+        .map_err(op_error)?;
+    for byte in 1..=255u8 {
+        fst.add_tr(
+            start,
+            Tr::new(
+                byte as Label,
+                byte as Label,
+                TropicalWeight::new(PASSTHROUGH_WEIGHT),
+                end,
+            ),
+        )
+        .map_err(op_error)?;
+    }
+    Ok(fst)
+}
+
+fn swap_ascii_case(b: u8) -> u8 {
+    if b.is_ascii_lowercase() {
+        b.to_ascii_uppercase()
+    } else {
+        b.to_ascii_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_rewriter() {
+        let rewriter = Rule::replace("kg", "千克").to_rewriter().unwrap();
+        assert_eq!(rewriter.normalize("5kg").unwrap(), "5千克");
+        assert_eq!(rewriter.normalize("5KG").unwrap(), "5KG");
+    }
+
+    #[test]
+    fn test_insensitive() {
+        let rewriter = Rule::replace("kg", "千克")
+            .insensitive()
+            .to_rewriter()
+            .unwrap();
+        assert_eq!(rewriter.normalize("5Kg").unwrap(), "5千克");
+    }
+
+    #[test]
+    fn test_weight_prefers_cheaper_rule() {
+        let rule = Rule::replace("ms", "毫秒")
+            .weight(0.5)
+            .or(Rule::replace("ms", "女士").weight(1.0));
+        let rewriter = rule.to_rewriter().unwrap();
+        assert_eq!(rewriter.normalize("10ms").unwrap(), "10毫秒");
+    }
+
+    #[test]
+    fn test_concat_and_closure() {
+        let digits = Rule::union(["1", "2", "3"].map(Rule::accept)).plus();
+        let rule = digits
+            .then(Rule::delete(" ").optional())
+            .then(Rule::replace("%", "percent"));
+        let fst = rule.to_fst().unwrap();
+        let normalizer = FstTextNormalizer::from_fst(fst);
+        assert_eq!(normalizer.normalize("12 %").unwrap(), "12percent");
+        assert_eq!(normalizer.normalize("321%").unwrap(), "321percent");
+    }
+}
@@ -0,0 +1,410 @@
+//! Phonetic output scripts for verbalized Chinese and Japanese
+//!
+//! Verbalized numbers, dates, times, money and measures use a small set of
+//! characters, so they are transcribed with fixed reading tables rather
+//! than a full dictionary. Characters outside the tables are kept as they
+//! are. Tone sandhi (e.g., 一 before a fourth tone) is not applied.
+//! Japanese numbers are read with their sound changes (三百 "sanbyaku",
+//! 四月 "shigatsu", 一日 "tsuitachi").
+
+use crate::config::{Language, OutputScript};
+
+/// Readings as (character, pinyin with tone number, zhuyin, Cantonese jyutping)
+const READINGS: &[(char, &str, &str, &str)] = &[
+    ('零', "ling2", "ㄌㄧㄥˊ", "ling4"),
+    ('〇', "ling2", "ㄌㄧㄥˊ", "ling4"),
+    ('一', "yi1", "ㄧ", "jat1"),
+    ('幺', "yao1", "ㄧㄠ", "jiu1"),
+    ('二', "er4", "ㄦˋ", "ji6"),
+    ('两', "liang3", "ㄌㄧㄤˇ", "loeng5"),
+    ('兩', "liang3", "ㄌㄧㄤˇ", "loeng5"),
+    ('三', "san1", "ㄙㄢ", "saam1"),
+    ('四', "si4", "ㄙˋ", "sei3"),
+    ('五', "wu3", "ㄨˇ", "ng5"),
+    ('六', "liu4", "ㄌㄧㄡˋ", "luk6"),
+    ('七', "qi1", "ㄑㄧ", "cat1"),
+    ('八', "ba1", "ㄅㄚ", "baat3"),
+    ('九', "jiu3", "ㄐㄧㄡˇ", "gau2"),
+    ('十', "shi2", "ㄕˊ", "sap6"),
+    ('百', "bai3", "ㄅㄞˇ", "baak3"),
+    ('千', "qian1", "ㄑㄧㄢ", "cin1"),
+    ('万', "wan4", "ㄨㄢˋ", "maan6"),
+    ('萬', "wan4", "ㄨㄢˋ", "maan6"),
+    ('亿', "yi4", "ㄧˋ", "jik1"),
+    ('億', "yi4", "ㄧˋ", "jik1"),
+    ('点', "dian3", "ㄉㄧㄢˇ", "dim2"),
+    ('點', "dian3", "ㄉㄧㄢˇ", "dim2"),
+    ('负', "fu4", "ㄈㄨˋ", "fu6"),
+    ('負', "fu4", "ㄈㄨˋ", "fu6"),
+    ('正', "zheng4", "ㄓㄥˋ", "zing3"),
+    ('第', "di4", "ㄉㄧˋ", "dai6"),
+    ('分', "fen1", "ㄈㄣ", "fan1"),
+    ('之', "zhi1", "ㄓ", "zi1"),
+    ('半', "ban4", "ㄅㄢˋ", "bun3"),
+    ('年', "nian2", "ㄋㄧㄢˊ", "nin4"),
+    ('月', "yue4", "ㄩㄝˋ", "jyut6"),
+    ('日', "ri4", "ㄖˋ", "jat6"),
+    ('号', "hao4", "ㄏㄠˋ", "hou6"),
+    ('號', "hao4", "ㄏㄠˋ", "hou6"),
+    ('时', "shi2", "ㄕˊ", "si4"),
+    ('時', "shi2", "ㄕˊ", "si4"),
+    ('秒', "miao3", "ㄇㄧㄠˇ", "miu5"),
+    ('钟', "zhong1", "ㄓㄨㄥ", "zung1"),
+    ('鐘', "zhong1", "ㄓㄨㄥ", "zung1"),
+    ('上', "shang4", "ㄕㄤˋ", "soeng6"),
+    ('下', "xia4", "ㄒㄧㄚˋ", "haa6"),
+    ('中', "zhong1", "ㄓㄨㄥ", "zung1"),
+    ('午', "wu3", "ㄨˇ", "ng5"),
+    ('夜', "ye4", "ㄧㄝˋ", "je6"),
+    ('凌', "ling2", "ㄌㄧㄥˊ", "ling4"),
+    ('晨', "chen2", "ㄔㄣˊ", "san4"),
+    ('早', "zao3", "ㄗㄠˇ", "zou2"),
+    ('晚', "wan3", "ㄨㄢˇ", "maan5"),
+    ('星', "xing1", "ㄒㄧㄥ", "sing1"),
+    ('期', "qi1", "ㄑㄧ", "kei4"),
+    ('周', "zhou1", "ㄓㄡ", "zau1"),
+    ('天', "tian1", "ㄊㄧㄢ", "tin1"),
+    ('元', "yuan2", "ㄩㄢˊ", "jyun4"),
+    ('圆', "yuan2", "ㄩㄢˊ", "jyun4"),
+    ('角', "jiao3", "ㄐㄧㄠˇ", "gok3"),
+    ('毛', "mao2", "ㄇㄠˊ", "mou4"),
+    ('块', "kuai4", "ㄎㄨㄞˋ", "faai3"),
+    ('塊', "kuai4", "ㄎㄨㄞˋ", "faai3"),
+    ('美', "mei3", "ㄇㄟˇ", "mei5"),
+    ('欧', "ou1", "ㄡ", "au1"),
+    ('歐', "ou1", "ㄡ", "au1"),
+    ('英', "ying1", "ㄧㄥ", "jing1"),
+    ('镑', "bang4", "ㄅㄤˋ", "bong6"),
+    ('鎊', "bang4", "ㄅㄤˋ", "bong6"),
+    ('港', "gang3", "ㄍㄤˇ", "gong2"),
+    ('币', "bi4", "ㄅㄧˋ", "bai6"),
+    ('幣', "bi4", "ㄅㄧˋ", "bai6"),
+    ('比', "bi3", "ㄅㄧˇ", "bei2"),
+    ('度', "du4", "ㄉㄨˋ", "dou6"),
+    ('米', "mi3", "ㄇㄧˇ", "mai5"),
+    ('公', "gong1", "ㄍㄨㄥ", "gung1"),
+    ('斤', "jin1", "ㄐㄧㄣ", "gan1"),
+    ('克', "ke4", "ㄎㄜˋ", "hak1"),
+    ('里', "li3", "ㄌㄧˇ", "lei5"),
+    ('厘', "li2", "ㄌㄧˊ", "lei4"),
+    ('毫', "hao2", "ㄏㄠˊ", "hou4"),
+    ('升', "sheng1", "ㄕㄥ", "sing1"),
+    ('至', "zhi4", "ㄓˋ", "zi3"),
+    ('到', "dao4", "ㄉㄠˋ", "dou3"),
+    ('加', "jia1", "ㄐㄧㄚ", "gaa1"),
+    ('减', "jian3", "ㄐㄧㄢˇ", "gaam2"),
+    ('乘', "cheng2", "ㄔㄥˊ", "sing4"),
+    ('除', "chu2", "ㄔㄨˊ", "ceoi4"),
+    ('以', "yi3", "ㄧˇ", "ji5"),
+    ('等', "deng3", "ㄉㄥˇ", "dang2"),
+    ('于', "yu2", "ㄩˊ", "jyu1"),
+    ('於', "yu2", "ㄩˊ", "jyu1"),
+    ('个', "ge4", "ㄍㄜˋ", "go3"),
+    ('個', "ge4", "ㄍㄜˋ", "go3"),
+    ('小', "xiao3", "ㄒㄧㄠˇ", "siu2"),
+];
+
+/// Transcribe verbalized text into `script`
+///
+/// Chinese scripts apply to Chinese text and romaji to Japanese text; other
+/// combinations leave the text unchanged. Syllables (or Japanese numbers
+/// with their counter) are separated by spaces; runs of characters without
+/// a reading are kept together.
+pub(crate) fn to_script(text: &str, script: OutputScript, lang: Language) -> String {
+    match (script, lang) {
+        (OutputScript::Hanzi, _) => text.to_string(),
+        (OutputScript::Romaji, Language::Ja) => ja_romaji(text),
+        (OutputScript::Romaji, _) | (_, Language::Ja | Language::En) => text.to_string(),
+        _ => zh_script(text, script),
+    }
+}
+
+fn zh_script(text: &str, script: OutputScript) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let mut run = String::new();
+    for c in text.chars() {
+        let reading = READINGS
+            .iter()
+            .find(|(h, _, _, _)| *h == c)
+            .map(|r| match script {
+                OutputScript::Bopomofo => r.2,
+                OutputScript::Jyutping => r.3,
+                _ => r.1,
+            });
+        match reading {
+            Some(reading) => {
+                if !run.trim().is_empty() {
+                    words.push(run.trim().to_string());
+                }
+                run.clear();
+                words.push(reading.to_string());
+            }
+            None => run.push(c),
+        }
+    }
+    if !run.trim().is_empty() {
+        words.push(run.trim().to_string());
+    }
+    words.join(" ")
+}
+
+const JA_DIGITS: [&str; 10] = [
+    "zero", "ichi", "ni", "san", "yon", "go", "roku", "nana", "hachi", "kyuu",
+];
+
+/// Days of the month with their own reading
+const JA_DAYS: &[(i64, &str)] = &[
+    (1, "tsuitachi"),
+    (2, "futsuka"),
+    (3, "mikka"),
+    (4, "yokka"),
+    (5, "itsuka"),
+    (6, "muika"),
+    (7, "nanoka"),
+    (8, "youka"),
+    (9, "kokonoka"),
+    (10, "tooka"),
+    (14, "juuyokka"),
+    (20, "hatsuka"),
+    (24, "nijuuyokka"),
+];
+
+fn ja_digit(c: char) -> Option<i64> {
+    match c {
+        '〇' | '零' => Some(0),
+        _ => crate::numbers::cjk_digit(c),
+    }
+}
+
+/// Romaji for verbalized Japanese numbers, dates and times
+fn ja_romaji(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut run = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        while i < chars.len()
+            && (ja_digit(chars[i]).is_some() || "十百千万億兆点".contains(chars[i]))
+        {
+            i += 1;
+        }
+        if i == start {
+            let rest: String = chars[i..].iter().collect();
+            if let Some((word, len)) = [("マイナス", "mainasu"), ("番目", "banme")]
+                .iter()
+                .find(|(kana, _)| rest.starts_with(kana))
+                .map(|(kana, word)| (*word, kana.chars().count()))
+            {
+                push_run(&mut words, &mut run);
+                words.push(word.to_string());
+                i += len;
+            } else {
+                run.push(chars[i]);
+                i += 1;
+            }
+            continue;
+        }
+
+        push_run(&mut words, &mut run);
+        let number: String = chars[start..i].iter().collect();
+        let (integer, fraction) = match number.split_once('点') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (number.as_str(), None),
+        };
+        let (mut reading, value) = ja_number(integer);
+
+        if let Some(fraction) = fraction {
+            let digits: Vec<&str> = fraction
+                .chars()
+                .filter_map(ja_digit)
+                .map(|d| JA_DIGITS[d as usize])
+                .collect();
+            reading = format!("{} ten {}", reading, digits.join(" "));
+        } else if let Some(&counter) = chars.get(i) {
+            if let Some(counted) = ja_counter(&reading, value, counter) {
+                reading = counted;
+                i += 1;
+            }
+        }
+        words.push(reading);
+    }
+    push_run(&mut words, &mut run);
+    words.join(" ")
+}
+
+fn push_run(words: &mut Vec<String>, run: &mut String) {
+    if !run.trim().is_empty() {
+        words.push(run.trim().to_string());
+    }
+    run.clear();
+}
+
+/// Read a Japanese number written with kanji, returning the reading and value
+///
+/// Runs without unit characters (e.g., the year 二〇二四) are read digit by
+/// digit.
+fn ja_number(number: &str) -> (String, i64) {
+    if number.chars().all(|c| ja_digit(c).is_some()) {
+        let digits: Vec<i64> = number.chars().filter_map(ja_digit).collect();
+        let words: Vec<&str> = digits.iter().map(|&d| JA_DIGITS[d as usize]).collect();
+        let value = digits
+            .iter()
+            .fold(0i64, |acc, d| acc.saturating_mul(10) + d);
+        return (words.join(" "), value);
+    }
+
+    let mut reading = String::new();
+    let mut total: i64 = 0;
+    let mut section: i64 = 0;
+    let mut digit: Option<i64> = None;
+    for c in number.chars() {
+        if let Some(d) = ja_digit(c) {
+            digit = Some(d);
+            continue;
+        }
+        let d = digit.take();
+        match c {
+            '十' | '百' | '千' => {
+                let (unit, word) = match c {
+                    '十' => (10, "juu"),
+                    '百' => (100, "hyaku"),
+                    _ => (1000, "sen"),
+                };
+                reading.push_str(&match (c, d) {
+                    ('百', Some(3)) => "sanbyaku".to_string(),
+                    ('百', Some(6)) => "roppyaku".to_string(),
+                    ('百', Some(8)) => "happyaku".to_string(),
+                    ('千', Some(1)) => "issen".to_string(),
+                    ('千', Some(3)) => "sanzen".to_string(),
+                    ('千', Some(8)) => "hassen".to_string(),
+                    (_, Some(d)) if d > 1 => format!("{}{}", JA_DIGITS[d as usize], word),
+                    _ => word.to_string(),
+                });
+                section += d.unwrap_or(1) * unit;
+            }
+            '万' | '億' | '兆' => {
+                let (unit, word): (i64, &str) = match c {
+                    '万' => (10_000, "man"),
+                    '億' => (100_000_000, "oku"),
+                    _ => (1_000_000_000_000, "chou"),
+                };
+                if let Some(d) = d {
+                    reading.push_str(match (c, d) {
+                        ('兆', 1) => "it",
+                        ('兆', 8) => "hat",
+                        _ => JA_DIGITS[d as usize],
+                    });
+                }
+                reading.push_str(word);
+                total = total.saturating_add((section + d.unwrap_or(0)).saturating_mul(unit));
+                section = 0;
+            }
+            _ => {}
+        }
+    }
+    if let Some(d) = digit {
+        reading.push_str(JA_DIGITS[d as usize]);
+        section += d;
+    }
+    (reading, total.saturating_add(section))
+}
+
+/// Attach a counter to a number reading, applying its sound changes
+fn ja_counter(reading: &str, value: i64, counter: char) -> Option<String> {
+    // Years read digit by digit keep the counter as its own word
+    if reading.contains(' ') {
+        return (counter == '年').then(|| format!("{} nen", reading));
+    }
+    let ones = value % 10;
+    // Replace the reading of the last digit
+    let with_last = |last: &str, suffix: &str| -> String {
+        let digit = JA_DIGITS[ones as usize];
+        match reading.strip_suffix(digit) {
+            Some(head) => format!("{}{}{}", head, last, suffix),
+            None => format!("{}{}", reading, suffix),
+        }
+    };
+    let counted = match counter {
+        '年' if ones == 4 => with_last("yo", "nen"),
+        '年' => format!("{}nen", reading),
+        '円' if ones == 4 => with_last("yo", "en"),
+        '円' => format!("{}en", reading),
+        '月' => match value {
+            4 => "shigatsu".to_string(),
+            7 => "shichigatsu".to_string(),
+            9 => "kugatsu".to_string(),
+            _ => format!("{}gatsu", reading),
+        },
+        '日' => match JA_DAYS.iter().find(|(day, _)| *day == value) {
+            Some((_, word)) => word.to_string(),
+            None if ones == 7 => with_last("shichi", "nichi"),
+            None if ones == 9 => with_last("ku", "nichi"),
+            None => format!("{}nichi", reading),
+        },
+        '時' => match ones {
+            4 => with_last("yo", "ji"),
+            7 => with_last("shichi", "ji"),
+            9 => with_last("ku", "ji"),
+            _ => format!("{}ji", reading),
+        },
+        '分' => match ones {
+            1 => with_last("ip", "pun"),
+            3 | 4 => format!("{}pun", reading),
+            6 => with_last("rop", "pun"),
+            8 => with_last("hap", "pun"),
+            0 if reading.ends_with("juu") => {
+                format!("{}juppun", &reading[..reading.len() - "juu".len()])
+            }
+            _ => format!("{}fun", reading),
+        },
+        '秒' => format!("{}byou", reading),
+        _ => return None,
+    };
+    Some(counted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_script() {
+        assert_eq!(
+            to_script("二零二四年", OutputScript::Pinyin, Language::Zh),
+            "er4 ling2 er4 si4 nian2"
+        );
+        assert_eq!(
+            to_script("三点五元", OutputScript::Bopomofo, Language::Zh),
+            "ㄙㄢ ㄉㄧㄢˇ ㄨˇ ㄩㄢˊ"
+        );
+        assert_eq!(
+            to_script("共十米", OutputScript::Pinyin, Language::Zh),
+            "共 shi2 mi3"
+        );
+        assert_eq!(to_script("十米", OutputScript::Hanzi, Language::Zh), "十米");
+        assert_eq!(
+            to_script("两点半", OutputScript::Jyutping, Language::Zh),
+            "loeng5 dim2 bun3"
+        );
+    }
+
+    #[test]
+    fn test_romaji() {
+        let romaji = |text| to_script(text, OutputScript::Romaji, Language::Ja);
+        assert_eq!(romaji("三百六十五日"), "sanbyakurokujuugonichi");
+        assert_eq!(
+            romaji("二〇二四年四月一日"),
+            "ni zero ni yon nen shigatsu tsuitachi"
+        );
+        assert_eq!(romaji("十四時十分"), "juuyoji juppun");
+        assert_eq!(romaji("三点一四"), "san ten ichi yon");
+        assert_eq!(romaji("千八百円です"), "senhappyakuen です");
+        // Romaji only applies to Japanese
+        assert_eq!(
+            to_script("三百", OutputScript::Romaji, Language::Zh),
+            "三百"
+        );
+    }
+}
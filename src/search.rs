@@ -0,0 +1,217 @@
+//! Bounded best-path search
+//!
+//! Composing the input with a grammar materializes every path the grammar
+//! allows, and inputs such as long digit runs can make that lattice large.
+//! Beam pruning drops the arcs that cannot be on a path close to the best
+//! one before the n-best search, and the early-stopping search composes
+//! lazily, expanding the cheapest states first (Dijkstra) and stopping as
+//! soon as the cheapest final state is known.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use rustfst::algorithms::compose::ComposeFst;
+use rustfst::algorithms::{connect, shortest_distance};
+use rustfst::fst_impls::VectorFst;
+use rustfst::prelude::*;
+use rustfst::semirings::TropicalWeight;
+use rustfst::Label;
+
+use crate::error::{Result, WeTextError};
+
+fn op_error(e: anyhow::Error) -> WeTextError {
+    WeTextError::FstOperationError(e.to_string())
+}
+
+/// Whether all arc and final weights are non-negative, so that Dijkstra's
+/// search finds the best path
+pub(crate) fn has_nonnegative_weights<F: ExpandedFst<TropicalWeight>>(fst: &F) -> bool {
+    fst.states_iter().all(|state| {
+        let trs = fst
+            .get_trs(state)
+            .map_or(true, |trs| trs.iter().all(|tr| *tr.weight.value() >= 0.0));
+        let final_weight = fst
+            .final_weight(state)
+            .ok()
+            .flatten()
+            .is_none_or(|weight| *weight.value() >= 0.0);
+        trs && final_weight
+    })
+}
+
+/// Remove the arcs and final weights of `fst` that are only on paths more
+/// than `beam` above the best path
+pub(crate) fn prune(fst: &mut VectorFst<TropicalWeight>, beam: f32) -> Result<()> {
+    let Some(start) = fst.start() else {
+        return Ok(());
+    };
+    let forward = shortest_distance(fst, false).map_err(op_error)?;
+    let backward = shortest_distance(fst, true).map_err(op_error)?;
+    let distance = |weights: &[TropicalWeight], state: StateId| {
+        weights
+            .get(state as usize)
+            .map_or(f32::INFINITY, |weight| *weight.value())
+    };
+    let limit = distance(&backward, start) + beam;
+    for state in 0..fst.num_states() as StateId {
+        let before = distance(&forward, state);
+        let trs = fst.pop_trs(state).map_err(op_error)?;
+        for tr in trs {
+            if before + *tr.weight.value() + distance(&backward, tr.nextstate) <= limit {
+                fst.add_tr(state, tr).map_err(op_error)?;
+            }
+        }
+        let final_weight = fst.final_weight(state).map_err(op_error)?;
+        if final_weight.is_some_and(|weight| before + *weight.value() > limit) {
+            fst.delete_final_weight(state).map_err(op_error)?;
+        }
+    }
+    connect(fst).map_err(op_error)
+}
+
+/// Open state of the search, ordered cheapest first
+struct Open {
+    distance: f32,
+    state: StateId,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then_with(|| other.state.cmp(&self.state))
+    }
+}
+
+/// Best distance and incoming (state, output label) of each state seen
+#[derive(Default)]
+struct Visited {
+    distances: Vec<f32>,
+    previous: Vec<Option<(StateId, Label)>>,
+}
+
+impl Visited {
+    /// Record a path to `state`, returning whether it is the cheapest so far
+    fn relax(&mut self, state: StateId, distance: f32, from: Option<(StateId, Label)>) -> bool {
+        let index = state as usize;
+        if index >= self.distances.len() {
+            self.distances.resize(index + 1, f32::INFINITY);
+            self.previous.resize(index + 1, None);
+        }
+        if distance < self.distances[index] {
+            self.distances[index] = distance;
+            self.previous[index] = from;
+            return true;
+        }
+        false
+    }
+}
+
+/// Output labels of the best path through the composition of `input` and
+/// `grammar`, composing only the states cheaper than that path
+///
+/// Both FSTs must have non-negative weights. Returns `None` if the
+/// composition accepts nothing.
+pub(crate) fn best_path_early<F: ExpandedFst<TropicalWeight>>(
+    input: &VectorFst<TropicalWeight>,
+    grammar: &F,
+) -> Result<Option<Vec<Label>>> {
+    let composed =
+        ComposeFst::<TropicalWeight, VectorFst<TropicalWeight>, F, _, _, _, _, _>::new_auto(
+            input, grammar,
+        )
+        .map_err(op_error)?;
+    let Some(start) = composed.start() else {
+        return Ok(None);
+    };
+    let mut visited = Visited::default();
+    let mut open = BinaryHeap::new();
+    visited.relax(start, 0.0, None);
+    open.push(Open {
+        distance: 0.0,
+        state: start,
+    });
+    let mut best: Option<(f32, StateId)> = None;
+    while let Some(Open { distance, state }) = open.pop() {
+        if best.is_some_and(|(cost, _)| distance >= cost) {
+            break;
+        }
+        if distance > visited.distances[state as usize] {
+            continue;
+        }
+        if let Some(weight) = composed.final_weight(state).map_err(op_error)? {
+            let cost = distance + *weight.value();
+            if best.is_none_or(|(best_cost, _)| cost < best_cost) {
+                best = Some((cost, state));
+            }
+        }
+        for tr in composed.get_trs(state).map_err(op_error)?.iter() {
+            let next = distance + *tr.weight.value();
+            if visited.relax(tr.nextstate, next, Some((state, tr.olabel))) {
+                open.push(Open {
+                    distance: next,
+                    state: tr.nextstate,
+                });
+            }
+        }
+    }
+
+    let Some((_, mut state)) = best else {
+        return Ok(None);
+    };
+    let mut olabels = Vec::new();
+    while let Some((from, olabel)) = visited.previous[state as usize] {
+        olabels.push(olabel);
+        state = from;
+    }
+    olabels.reverse();
+    Ok(Some(olabels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustfst::utils::acceptor;
+    use rustfst::EPS_LABEL;
+
+    fn input(text: &str) -> VectorFst<TropicalWeight> {
+        let labels: Vec<Label> = text.bytes().map(Label::from).collect();
+        acceptor(&labels, TropicalWeight::one())
+    }
+
+    fn output(olabels: &[Label]) -> String {
+        olabels
+            .iter()
+            .filter(|&&label| label != EPS_LABEL)
+            .map(|&label| label as u8 as char)
+            .collect()
+    }
+
+    #[test]
+    fn test_best_path_early() {
+        let grammar = crate::Rule::replace("a", "x")
+            .weight(2.0)
+            .or(crate::Rule::replace("a", "y").weight(1.0))
+            .to_fst()
+            .unwrap();
+        let olabels = best_path_early(&input("a"), &grammar).unwrap().unwrap();
+        assert_eq!(output(&olabels), "y");
+        assert!(best_path_early(&input("b"), &grammar).unwrap().is_none());
+        assert!(has_nonnegative_weights(&grammar));
+    }
+}
@@ -0,0 +1,72 @@
+//! Word segmentation respected by the tagger
+//!
+//! The taggers match entities character by character and can take part of a
+//! word for a number: ITN reads "一会儿" as "1会儿", and "第一百货商店" as an
+//! ordinal "第一百". With a word segmenter plugged in, words that an entity
+//! only partly covers are copied through unchanged, and the text around them
+//! is normalized on its own.
+
+use std::ops::Range;
+
+/// External word segmenter (e.g. jieba) run on text before tagging
+///
+/// Implemented for closures `Fn(&str) -> Vec<Range<usize>>`.
+pub trait Segmenter: Send + Sync {
+    /// Byte ranges of the words of `text`
+    ///
+    /// Ranges need not cover whitespace or punctuation; only their ends
+    /// are used.
+    fn segment(&self, text: &str) -> Vec<Range<usize>>;
+}
+
+impl<F> Segmenter for F
+where
+    F: Fn(&str) -> Vec<Range<usize>> + Send + Sync,
+{
+    fn segment(&self, text: &str) -> Vec<Range<usize>> {
+        self(text)
+    }
+}
+
+/// Words that an entity covers only in part, sorted and without overlaps
+pub(crate) fn split_words(words: &[Range<usize>], entities: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut split: Vec<Range<usize>> = words
+        .iter()
+        .filter(|word| {
+            entities.iter().any(|entity| {
+                let overlaps = entity.start < word.end && word.start < entity.end;
+                overlaps && (entity.start > word.start || entity.end < word.end)
+            })
+        })
+        .cloned()
+        .collect();
+    split.sort_by_key(|word| word.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(split.len());
+    for word in split {
+        match merged.last_mut() {
+            Some(last) if word.start < last.end => last.end = last.end.max(word.end),
+            _ => merged.push(word),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        // "第一百货商店": the ordinal "第一百" ends inside "百货商店"
+        let words = vec![0..6, 6..18];
+        let split = split_words(&words, std::slice::from_ref(&(0..9)));
+        assert_eq!(split, &words[1..]);
+        // An entity made of whole words splits none of them
+        let words = vec![0..7, 7..11, 11..16];
+        assert!(split_words(&words, std::slice::from_ref(&(0..16))).is_empty());
+        // "一会儿": the number "一" is part of a longer word
+        let word = 0..9;
+        let split = split_words(std::slice::from_ref(&word), std::slice::from_ref(&(0..3)));
+        assert_eq!(split, std::slice::from_ref(&word));
+    }
+}
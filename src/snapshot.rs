@@ -0,0 +1,165 @@
+//! Single-file snapshots of the FST cache
+//!
+//! A service that loads a dozen FSTs on startup opens, parses and (with
+//! `compact_fsts`) converts each of them. A snapshot stores the cached FSTs
+//! in their in-memory layout in one file, so a new instance reads one file
+//! and skips the conversion.
+//!
+//! Layout, integers little-endian: the magic `WETEXTSNAP`, a `u32` format
+//! version and a `u32` entry count, then per entry the relative path (`u32`
+//! length and UTF-8 bytes), the source modification time (`u8` presence
+//! flag, `u64` seconds and `u32` nanoseconds since the Unix epoch), a `u8`
+//! layout (0 for `VectorFst`, 1 for `ConstFst`) and the FST in OpenFST
+//! binary format (`u64` length and bytes).
+
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{Result, WeTextError};
+use crate::text_normalizer::FstTextNormalizer;
+
+const MAGIC: &[u8] = b"WETEXTSNAP";
+const VERSION: u32 = 1;
+
+/// A cached FST as stored in a snapshot
+pub(crate) struct Entry {
+    /// Path relative to the FST directory
+    pub(crate) path: String,
+    /// Modification time of the source file when it was loaded
+    pub(crate) modified: Option<SystemTime>,
+    pub(crate) normalizer: FstTextNormalizer,
+}
+
+/// Write `entries` to a snapshot file
+pub(crate) fn write<'a, P, I>(path: P, entries: I) -> Result<()>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = (&'a str, Option<SystemTime>, &'a FstTextNormalizer)>,
+{
+    let entries: Vec<_> = entries.into_iter().collect();
+    let mut out = BufWriter::new(std::fs::File::create(path)?);
+    out.write_all(MAGIC)?;
+    out.write_all(&VERSION.to_le_bytes())?;
+    out.write_all(&(entries.len() as u32).to_le_bytes())?;
+    let mut fst = Vec::new();
+    for (relative_path, modified, normalizer) in entries {
+        out.write_all(&(relative_path.len() as u32).to_le_bytes())?;
+        out.write_all(relative_path.as_bytes())?;
+        match modified.and_then(|time| time.duration_since(UNIX_EPOCH).ok()) {
+            Some(since) => {
+                out.write_all(&[1])?;
+                out.write_all(&since.as_secs().to_le_bytes())?;
+                out.write_all(&since.subsec_nanos().to_le_bytes())?;
+            }
+            None => out.write_all(&[0; 13])?,
+        }
+        fst.clear();
+        normalizer.store(&mut fst)?;
+        out.write_all(&[u8::from(normalizer.is_compact())])?;
+        out.write_all(&(fst.len() as u64).to_le_bytes())?;
+        out.write_all(&fst)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Cursor over the bytes of a snapshot
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(WeTextError::FstLoadError("truncated snapshot".to_string()));
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+}
+
+/// Read the entries of a snapshot file
+pub(crate) fn read<P: AsRef<Path>>(path: P) -> Result<Vec<Entry>> {
+    let data = std::fs::read(path)?;
+    let mut reader = Reader { data: &data };
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(WeTextError::FstLoadError("not a snapshot file".to_string()));
+    }
+    let version = u32::from_le_bytes(reader.array()?);
+    if version != VERSION {
+        return Err(WeTextError::FstLoadError(format!(
+            "unsupported snapshot version {}",
+            version
+        )));
+    }
+    let count = u32::from_le_bytes(reader.array()?);
+    let mut entries = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let len = u32::from_le_bytes(reader.array()?) as usize;
+        let path = std::str::from_utf8(reader.take(len)?)
+            .map_err(|e| WeTextError::FstLoadError(format!("invalid snapshot path: {}", e)))?
+            .to_string();
+        let [has_modified] = reader.array()?;
+        let secs = u64::from_le_bytes(reader.array()?);
+        let nanos = u32::from_le_bytes(reader.array()?);
+        let modified = (has_modified == 1).then(|| UNIX_EPOCH + Duration::new(secs, nanos));
+        let [compact] = reader.array()?;
+        let len = u64::from_le_bytes(reader.array()?) as usize;
+        let normalizer = FstTextNormalizer::load(reader.take(len)?, compact == 1)?;
+        entries.push(Entry {
+            path,
+            modified,
+            normalizer,
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.snap");
+        let vector = crate::Rule::replace("a", "b").to_rewriter().unwrap();
+        let mut compact = crate::Rule::replace("c", "d").to_rewriter().unwrap();
+        compact.compact();
+        let modified = UNIX_EPOCH + Duration::new(1_700_000_000, 5);
+        write(
+            &path,
+            [
+                ("zh/tn/tagger.fst", Some(modified), &vector),
+                ("zh/tn/verbalizer.fst", None, &compact),
+            ],
+        )
+        .unwrap();
+
+        let entries = read(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "zh/tn/tagger.fst");
+        assert_eq!(entries[0].modified, Some(modified));
+        assert!(!entries[0].normalizer.is_compact());
+        assert_eq!(entries[0].normalizer.normalize("xa").unwrap(), "xb");
+        assert_eq!(entries[1].modified, None);
+        assert!(entries[1].normalizer.is_compact());
+        assert_eq!(entries[1].normalizer.normalize("c").unwrap(), "d");
+    }
+
+    #[test]
+    fn test_invalid_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.snap");
+        std::fs::write(&path, b"not a snapshot").unwrap();
+        assert!(read(&path).is_err());
+        std::fs::write(&path, b"WETEXTSNAP\x01\0\0\0\x01\0\0\0").unwrap();
+        assert!(read(&path).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Character-by-character spell-out
+//!
+//! Serial numbers, booking codes and captchas are read one character at a
+//! time rather than as words or numbers.
+
+use crate::config::Language;
+use crate::numbers::digit_word;
+
+const EN_LETTERS: [&str; 26] = [
+    "ay",
+    "bee",
+    "cee",
+    "dee",
+    "ee",
+    "ef",
+    "gee",
+    "aitch",
+    "eye",
+    "jay",
+    "kay",
+    "el",
+    "em",
+    "en",
+    "oh",
+    "pee",
+    "cue",
+    "ar",
+    "ess",
+    "tee",
+    "you",
+    "vee",
+    "double you",
+    "ex",
+    "why",
+    "zee",
+];
+
+const ZH_LETTERS: [&str; 26] = [
+    "诶",
+    "比",
+    "西",
+    "迪",
+    "伊",
+    "艾弗",
+    "吉",
+    "艾尺",
+    "艾",
+    "杰",
+    "开",
+    "艾勒",
+    "艾马",
+    "艾娜",
+    "哦",
+    "屁",
+    "吉吾",
+    "艾儿",
+    "艾丝",
+    "提",
+    "伊吾",
+    "维",
+    "豆贝尔维",
+    "艾克斯",
+    "吾艾",
+    "贼德",
+];
+
+const JA_LETTERS: [&str; 26] = [
+    "エー",
+    "ビー",
+    "シー",
+    "ディー",
+    "イー",
+    "エフ",
+    "ジー",
+    "エイチ",
+    "アイ",
+    "ジェー",
+    "ケー",
+    "エル",
+    "エム",
+    "エヌ",
+    "オー",
+    "ピー",
+    "キュー",
+    "アール",
+    "エス",
+    "ティー",
+    "ユー",
+    "ブイ",
+    "ダブリュー",
+    "エックス",
+    "ワイ",
+    "ゼット",
+];
+
+/// Spoken name of an ASCII letter
+pub(crate) fn letter_name(c: char, lang: Language) -> Option<&'static str> {
+    if !c.is_ascii_alphabetic() {
+        return None;
+    }
+    let index = (c.to_ascii_uppercase() as u8 - b'A') as usize;
+    Some(match lang {
+        Language::En => EN_LETTERS[index],
+        Language::Ja => JA_LETTERS[index],
+        Language::Zh | Language::Auto => ZH_LETTERS[index],
+    })
+}
+
+/// Read text one character at a time
+///
+/// Letters and digits are read by name; ASCII punctuation and whitespace
+/// are dropped, and any other character is kept as it is. English readings
+/// are separated by spaces.
+///
+/// # Example
+/// ```rust
+/// use wetext_rs::{spell_out, Language};
+///
+/// assert_eq!(spell_out("AB-12", Language::En), "ay bee one two");
+/// assert_eq!(spell_out("AB-12", Language::Zh), "诶比一二");
+/// ```
+pub fn spell_out(text: &str, lang: Language) -> String {
+    let words: Vec<String> = text
+        .chars()
+        .filter(|c| !c.is_ascii_punctuation() && !c.is_whitespace())
+        .map(|c| match letter_name(c, lang) {
+            Some(name) => name.to_string(),
+            None if c.is_ascii_digit() => digit_word(c, lang).to_string(),
+            None => c.to_string(),
+        })
+        .collect();
+    match lang {
+        Language::En => words.join(" "),
+        _ => words.concat(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spell_out() {
+        assert_eq!(spell_out("x7w", Language::En), "ex seven double you");
+        assert_eq!(spell_out("QR 9", Language::Ja), "キューアール九");
+        assert_eq!(spell_out("号A1", Language::Zh), "号诶一");
+    }
+}
@@ -0,0 +1,199 @@
+//! Incremental normalization of a growing transcript
+//!
+//! Live ASR output arrives a few characters at a time and the end of the
+//! transcript keeps changing until a sentence is complete. `StreamNormalizer`
+//! buffers that unstable suffix and only normalizes and emits text once a
+//! sentence boundary has been seen, so committed output never changes.
+
+use crate::error::Result;
+use crate::normalizer::Normalizer;
+
+/// Whether a character ends a sentence wherever it appears
+fn is_terminator(c: char) -> bool {
+    matches!(c, '。' | '！' | '？' | '；' | '!' | '?' | ';' | '\n')
+}
+
+/// Byte offsets just past each sentence end in `text`
+///
+/// A full stop only ends a sentence once whitespace follows it, so that
+/// "3." is not committed before a following "5" arrives.
+fn sentence_ends(text: &str) -> Vec<usize> {
+    let mut ends = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let full_stop = c == '.' && chars.peek().is_some_and(|(_, next)| next.is_whitespace());
+        if is_terminator(c) || full_stop {
+            ends.push(index + c.len_utf8());
+        }
+    }
+    ends
+}
+
+/// Byte offset just past the last complete sentence in `text`
+pub(crate) fn commit_point(text: &str) -> Option<usize> {
+    sentence_ends(text).last().copied()
+}
+
+/// Split text into sentences, each keeping its terminator and the whitespace before it
+///
+/// Concatenating the pieces gives back `text`. Text after the last
+/// terminator is returned as a final, unterminated piece.
+pub(crate) fn split_sentences(text: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    for end in sentence_ends(text) {
+        pieces.push(&text[start..end]);
+        start = end;
+    }
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+    pieces
+}
+
+/// Split a sentence into words and clauses, each keeping the whitespace after it
+///
+/// Concatenating the pieces gives back `text`. CJK commas and colons end a
+/// clause; ASCII ones only do through the whitespace that follows them, so
+/// "1,000" stays whole.
+pub(crate) fn split_spans(text: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, next)| next);
+        let boundary = matches!(c, '，' | '、' | '：')
+            || (c.is_whitespace() && next.is_some_and(|next| !next.is_whitespace()));
+        if boundary {
+            let end = index + c.len_utf8();
+            pieces.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+    pieces
+}
+
+/// Normalizer for text that arrives incrementally
+///
+/// # Example
+/// ```rust,ignore
+/// let mut stream = StreamNormalizer::new(normalizer);
+/// assert_eq!(stream.push_str("今天是二零二").unwrap(), "");
+/// assert_eq!(stream.push_str("四年。明天").unwrap(), "今天是2024年。");
+/// assert_eq!(stream.flush().unwrap(), "明天");
+/// ```
+pub struct StreamNormalizer {
+    normalizer: Normalizer,
+    pending: String,
+}
+
+impl StreamNormalizer {
+    /// Create a stream normalizer using the given normalizer's configuration
+    pub fn new(normalizer: Normalizer) -> Self {
+        Self {
+            normalizer,
+            pending: String::new(),
+        }
+    }
+
+    /// Append text to the transcript
+    ///
+    /// # Returns
+    /// Normalized output for the sentences completed by this text (empty if
+    /// none were); the rest is kept until more text arrives
+    pub fn push_str(&mut self, text: &str) -> Result<String> {
+        self.pending.push_str(text);
+        let Some(point) = commit_point(&self.pending) else {
+            return Ok(String::new());
+        };
+        let rest = self.pending.split_off(point);
+        let committed = std::mem::replace(&mut self.pending, rest);
+        self.normalizer.normalize_sentences(&committed)
+    }
+
+    /// Normalize the unstable suffix without committing it
+    ///
+    /// Useful for displaying a provisional rendering of the sentence in
+    /// progress; the result may change as more text arrives.
+    pub fn preview(&mut self) -> Result<String> {
+        let pending = self.pending.clone();
+        self.normalizer.normalize_sentences(&pending)
+    }
+
+    /// Normalize and emit everything still buffered
+    pub fn flush(&mut self) -> Result<String> {
+        let pending = std::mem::take(&mut self.pending);
+        self.normalizer.normalize_sentences(&pending)
+    }
+
+    /// Text received but not yet committed
+    pub fn pending(&self) -> &str {
+        &self.pending
+    }
+
+    /// Return the wrapped normalizer, dropping any buffered text
+    pub fn into_inner(self) -> Normalizer {
+        self.normalizer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Language, NormalizerConfig};
+
+    #[test]
+    fn test_commit_point() {
+        assert_eq!(commit_point("你好"), None);
+        assert_eq!(commit_point("你好。再"), Some("你好。".len()));
+        assert_eq!(commit_point("It costs 3."), None);
+        assert_eq!(commit_point("It costs 3.5"), None);
+        assert_eq!(commit_point("Done. Next"), Some("Done.".len()));
+        assert_eq!(commit_point("Yes! No? Ma"), Some("Yes! No?".len()));
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        assert_eq!(
+            split_sentences("你好。再见！好"),
+            vec!["你好。", "再见！", "好"]
+        );
+        assert_eq!(
+            split_sentences("Hi. It is 3.5 m. "),
+            vec!["Hi.", " It is 3.5 m.", " "]
+        );
+        assert!(split_sentences("").is_empty());
+    }
+
+    #[test]
+    fn test_split_spans() {
+        assert_eq!(split_spans("共5元，共6元"), vec!["共5元，", "共6元"]);
+        assert_eq!(
+            split_spans("pay 1,000  now "),
+            vec!["pay ", "1,000  ", "now "]
+        );
+        assert!(split_spans("").is_empty());
+    }
+
+    #[test]
+    fn test_push_and_flush() {
+        // Text without digits passes through TN without loading any FST
+        let dir = tempfile::tempdir().unwrap();
+        let config = NormalizerConfig::new().with_lang(Language::Zh);
+        let mut stream = StreamNormalizer::new(Normalizer::new(dir.path(), config));
+
+        assert_eq!(stream.push_str("你好").unwrap(), "");
+        assert_eq!(stream.pending(), "你好");
+        assert_eq!(stream.push_str("。再").unwrap(), "你好。");
+        assert_eq!(stream.preview().unwrap(), "再");
+        assert_eq!(stream.push_str("见").unwrap(), "");
+        assert_eq!(stream.flush().unwrap(), "再见");
+        assert_eq!(stream.pending(), "");
+
+        // Line breaks are sentence boundaries and are kept
+        assert_eq!(stream.push_str("你好\n再").unwrap(), "你好\n");
+    }
+}
@@ -0,0 +1,285 @@
+//! Reference comparison runner
+//!
+//! Loads test cases of the form `(input, lang, operator, expected)` from
+//! JSON, runs them through a `Normalizer`, and returns a structured report,
+//! so model drops can be validated against known-good outputs.
+//!
+//! The case file is a JSON array of objects:
+//!
+//! ```json
+//! [
+//!   {"input": "2024年", "lang": "zh", "operator": "tn", "expected": "二零二四年"}
+//! ]
+//! ```
+//!
+//! `expected_output` is accepted in place of `expected`, and cases with a
+//! non-null `error` field are skipped, so reference files written by
+//! `tests/generate_reference.py` can be used as is.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::config::{Language, NormalizerConfig, Operator};
+use crate::error::{Result, WeTextError};
+use crate::normalizer::Normalizer;
+
+/// A reference test case
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCase {
+    /// Input text
+    pub input: String,
+    /// Language to normalize as
+    pub lang: Language,
+    /// Operation to run
+    pub operator: Operator,
+    /// Expected output
+    pub expected: String,
+}
+
+/// Outcome of running one test case
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseResult {
+    /// The case that was run
+    pub case: TestCase,
+    /// Normalizer output, or the error message if normalization failed
+    pub actual: std::result::Result<String, String>,
+}
+
+impl CaseResult {
+    /// Whether the output matched the expected output
+    pub fn passed(&self) -> bool {
+        self.actual.as_deref() == Ok(self.case.expected.as_str())
+    }
+}
+
+/// Results of a test run
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TestReport {
+    /// Result of each case, in input order
+    pub results: Vec<CaseResult>,
+    /// Number of cases skipped because the reference recorded an error
+    pub skipped: usize,
+}
+
+impl TestReport {
+    /// Number of passing cases
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.passed()).count()
+    }
+
+    /// Number of failing cases
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.passed()
+    }
+
+    /// Failing cases, in input order
+    pub fn failures(&self) -> impl Iterator<Item = &CaseResult> {
+        self.results.iter().filter(|r| !r.passed())
+    }
+
+    /// Whether every case passed
+    pub fn is_success(&self) -> bool {
+        self.failed() == 0
+    }
+}
+
+impl fmt::Display for TestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in self.failures() {
+            let case = &result.case;
+            writeln!(
+                f,
+                "FAIL: '{}' ({:?}/{:?})\n  expected: '{}'",
+                case.input, case.lang, case.operator, case.expected
+            )?;
+            match &result.actual {
+                Ok(actual) => writeln!(f, "  got:      '{}'", actual)?,
+                Err(e) => writeln!(f, "  error:    {}", e)?,
+            }
+        }
+        write!(
+            f,
+            "{} passed, {} failed, {} skipped",
+            self.passed(),
+            self.failed(),
+            self.skipped
+        )
+    }
+}
+
+fn case_error(index: usize, message: &str) -> WeTextError {
+    WeTextError::TestCaseError(format!("case {}: {}", index, message))
+}
+
+fn parse_lang(value: &str) -> Option<Language> {
+    match value {
+        "auto" => Some(Language::Auto),
+        "zh" => Some(Language::Zh),
+        "en" => Some(Language::En),
+        "ja" => Some(Language::Ja),
+        _ => None,
+    }
+}
+
+fn parse_operator(value: &str) -> Option<Operator> {
+    match value {
+        "tn" => Some(Operator::Tn),
+        "itn" => Some(Operator::Itn),
+        _ => None,
+    }
+}
+
+/// Parse test cases from JSON
+///
+/// # Returns
+/// The cases and the number of cases skipped for a recorded error
+pub fn parse_cases(json: &str) -> Result<(Vec<TestCase>, usize)> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| WeTextError::TestCaseError(e.to_string()))?;
+    let Value::Array(items) = value else {
+        return Err(WeTextError::TestCaseError(
+            "expected an array of cases".to_string(),
+        ));
+    };
+
+    let mut cases = Vec::with_capacity(items.len());
+    let mut skipped = 0;
+    for (index, item) in items.iter().enumerate() {
+        if item.get("error").is_some_and(|e| !e.is_null()) {
+            skipped += 1;
+            continue;
+        }
+        let field = |name: &str| item.get(name).and_then(Value::as_str);
+        let input = field("input").ok_or_else(|| case_error(index, "missing input"))?;
+        let expected = field("expected")
+            .or_else(|| field("expected_output"))
+            .ok_or_else(|| case_error(index, "missing expected"))?;
+        let lang = field("lang").unwrap_or("auto");
+        let lang = parse_lang(lang)
+            .ok_or_else(|| case_error(index, &format!("unknown language '{}'", lang)))?;
+        let operator = field("operator").unwrap_or("tn");
+        let operator = parse_operator(operator)
+            .ok_or_else(|| case_error(index, &format!("unknown operator '{}'", operator)))?;
+        cases.push(TestCase {
+            input: input.to_string(),
+            lang,
+            operator,
+            expected: expected.to_string(),
+        });
+    }
+    Ok((cases, skipped))
+}
+
+/// Load test cases from a JSON file
+///
+/// # Returns
+/// The cases and the number of cases skipped for a recorded error
+pub fn load_cases<P: AsRef<Path>>(path: P) -> Result<(Vec<TestCase>, usize)> {
+    parse_cases(&std::fs::read_to_string(path)?)
+}
+
+/// Run test cases against the FSTs in `fst_dir`
+///
+/// Each case is normalized with `base` configured for its language and
+/// operator. One normalizer is kept per language and operator, so FSTs are
+/// loaded once.
+pub fn run_cases<P: AsRef<Path>>(
+    fst_dir: P,
+    base: &NormalizerConfig,
+    cases: &[TestCase],
+) -> TestReport {
+    let fst_dir: PathBuf = fst_dir.as_ref().to_path_buf();
+    let mut normalizers: HashMap<(Language, Operator), Normalizer> = HashMap::new();
+    let results = cases
+        .iter()
+        .map(|case| {
+            let normalizer = normalizers
+                .entry((case.lang, case.operator))
+                .or_insert_with(|| {
+                    let config = base
+                        .clone()
+                        .with_lang(case.lang)
+                        .with_operator(case.operator);
+                    Normalizer::new(&fst_dir, config)
+                });
+            CaseResult {
+                case: case.clone(),
+                actual: normalizer.normalize(&case.input).map_err(|e| e.to_string()),
+            }
+        })
+        .collect();
+    TestReport {
+        results,
+        skipped: 0,
+    }
+}
+
+/// Load test cases from a JSON file and run them
+pub fn run_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    fst_dir: P,
+    base: &NormalizerConfig,
+    path: Q,
+) -> Result<TestReport> {
+    let (cases, skipped) = load_cases(path)?;
+    let mut report = run_cases(fst_dir, base, &cases);
+    report.skipped = skipped;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cases() {
+        let json = r#"[
+            {"input": "2024年", "lang": "zh", "operator": "tn", "expected": "二零二四年"},
+            {"input": "一百", "lang": "zh", "operator": "itn", "expected_output": "100"},
+            {"input": "x", "lang": "zh", "operator": "tn", "expected_output": "", "error": "boom"}
+        ]"#;
+        let (cases, skipped) = parse_cases(json).unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(skipped, 1);
+        assert_eq!(cases[1].operator, Operator::Itn);
+        assert_eq!(cases[1].expected, "100");
+
+        assert!(parse_cases(r#"[{"input": "a", "lang": "fr", "expected": "a"}]"#).is_err());
+        assert!(parse_cases(r#"{"input": "a"}"#).is_err());
+    }
+
+    #[test]
+    fn test_run_cases() {
+        // Text without digits passes through TN without loading any FST
+        let cases = vec![
+            TestCase {
+                input: "你好".to_string(),
+                lang: Language::Zh,
+                operator: Operator::Tn,
+                expected: "你好".to_string(),
+            },
+            TestCase {
+                input: "hello".to_string(),
+                lang: Language::En,
+                operator: Operator::Tn,
+                expected: "world".to_string(),
+            },
+            TestCase {
+                input: "1".to_string(),
+                lang: Language::Zh,
+                operator: Operator::Tn,
+                expected: "一".to_string(),
+            },
+        ];
+        let report = run_cases("missing", &NormalizerConfig::new(), &cases);
+        assert_eq!(report.passed(), 1);
+        assert_eq!(report.failed(), 2);
+        assert!(!report.is_success());
+        assert!(report.results[2].actual.is_err());
+        assert!(report
+            .to_string()
+            .ends_with("1 passed, 2 failed, 0 skipped"));
+    }
+}
@@ -3,24 +3,125 @@
 //! This module provides FST (Finite State Transducer) based text normalization,
 //! equivalent to kaldifst.TextNormalizer in Python.
 
+use std::fmt;
+use std::mem::size_of;
 use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
 use rustfst::algorithms::compose::compose;
-use rustfst::algorithms::shortest_path;
-use rustfst::fst_impls::VectorFst;
+use rustfst::algorithms::tr_compares::OLabelCompare;
+use rustfst::algorithms::{fst_convert_from_ref, tr_sort};
+use rustfst::algorithms::{shortest_path, shortest_path_with_config, ShortestPathConfig};
+use rustfst::fst_impls::{ConstFst, VectorFst};
 use rustfst::fst_traits::SerializableFst;
 use rustfst::prelude::*;
 use rustfst::semirings::TropicalWeight;
-use rustfst::utils::{acceptor, decode_linear_fst};
+use rustfst::utils::decode_linear_fst;
 use rustfst::{Label, EPS_LABEL};
 
+use crate::acceptor;
+use crate::config::{InvalidUtf8, SearchLimits};
+use crate::dot;
 use crate::error::{Result, WeTextError};
+use crate::labels;
+use crate::report::Confidence;
+use crate::search;
+
+/// Size and shape of a loaded FST, as returned by `FstTextNormalizer::info`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FstInfo {
+    /// Number of states
+    pub num_states: usize,
+    /// Number of arcs
+    pub num_arcs: usize,
+    /// Number of final states
+    pub num_final_states: usize,
+    /// Start state, if any
+    pub start: Option<StateId>,
+    /// Semiring of the arc weights (e.g. `"tropical"`)
+    pub weight_type: String,
+    /// Estimated heap and inline memory used by states and arcs, in bytes
+    ///
+    /// Symbol tables and allocator overhead are not included.
+    pub memory_bytes: usize,
+}
+
+impl fmt::Display for FstInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} states, {} arcs, {} final, start {}, {} weights, ~{} bytes",
+            self.num_states,
+            self.num_arcs,
+            self.num_final_states,
+            self.start
+                .map_or_else(|| "none".to_string(), |s| s.to_string()),
+            self.weight_type,
+            self.memory_bytes
+        )
+    }
+}
+
+/// Loaded grammar, mutable or compacted
+enum Grammar {
+    Vector(VectorFst<TropicalWeight>),
+    /// States and arcs in two flat arrays, without a heap block per state
+    Const(ConstFst<TropicalWeight>),
+}
+
+/// Evaluate `$body` with `$fst` bound to the grammar, whichever its layout
+macro_rules! with_grammar {
+    ($grammar:expr, $fst:ident => $body:expr) => {
+        match $grammar {
+            Grammar::Vector($fst) => $body,
+            Grammar::Const($fst) => $body,
+        }
+    };
+}
+
+/// Size and shape of an FST; `state_bytes` is the memory of one state
+fn fst_info<F: ExpandedFst<TropicalWeight>>(fst: &F, state_bytes: usize) -> FstInfo {
+    let mut num_arcs = 0;
+    let mut num_final_states = 0;
+    for state in fst.states_iter() {
+        num_arcs += fst.num_trs(state).unwrap_or(0);
+        if fst.is_final(state).unwrap_or(false) {
+            num_final_states += 1;
+        }
+    }
+    let num_states = fst.num_states();
+    FstInfo {
+        num_states,
+        num_arcs,
+        num_final_states,
+        start: fst.start(),
+        weight_type: TropicalWeight::weight_type(),
+        memory_bytes: num_states * state_bytes + num_arcs * size_of::<Tr<TropicalWeight>>(),
+    }
+}
+
+/// Compose `first` with `second`
+fn compose_fst<F: ExpandedFst<TropicalWeight>>(
+    first: &VectorFst<TropicalWeight>,
+    second: &F,
+) -> Result<VectorFst<TropicalWeight>> {
+    // Note: compose() requires output type to implement AllocableFst
+    // Explicitly specify all type parameters for compose
+    compose::<TropicalWeight, VectorFst<TropicalWeight>, F, VectorFst<TropicalWeight>, _, _>(
+        first, second,
+    )
+    .map_err(|e| WeTextError::FstOperationError(format!("compose failed: {}", e)))
+}
 
 /// FST-based text normalizer
 ///
 /// Equivalent to kaldifst.TextNormalizer in Python
 pub struct FstTextNormalizer {
-    fst: VectorFst<TropicalWeight>,
+    fst: Grammar,
+    search: SearchLimits,
+    invalid_utf8: InvalidUtf8,
+    /// Whether the FST has no negative weights, checked on first use
+    nonnegative: OnceLock<bool>,
 }
 
 impl FstTextNormalizer {
@@ -42,7 +143,125 @@ impl FstTextNormalizer {
         let fst = VectorFst::<TropicalWeight>::read(path)
             .map_err(|e| WeTextError::FstLoadError(e.to_string()))?;
 
-        Ok(Self { fst })
+        Ok(Self::from_fst(fst))
+    }
+
+    /// Create a normalizer from an in-memory FST
+    ///
+    /// The FST must use the same label encoding as the WeText FSTs
+    /// (UTF-8 bytes on the input side).
+    pub fn from_fst(fst: VectorFst<TropicalWeight>) -> Self {
+        Self {
+            fst: Grammar::Vector(fst),
+            search: SearchLimits::default(),
+            invalid_utf8: InvalidUtf8::default(),
+            nonnegative: OnceLock::new(),
+        }
+    }
+
+    /// Convert the FST to an immutable layout with all states in one array
+    /// and all arcs in another
+    ///
+    /// A `VectorFst` allocates an arc vector per state; on the large
+    /// verbalizers this halves resident memory or better. Results are
+    /// unchanged, and `compose_with` on a compact FST copies it back to a
+    /// mutable one first.
+    pub fn compact(&mut self) {
+        if let Grammar::Vector(fst) = &mut self.fst {
+            let fst = std::mem::replace(fst, VectorFst::new());
+            self.fst = Grammar::Const(ConstFst::from(fst));
+        }
+    }
+
+    /// Whether the FST has been compacted with `compact`
+    pub fn is_compact(&self) -> bool {
+        matches!(self.fst, Grammar::Const(_))
+    }
+
+    /// Write the FST in OpenFST binary format, in its current layout
+    pub(crate) fn store(&self, output: &mut Vec<u8>) -> Result<()> {
+        with_grammar!(&self.fst, fst => fst.store(output))
+            .map_err(|e| WeTextError::FstOperationError(format!("store failed: {}", e)))
+    }
+
+    /// Read an FST written by `store`
+    pub(crate) fn load(data: &[u8], compact: bool) -> Result<Self> {
+        let fst = if compact {
+            ConstFst::load(data).map(Grammar::Const)
+        } else {
+            VectorFst::load(data).map(Grammar::Vector)
+        };
+        let mut normalizer = Self::from_fst(VectorFst::new());
+        normalizer.fst = fst.map_err(|e| WeTextError::FstLoadError(e.to_string()))?;
+        Ok(normalizer)
+    }
+
+    /// Set limits on the path search of every call
+    pub fn with_search(mut self, search: SearchLimits) -> Self {
+        self.search = search;
+        self
+    }
+
+    /// Set limits on the path search of every call
+    pub fn set_search(&mut self, search: SearchLimits) {
+        self.search = search;
+    }
+
+    /// Set the decoding of output bytes that are not valid UTF-8
+    pub fn with_invalid_utf8(mut self, policy: InvalidUtf8) -> Self {
+        self.invalid_utf8 = policy;
+        self
+    }
+
+    /// Set the decoding of output bytes that are not valid UTF-8
+    pub fn set_invalid_utf8(&mut self, policy: InvalidUtf8) {
+        self.invalid_utf8 = policy;
+    }
+
+    /// Size and shape of the FST
+    ///
+    /// The memory estimate assumes the layout in use. A `VectorFst` has per
+    /// state a final weight, epsilon counters and a shared arc vector; a
+    /// compact FST has per state a final weight, epsilon counters and the
+    /// position of its arcs. Both have one `Tr` per arc.
+    pub fn info(&self) -> FstInfo {
+        match &self.fst {
+            Grammar::Vector(fst) => {
+                let state_bytes = size_of::<Option<TropicalWeight>>()
+                    + 2 * size_of::<usize>()
+                    + size_of::<Arc<Vec<Tr<TropicalWeight>>>>()
+                    // Arc counters and the Vec header behind it
+                    + 2 * size_of::<usize>()
+                    + size_of::<Vec<Tr<TropicalWeight>>>();
+                fst_info(fst, state_bytes)
+            }
+            Grammar::Const(fst) => {
+                let state_bytes = size_of::<Option<TropicalWeight>>() + 4 * size_of::<usize>();
+                fst_info(fst, state_bytes)
+            }
+        }
+    }
+
+    /// Write the FST to a file in OpenFST binary format
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        with_grammar!(&self.fst, fst => fst.write(path))
+            .map_err(|e| WeTextError::FstOperationError(format!("write failed: {}", e)))
+    }
+
+    /// Compose this FST with `next`, giving a single FST equivalent to
+    /// applying this one and then `next`
+    ///
+    /// Unlike running the two passes one after another, the composed FST picks
+    /// the overall best path, which is the same result as long as the first
+    /// FST's best output is accepted by `next`.
+    pub fn compose_with(&self, next: &FstTextNormalizer) -> Result<FstTextNormalizer> {
+        let mut first: VectorFst<TropicalWeight> = match &self.fst {
+            Grammar::Vector(fst) => fst.clone(),
+            Grammar::Const(fst) => fst_convert_from_ref(fst),
+        };
+        tr_sort(&mut first, OLabelCompare {});
+        let composed = with_grammar!(&next.fst, fst => compose_fst(&first, fst))?;
+        Ok(Self::from_fst(composed))
     }
 
     /// Apply FST for text transformation
@@ -53,51 +272,241 @@ impl FstTextNormalizer {
     /// 3. Find shortest path
     /// 4. Extract output string from the path
     ///
+    /// With `SearchLimits::early_stop`, steps 2 and 3 are one search that
+    /// composes only the states cheaper than the best path.
+    ///
     /// # Arguments
     /// * `input` - Input text to normalize
     ///
     /// # Returns
     /// Normalized text string
     pub fn normalize(&self, input: &str) -> Result<String> {
+        // If no match, return original input (same as kaldifst behavior)
+        Ok(self
+            .try_normalize(input)?
+            .unwrap_or_else(|| input.to_string()))
+    }
+
+    /// Apply FST like `normalize`, returning `None` when it accepts no path
+    /// for the input instead of the input itself
+    pub fn try_normalize(&self, input: &str) -> Result<Option<String>> {
         if input.is_empty() {
-            return Ok(String::new());
+            return Ok(Some(String::new()));
         }
 
-        // Step 1: Convert input string to linear FST using UTF-8 bytes
-        // WeText FSTs use UTF-8 byte encoding for labels
-        let labels: Vec<Label> = input.as_bytes().iter().map(|&b| b as Label).collect();
-        let input_fst: VectorFst<TropicalWeight> = acceptor(&labels, TropicalWeight::one());
-
-        // Step 2: Compose with the normalizer FST
-        // Note: compose() requires output type to implement AllocableFst
-        // Explicitly specify all type parameters for compose
-        let composed: VectorFst<TropicalWeight> = compose::<
-            TropicalWeight,
-            VectorFst<TropicalWeight>,
-            VectorFst<TropicalWeight>,
-            VectorFst<TropicalWeight>,
-            _,
-            _,
-        >(&input_fst, &self.fst)
-        .map_err(|e| WeTextError::FstOperationError(format!("compose failed: {}", e)))?;
-
-        // Check if compose result is empty (no match)
-        if composed.num_states() == 0 {
-            // If no match, return original input (same as kaldifst behavior)
-            return Ok(input.to_string());
+        if self.search.early_stop
+            && *self.nonnegative.get_or_init(
+                || with_grammar!(&self.fst, fst => search::has_nonnegative_weights(fst)),
+            )
+        {
+            let olabels = acceptor::with_acceptor(
+                input,
+                |input_fst| with_grammar!(&self.fst, fst => search::best_path_early(input_fst, fst)),
+            )?;
+            return match olabels {
+                Some(olabels) => self.labels_to_string(&olabels).map(Some),
+                None => Ok(None),
+            };
         }
 
+        // Steps 1-2: Build the input acceptor and compose
+        let Some(composed) = self.compose_input(input)? else {
+            return Ok(None);
+        };
+
         // Step 3: Find shortest path
         let best_path: VectorFst<TropicalWeight> = shortest_path(&composed)
             .map_err(|e| WeTextError::FstOperationError(format!("shortest_path failed: {}", e)))?;
 
         // Check if shortest_path result is empty
         if best_path.num_states() == 0 {
-            return Ok(input.to_string());
+            return Ok(None);
         }
 
         // Step 4: Extract output string using decode_linear_fst
-        self.fst_to_string(&best_path)
+        self.fst_to_string(&best_path).map(Some)
+    }
+
+    /// Apply FST, choosing among the `nbest` best paths after rescoring
+    ///
+    /// `penalty` is called with the output string of each candidate path and
+    /// its result is added to the path weight; the candidate with the lowest
+    /// total wins. This biases the shortest-path selection without modifying
+    /// the FST.
+    ///
+    /// # Arguments
+    /// * `input` - Input text to normalize
+    /// * `nbest` - Number of candidate paths to consider
+    /// * `penalty` - Extra cost for a candidate output
+    pub fn normalize_rescored<F: Fn(&str) -> f32>(
+        &self,
+        input: &str,
+        nbest: usize,
+        penalty: F,
+    ) -> Result<String> {
+        Ok(self
+            .try_normalize_rescored(input, nbest, penalty)?
+            .unwrap_or_else(|| input.to_string()))
+    }
+
+    /// Apply FST like `normalize_rescored`, returning `None` when it accepts
+    /// no path for the input instead of the input itself
+    pub fn try_normalize_rescored<F: Fn(&str) -> f32>(
+        &self,
+        input: &str,
+        nbest: usize,
+        penalty: F,
+    ) -> Result<Option<String>> {
+        if input.is_empty() {
+            return Ok(Some(String::new()));
+        }
+
+        let Some(composed) = self.compose_input(input)? else {
+            return Ok(None);
+        };
+
+        let config = ShortestPathConfig::default().with_nshortest(nbest.max(1));
+        let candidates: VectorFst<TropicalWeight> = shortest_path_with_config(&composed, config)
+            .map_err(|e| WeTextError::FstOperationError(format!("shortest_path failed: {}", e)))?;
+
+        let mut best: Option<(f32, String)> = None;
+        for path in candidates.paths_iter() {
+            let output = self.labels_to_string(&path.olabels)?;
+            let score = *path.weight.value() + penalty(&output);
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, _)| score < *best_score)
+            {
+                best = Some((score, output));
+            }
+        }
+
+        Ok(best.map(|(_, output)| output))
+    }
+
+    /// Apply FST and score the chosen path
+    ///
+    /// Returns the output of the shortest path with its weight. When `nbest`
+    /// is above 1, that many paths are searched and the margin to the best
+    /// path with a different output is reported too. The confidence is
+    /// `None` if the FST does not accept the input.
+    pub fn normalize_scored(
+        &self,
+        input: &str,
+        nbest: usize,
+    ) -> Result<(String, Option<Confidence>)> {
+        let outputs = self.normalize_nbest(input, nbest)?;
+        let Some((output, weight)) = outputs.first().cloned() else {
+            return Ok((input.to_string(), None));
+        };
+        let margin = outputs.get(1).map(|(_, other)| other - weight);
+        Ok((output, Some(Confidence { weight, margin })))
+    }
+
+    /// Apply FST and return the distinct outputs of the `nbest` best paths
+    ///
+    /// Outputs are sorted by path weight, best first, each with the weight
+    /// of its best path. Empty if the FST does not accept the input.
+    pub fn normalize_nbest(&self, input: &str, nbest: usize) -> Result<Vec<(String, f32)>> {
+        if input.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let Some(composed) = self.compose_input(input)? else {
+            return Ok(Vec::new());
+        };
+
+        let config = ShortestPathConfig::default().with_nshortest(nbest.max(1));
+        let candidates: VectorFst<TropicalWeight> = shortest_path_with_config(&composed, config)
+            .map_err(|e| WeTextError::FstOperationError(format!("shortest_path failed: {}", e)))?;
+
+        let mut paths = Vec::new();
+        for path in candidates.paths_iter() {
+            paths.push((self.labels_to_string(&path.olabels)?, *path.weight.value()));
+        }
+        paths.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let mut outputs: Vec<(String, f32)> = Vec::new();
+        for (output, weight) in paths {
+            if !outputs.iter().any(|(seen, _)| *seen == output) {
+                outputs.push((output, weight));
+            }
+        }
+        Ok(outputs)
+    }
+
+    /// Export the best path for an input as a Graphviz DOT graph
+    ///
+    /// With `neighborhood` 0, only the path is drawn. Otherwise the path is
+    /// drawn inside this FST, together with the states up to `neighborhood`
+    /// arcs away from it, to show the alternatives it did not take.
+    ///
+    /// # Returns
+    /// The DOT source, or `None` if the FST does not accept the input
+    pub fn path_dot(&self, input: &str, neighborhood: usize) -> Result<Option<String>> {
+        let Some(composed) = self.compose_input(input)? else {
+            return Ok(None);
+        };
+        let best_path: VectorFst<TropicalWeight> = shortest_path(&composed)
+            .map_err(|e| WeTextError::FstOperationError(format!("shortest_path failed: {}", e)))?;
+        if best_path.num_states() == 0 {
+            return Ok(None);
+        }
+        let dot = if neighborhood == 0 {
+            dot::path_to_dot(&best_path)?
+        } else {
+            with_grammar!(&self.fst, fst => dot::neighborhood_to_dot(&best_path, fst, neighborhood))?
+        };
+        Ok(Some(dot))
+    }
+
+    /// Dump the arcs of the best path for an input
+    ///
+    /// Lists the raw input and output labels of each arc with their decoding
+    /// (using the FST's symbol tables when present), followed by the output
+    /// decoded as bytes and as code points. Useful when `normalize` output
+    /// looks garbled.
+    ///
+    /// # Returns
+    /// The dump, or `None` if the FST does not accept the input
+    pub fn dump_path(&self, input: &str) -> Result<Option<String>> {
+        if input.is_empty() {
+            return Ok(None);
+        }
+        let Some(composed) = self.compose_input(input)? else {
+            return Ok(None);
+        };
+        let best_path: VectorFst<TropicalWeight> = shortest_path(&composed)
+            .map_err(|e| WeTextError::FstOperationError(format!("shortest_path failed: {}", e)))?;
+        if best_path.num_states() == 0 {
+            return Ok(None);
+        }
+        let dump = with_grammar!(&self.fst, fst => labels::dump_path(
+            &best_path,
+            fst.input_symbols().map(|table| table.as_ref()),
+            fst.output_symbols().map(|table| table.as_ref()),
+        ))?;
+        Ok(Some(dump))
+    }
+
+    /// Compose the input string with the FST
+    ///
+    /// Returns `None` if the FST does not accept the input.
+    fn compose_input(&self, input: &str) -> Result<Option<VectorFst<TropicalWeight>>> {
+        // WeText FSTs use UTF-8 byte encoding for labels; the acceptor is
+        // pooled per thread
+        let mut composed = acceptor::with_acceptor(
+            input,
+            |input_fst| with_grammar!(&self.fst, fst => compose_fst(input_fst, fst)),
+        )?;
+
+        // Check if compose result is empty (no match)
+        if composed.num_states() == 0 {
+            return Ok(None);
+        }
+        if let Some(beam) = self.search.beam {
+            search::prune(&mut composed, beam)?;
+        }
+        Ok(Some(composed))
     }
 
     /// Extract output string from linear FST
@@ -110,55 +519,65 @@ impl FstTextNormalizer {
         let path =
             decode_linear_fst(fst).map_err(|e| WeTextError::FstOperationError(e.to_string()))?;
 
-        // FST labels can be either:
-        // 1. Unicode code points (for CJK characters, code > 255)
-        // 2. UTF-8 bytes (for ASCII, code < 256)
-        // We need to handle both cases
-
-        // Check if labels look like UTF-8 bytes (all < 256) or Unicode code points
-        let has_high_codepoint = path
-            .olabels
-            .iter()
-            .any(|&label| label != EPS_LABEL && label > 255);
-
-        if has_high_codepoint {
-            // Labels are Unicode code points - convert directly
-            let output: String = path
-                .olabels
-                .iter()
-                .filter_map(|&label| {
-                    if label == EPS_LABEL {
-                        None
-                    } else {
-                        char::from_u32(label)
-                    }
-                })
-                .collect();
-            Ok(output)
-        } else {
-            // Labels are likely UTF-8 bytes - collect and decode
-            let bytes: Vec<u8> = path
-                .olabels
+        self.labels_to_string(&path.olabels)
+    }
+
+    /// Decode output labels to a string
+    ///
+    /// Labels above 255 are Unicode code points (e.g. CJK characters
+    /// inserted on epsilon arcs) and runs of lower labels are UTF-8 bytes
+    /// (e.g. copied ASCII), so one path may mix both. In a path with code
+    /// points, a byte run that is not valid UTF-8 is read as code points too,
+    /// as grammars that label every character by code point produce.
+    fn labels_to_string(&self, olabels: &[Label]) -> Result<String> {
+        let has_codepoints = olabels.iter().any(|&label| label > 255);
+        let mut output = String::new();
+        for run in olabels.chunk_by(|a, b| (*a > 255) == (*b > 255)) {
+            if run[0] > 255 {
+                output.extend(run.iter().filter_map(|&label| char::from_u32(label)));
+                continue;
+            }
+            let bytes: Vec<u8> = run
                 .iter()
-                .filter_map(|&label| {
-                    if label == EPS_LABEL {
-                        None
-                    } else {
-                        Some(label as u8)
-                    }
-                })
+                .filter(|&&label| label != EPS_LABEL)
+                .map(|&label| label as u8)
                 .collect();
+            match std::str::from_utf8(&bytes) {
+                Ok(text) => output.push_str(text),
+                Err(_) if has_codepoints => output.extend(bytes.iter().map(|&b| char::from(b))),
+                Err(_) => decode_bytes(&bytes, self.invalid_utf8, &mut output)?,
+            }
+        }
+        Ok(output)
+    }
+}
 
-            String::from_utf8(bytes).map_err(|e| {
-                WeTextError::FstOperationError(format!("Invalid UTF-8 in FST output: {}", e))
-            })
+/// Decode UTF-8 output bytes onto `output`, handling invalid sequences as
+/// `policy` says
+fn decode_bytes(bytes: &[u8], policy: InvalidUtf8, output: &mut String) -> Result<()> {
+    if let Err(e) = std::str::from_utf8(bytes) {
+        if policy == InvalidUtf8::Error {
+            return Err(WeTextError::FstOperationError(format!(
+                "Invalid UTF-8 in FST output: {}",
+                e
+            )));
+        }
+    }
+    for chunk in bytes.utf8_chunks() {
+        output.push_str(chunk.valid());
+        match policy {
+            InvalidUtf8::Replace if !chunk.invalid().is_empty() => output.push('\u{FFFD}'),
+            InvalidUtf8::Latin1 => output.extend(chunk.invalid().iter().map(|&b| char::from(b))),
+            _ => {}
         }
     }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rustfst::utils::acceptor;
 
     #[test]
     fn test_acceptor_creation() {
@@ -166,4 +585,181 @@ mod tests {
         let fst: VectorFst<TropicalWeight> = acceptor(&labels, TropicalWeight::one());
         assert_eq!(fst.num_states(), 6); // 5 chars + 1 (start state)
     }
+
+    #[test]
+    fn test_info() {
+        let normalizer = crate::Rule::replace("ab", "x").to_rewriter().unwrap();
+        let info = normalizer.info();
+        assert!(info.num_states > 0);
+        assert!(info.num_arcs >= 2);
+        assert!(info.num_final_states > 0);
+        assert!(info.start.is_some());
+        assert_eq!(info.weight_type, "tropical");
+        assert!(info.memory_bytes > 0);
+        assert!(info.to_string().contains("tropical weights"));
+    }
+
+    #[test]
+    fn test_normalize_rescored() {
+        let rule = crate::Rule::replace("a", "x")
+            .weight(1.0)
+            .or(crate::Rule::replace("a", "y").weight(2.0));
+        let normalizer = FstTextNormalizer::from_fst(rule.to_fst().unwrap());
+
+        assert_eq!(normalizer.normalize("a").unwrap(), "x");
+        let penalty = |output: &str| if output == "x" { 5.0 } else { 0.0 };
+        assert_eq!(normalizer.normalize_rescored("a", 2, penalty).unwrap(), "y");
+        assert_eq!(normalizer.normalize_rescored("a", 1, penalty).unwrap(), "x");
+        assert_eq!(normalizer.normalize_rescored("b", 2, penalty).unwrap(), "b");
+        assert_eq!(
+            normalizer.try_normalize_rescored("b", 2, penalty).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_normalize_scored() {
+        let rule = crate::Rule::replace("a", "x")
+            .weight(1.0)
+            .or(crate::Rule::replace("a", "y").weight(2.5));
+        let normalizer = FstTextNormalizer::from_fst(rule.to_fst().unwrap());
+
+        let (output, confidence) = normalizer.normalize_scored("a", 4).unwrap();
+        assert_eq!(output, "x");
+        let confidence = confidence.unwrap();
+        assert_eq!(confidence.weight, 1.0);
+        assert_eq!(confidence.margin, Some(1.5));
+
+        let (_, confidence) = normalizer.normalize_scored("a", 1).unwrap();
+        assert_eq!(confidence.unwrap().margin, None);
+        assert_eq!(
+            normalizer.normalize_scored("b", 4).unwrap(),
+            ("b".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_compose_with() {
+        let first = crate::Rule::replace("a", "b").to_rewriter().unwrap();
+        let second = crate::Rule::replace("b", "c").to_rewriter().unwrap();
+        let composed = first.compose_with(&second).unwrap();
+        assert_eq!(composed.normalize("xay").unwrap(), "xcy");
+    }
+
+    #[test]
+    fn test_search_limits() {
+        let rule = crate::Rule::replace("a", "x")
+            .weight(1.0)
+            .or(crate::Rule::replace("a", "y").weight(1.5))
+            .or(crate::Rule::replace("a", "z").weight(9.0));
+        let normalizer = FstTextNormalizer::from_fst(rule.to_fst().unwrap());
+        let outputs = |normalizer: &FstTextNormalizer| {
+            normalizer
+                .normalize_nbest("a", 4)
+                .unwrap()
+                .into_iter()
+                .map(|(output, _)| output)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(outputs(&normalizer), ["x", "y", "z"]);
+
+        let pruned = normalizer.with_search(SearchLimits {
+            beam: Some(1.0),
+            ..Default::default()
+        });
+        assert_eq!(outputs(&pruned), ["x", "y"]);
+
+        let early = pruned.with_search(SearchLimits {
+            early_stop: true,
+            ..Default::default()
+        });
+        assert_eq!(early.normalize("a").unwrap(), "x");
+        assert_eq!(early.normalize("b").unwrap(), "b");
+
+        let rewriter = crate::Rule::replace("ab", "x")
+            .to_rewriter()
+            .unwrap()
+            .with_search(SearchLimits {
+                early_stop: true,
+                ..Default::default()
+            });
+        assert_eq!(rewriter.normalize("zabzab").unwrap(), "zxzx");
+    }
+
+    #[test]
+    fn test_compact() {
+        let rule = crate::Rule::replace("ab", "x").or(crate::Rule::replace("a", "y"));
+        let mut normalizer = rule.to_rewriter().unwrap();
+        let before = normalizer.info();
+        let expected = normalizer.normalize("zabza").unwrap();
+        normalizer.compact();
+        assert!(normalizer.is_compact());
+        let after = normalizer.info();
+        assert_eq!(after.num_states, before.num_states);
+        assert_eq!(after.num_arcs, before.num_arcs);
+        assert!(after.memory_bytes < before.memory_bytes);
+        assert_eq!(normalizer.normalize("zabza").unwrap(), expected);
+        assert!(normalizer.path_dot("zabza", 1).unwrap().is_some());
+
+        let next = crate::Rule::replace("x", "w").to_rewriter().unwrap();
+        let composed = normalizer.compose_with(&next).unwrap();
+        assert_eq!(composed.normalize("ab").unwrap(), "w");
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let olabels: Vec<Label> = vec![b'x' as Label, EPS_LABEL, 0xE9, b'y' as Label];
+        let normalizer = FstTextNormalizer::from_fst(VectorFst::new());
+        assert!(normalizer.labels_to_string(&olabels).is_err());
+        for (policy, expected) in [
+            (InvalidUtf8::Replace, "x\u{FFFD}y"),
+            (InvalidUtf8::Skip, "xy"),
+            (InvalidUtf8::Latin1, "xéy"),
+        ] {
+            let normalizer =
+                FstTextNormalizer::from_fst(VectorFst::new()).with_invalid_utf8(policy);
+            assert_eq!(normalizer.labels_to_string(&olabels).unwrap(), expected);
+            // Valid sequences around the invalid one are kept
+            let mut valid = olabels.clone();
+            valid.extend("é".bytes().map(Label::from));
+            let output = normalizer.labels_to_string(&valid).unwrap();
+            assert_eq!(output, format!("{}é", expected));
+        }
+    }
+
+    #[test]
+    fn test_mixed_labels() {
+        let normalizer = FstTextNormalizer::from_fst(VectorFst::new());
+        let decode = |text: &[&str]| {
+            let mut olabels = Vec::new();
+            for (i, piece) in text.iter().enumerate() {
+                // Even pieces as code points, odd ones as UTF-8 bytes
+                if i % 2 == 0 {
+                    olabels.extend(piece.chars().map(Label::from));
+                } else {
+                    olabels.extend(piece.bytes().map(Label::from));
+                }
+                olabels.push(EPS_LABEL);
+            }
+            normalizer.labels_to_string(&olabels).unwrap()
+        };
+        assert_eq!(decode(&["价格", "5 é", "元"]), "价格5 é元");
+        assert_eq!(decode(&["", "ab", "零"]), "ab零");
+        // Latin-1 characters labeled by code point next to CJK ones
+        assert_eq!(decode(&["café价", ""]), "café价");
+    }
+
+    #[test]
+    fn test_try_normalize() {
+        let fst = crate::Rule::replace("a", "b").to_fst().unwrap();
+        let normalizer = FstTextNormalizer::from_fst(fst);
+        assert_eq!(normalizer.try_normalize("a").unwrap().as_deref(), Some("b"));
+        assert_eq!(normalizer.try_normalize("c").unwrap(), None);
+        assert_eq!(normalizer.normalize("c").unwrap(), "c");
+        let early = normalizer.with_search(SearchLimits {
+            beam: None,
+            early_stop: true,
+        });
+        assert_eq!(early.try_normalize("c").unwrap(), None);
+    }
 }
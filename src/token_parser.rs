@@ -3,9 +3,13 @@
 //! This module parses the tagged token output from tagger FST and reorders
 //! fields according to predefined orders for each token type.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-use crate::config::{Language, Operator};
+#[cfg(any(feature = "lang-zh", feature = "lang-ja", feature = "lang-en"))]
+use once_cell::sync::Lazy;
+
+use crate::config::{EnVariant, Language, Operator};
 use crate::error::{Result, WeTextError};
 
 /// Token structure representing a tagged entity
@@ -15,7 +19,7 @@ pub struct Token {
     pub name: String,
     /// Order of fields as they were parsed
     pub order: Vec<String>,
-    /// Field key-value pairs
+    /// Field key-value pairs (values are unescaped)
     pub members: HashMap<String, String>,
 }
 
@@ -31,28 +35,61 @@ impl Token {
 
     /// Append a key-value pair to the token
     pub fn append(&mut self, key: &str, value: &str) {
-        self.order.push(key.to_string());
-        self.members.insert(key.to_string(), value.to_string());
+        self.push(key.to_string(), value.to_string());
+    }
+
+    /// Append an owned key-value pair, copying only the key for `order`
+    fn push(&mut self, key: String, value: String) {
+        self.order.push(key.clone());
+        self.members.insert(key, value);
+    }
+
+    /// Set a field, replacing its value if present or appending it otherwise
+    pub fn set(&mut self, key: &str, value: &str) {
+        if self.members.contains_key(key) {
+            self.members.insert(key.to_string(), value.to_string());
+        } else {
+            self.append(key, value);
+        }
+    }
+
+    /// Remove a field, returning its value
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.order.retain(|k| k != key);
+        self.members.remove(key)
+    }
+
+    /// Get a field value
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.members.get(key).map(String::as_str)
     }
 
     /// Convert token to string with specified field order
     pub fn to_string_with_order(&self, orders: &HashMap<String, Vec<String>>) -> String {
-        let mut output = format!("{} {{", self.name);
+        let capacity = self.name.len()
+            + 4
+            + self
+                .members
+                .iter()
+                .map(|(key, value)| key.len() + value.len() + 5)
+                .sum::<usize>();
+        let mut output = String::with_capacity(capacity);
+        output.push_str(&self.name);
+        output.push_str(" {");
 
         // Determine field order
-        let order = if let Some(defined_order) = orders.get(&self.name) {
-            if self.members.get("preserve_order") != Some(&"true".to_string()) {
-                defined_order.clone()
-            } else {
-                self.order.clone()
-            }
-        } else {
-            self.order.clone()
+        let order = match orders.get(&self.name) {
+            Some(defined_order) if self.get("preserve_order") != Some("true") => defined_order,
+            _ => &self.order,
         };
 
-        for key in &order {
+        for key in order {
             if let Some(value) = self.members.get(key) {
-                output.push_str(&format!(" {}: \"{}\"", key, value));
+                output.push(' ');
+                output.push_str(key);
+                output.push_str(": \"");
+                escape_value_into(value, &mut output);
+                output.push('"');
             }
         }
 
@@ -61,11 +98,41 @@ impl Token {
     }
 }
 
+/// Escape a field value for serialization, appending it to `output`
+///
+/// Backslashes and double quotes are escaped so that parsing the serialized
+/// token gives back the original value.
+fn escape_value_into(value: &str, output: &mut String) {
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+}
+
+/// Field orders of a token type name
+type Orders = HashMap<String, Vec<String>>;
+
+/// Built-in order tables, built once and shared by every parser
+#[cfg(any(feature = "lang-zh", feature = "lang-ja"))]
+static TN_ORDERS: Lazy<Orders> = Lazy::new(TokenParser::tn_orders);
+#[cfg(feature = "lang-en")]
+static EN_TN_ORDERS: Lazy<Orders> = Lazy::new(TokenParser::en_tn_orders);
+#[cfg(any(feature = "lang-zh", feature = "lang-ja"))]
+static ITN_ORDERS: Lazy<Orders> = Lazy::new(TokenParser::itn_orders);
+
 /// Token parser for reordering FST output fields
 ///
-/// Parses tagger FST output and reorders fields according to predefined orders
+/// Parses tagger FST output and reorders fields according to predefined orders.
+/// The built-in orders are shared, so creating a parser is cheap until
+/// `with_order` copies them.
+#[derive(Debug, Clone)]
 pub struct TokenParser {
-    orders: HashMap<String, Vec<String>>,
+    lang: Language,
+    operator: Operator,
+    orders: Cow<'static, Orders>,
+    strict: bool,
 }
 
 impl TokenParser {
@@ -79,21 +146,85 @@ impl TokenParser {
         // English ITN is not supported in Python (raises NotImplementedError),
         // so we return empty HashMap which means fields keep original order
         let orders = match (lang, operator) {
-            (Language::En, Operator::Tn) => Self::en_tn_orders(),
-            (Language::Zh | Language::Ja, Operator::Tn) => Self::tn_orders(),
-            (Language::Zh | Language::Ja, Operator::Itn) => Self::itn_orders(),
-            _ => HashMap::new(), // English ITN: not supported, use original order
+            #[cfg(feature = "lang-en")]
+            (Language::En, Operator::Tn) => Cow::Borrowed(&*EN_TN_ORDERS),
+            #[cfg(any(feature = "lang-zh", feature = "lang-ja"))]
+            (Language::Zh | Language::Ja, Operator::Tn) => Cow::Borrowed(&*TN_ORDERS),
+            #[cfg(any(feature = "lang-zh", feature = "lang-ja"))]
+            (Language::Zh | Language::Ja, Operator::Itn) => Cow::Borrowed(&*ITN_ORDERS),
+            _ => Cow::Owned(HashMap::new()), // English ITN: not supported, use original order
         };
 
-        Self { orders }
+        Self {
+            lang,
+            operator,
+            orders,
+            strict: false,
+        }
+    }
+
+    /// Report malformed input instead of passing it through
+    ///
+    /// By default `reorder` returns its input unchanged when it can't be
+    /// parsed, which hides grammar bugs. In strict mode it fails with a
+    /// `TokenParseError` giving the character position of the problem.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Use the field orders of an English variant
+    ///
+    /// British English puts the month field before the day field in dates,
+    /// the opposite of the American order. Has no effect on parsers for other
+    /// languages or for ITN.
+    pub fn with_en_variant(self, variant: EnVariant) -> Self {
+        if self.lang != Language::En || self.operator != Operator::Tn {
+            return self;
+        }
+        match variant {
+            EnVariant::American => self,
+            EnVariant::British | EnVariant::BritishLongScale => {
+                self.with_order("date", &["preserve_order", "text", "month", "day", "year"])
+            }
+        }
+    }
+
+    /// Register the field order for a token type
+    ///
+    /// Overrides the built-in order if the token type already has one. Use this
+    /// for token types emitted by custom grammars.
+    ///
+    /// # Example
+    /// ```rust
+    /// use wetext_rs::{Language, Operator, TokenParser};
+    ///
+    /// let parser = TokenParser::new(Language::Zh, Operator::Tn).with_order("measure", &["value", "unit"]);
+    /// let output = parser.reorder(r#"measure { unit: "千克" value: "5" }"#).unwrap();
+    /// assert_eq!(output, r#"measure { value: "5" unit: "千克" }"#);
+    /// ```
+    pub fn with_order(mut self, name: &str, order: &[&str]) -> Self {
+        self.orders.to_mut().insert(
+            name.to_string(),
+            order.iter().map(|key| key.to_string()).collect(),
+        );
+        self
     }
 
     /// Chinese/Japanese TN field orders
-    fn tn_orders() -> HashMap<String, Vec<String>> {
+    #[cfg(any(feature = "lang-zh", feature = "lang-ja"))]
+    fn tn_orders() -> Orders {
         let mut m = HashMap::new();
         m.insert(
             "date".to_string(),
-            vec!["year", "month", "day"]
+            vec!["era", "year", "month", "day"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+        m.insert(
+            "duration".to_string(),
+            vec!["hour", "minute", "second"]
                 .into_iter()
                 .map(String::from)
                 .collect(),
@@ -130,7 +261,8 @@ impl TokenParser {
     }
 
     /// English TN field orders
-    fn en_tn_orders() -> HashMap<String, Vec<String>> {
+    #[cfg(feature = "lang-en")]
+    fn en_tn_orders() -> Orders {
         let mut m = HashMap::new();
         m.insert(
             "date".to_string(),
@@ -139,6 +271,13 @@ impl TokenParser {
                 .map(String::from)
                 .collect(),
         );
+        m.insert(
+            "duration".to_string(),
+            vec!["hours", "minutes", "seconds"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
         m.insert(
             "money".to_string(),
             vec![
@@ -146,6 +285,7 @@ impl TokenParser {
                 "fractional_part",
                 "quantity",
                 "currency_maj",
+                "currency_min",
             ]
             .into_iter()
             .map(String::from)
@@ -155,11 +295,19 @@ impl TokenParser {
     }
 
     /// ITN field orders
-    fn itn_orders() -> HashMap<String, Vec<String>> {
+    #[cfg(any(feature = "lang-zh", feature = "lang-ja"))]
+    fn itn_orders() -> Orders {
         let mut m = HashMap::new();
         m.insert(
             "date".to_string(),
-            vec!["year", "month", "day"]
+            vec!["era", "year", "month", "day"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+        m.insert(
+            "duration".to_string(),
+            vec!["hour", "minute", "second"]
                 .into_iter()
                 .map(String::from)
                 .collect(),
@@ -222,6 +370,7 @@ impl TokenParser {
                     .collect();
                 Ok(output.join(" "))
             }
+            Err(e) if self.strict => Err(e),
             Err(_) => {
                 // If parsing fails, return original input
                 Ok(input.to_string())
@@ -229,10 +378,26 @@ impl TokenParser {
         }
     }
 
+    /// Parse error at character `index`, quoting the input around it
+    fn error(&self, message: &str, chars: &[char], index: usize) -> WeTextError {
+        let context: String = chars[index.saturating_sub(10)..(index + 10).min(chars.len())]
+            .iter()
+            .collect();
+        WeTextError::TokenParseError(format!(
+            "{} at position {} (near \"{}\")",
+            message, index, context
+        ))
+    }
+
+    /// Serialize a single token using this parser's field orders
+    pub(crate) fn serialize(&self, token: &Token) -> String {
+        token.to_string_with_order(&self.orders)
+    }
+
     /// Parse token string into structured tokens
     ///
     /// Expected format: `token_name { key1: "value1" key2: "value2" }`
-    fn parse(&self, input: &str) -> Result<Vec<Token>> {
+    pub(crate) fn parse(&self, input: &str) -> Result<Vec<Token>> {
         let mut tokens = Vec::new();
         let chars: Vec<char> = input.chars().collect();
         let mut index = 0;
@@ -268,11 +433,21 @@ impl TokenParser {
             }
 
             // Skip whitespace and opening brace '{'
+            while index < chars.len() && chars[index].is_whitespace() {
+                index += 1;
+            }
+            if self.strict && chars.get(index) != Some(&'{') {
+                return Err(self.error("expected '{'", &chars, index));
+            }
             while index < chars.len() && (chars[index].is_whitespace() || chars[index] == '{') {
                 index += 1;
             }
 
-            let mut token = Token::new(&name);
+            let mut token = Token {
+                name,
+                order: Vec::new(),
+                members: HashMap::new(),
+            };
 
             // Parse key-value pairs
             loop {
@@ -282,6 +457,9 @@ impl TokenParser {
                 }
 
                 // Check for end of token or end of input
+                if self.strict && index >= chars.len() {
+                    return Err(self.error("unterminated token, expected '}'", &chars, index));
+                }
                 if index >= chars.len() || chars[index] == '}' {
                     if index < chars.len() {
                         index += 1; // Skip '}'
@@ -300,6 +478,9 @@ impl TokenParser {
 
                 // Skip empty keys (can happen with malformed input)
                 if key.is_empty() {
+                    if self.strict {
+                        return Err(self.error("expected a field name", &chars, index));
+                    }
                     // Skip the problematic character to avoid infinite loop
                     if index < chars.len() && chars[index] != '}' {
                         index += 1;
@@ -308,6 +489,9 @@ impl TokenParser {
                 }
 
                 // Skip ':' and spaces
+                if self.strict && chars.get(index) != Some(&':') {
+                    return Err(self.error("expected ':'", &chars, index));
+                }
                 while index < chars.len() && (chars[index] == ':' || chars[index] == ' ') {
                     index += 1;
                 }
@@ -315,30 +499,40 @@ impl TokenParser {
                 // Skip opening quote '"'
                 if index < chars.len() && chars[index] == '"' {
                     index += 1;
+                } else if self.strict {
+                    return Err(self.error("expected '\"'", &chars, index));
                 }
 
-                // Parse value (handle escape sequences)
+                // Parse value: `\"` and `\\` are unescaped, other backslashes are kept
                 let mut value = String::new();
                 let mut escape = false;
                 while index < chars.len() && (escape || chars[index] != '"') {
                     if escape {
+                        if chars[index] != '"' && chars[index] != '\\' {
+                            value.push('\\');
+                        }
                         value.push(chars[index]);
                         escape = false;
                     } else if chars[index] == '\\' {
                         escape = true;
-                        value.push(chars[index]);
                     } else {
                         value.push(chars[index]);
                     }
                     index += 1;
                 }
+                if escape {
+                    // Trailing backslash at end of input
+                    value.push('\\');
+                }
 
                 // Skip closing quote '"'
                 if index < chars.len() && chars[index] == '"' {
                     index += 1;
+                } else if self.strict {
+                    return Err(self.error("unterminated value", &chars, index));
                 }
 
-                token.append(&key, &value);
+                token.push(key, value);
             }
 
             tokens.push(token);
@@ -361,10 +555,101 @@ mod tests {
         assert!(result.contains("year: \"2024\""));
     }
 
+    #[test]
+    fn test_registered_order() {
+        let parser = TokenParser::new(Language::Zh, Operator::Tn)
+            .with_order("date", &["day", "month", "year"])
+            .with_order("custom", &["b", "a"]);
+        let result = parser
+            .reorder(r#"date { year: "2024" day: "15" } custom { a: "1" b: "2" }"#)
+            .unwrap();
+        assert_eq!(
+            result,
+            r#"date { day: "15" year: "2024" } custom { b: "2" a: "1" }"#
+        );
+    }
+
     #[test]
     fn test_empty_input() {
         let parser = TokenParser::new(Language::Zh, Operator::Tn);
         assert_eq!(parser.reorder("").unwrap(), "");
         assert_eq!(parser.reorder("   ").unwrap(), "");
     }
+
+    #[test]
+    fn test_escaped_values() {
+        let parser = TokenParser::new(Language::Zh, Operator::Tn);
+        let input = r#"char { value: "\"" } char { value: "\\" } char { value: "}{" }"#;
+        let tokens = parser.parse(input).unwrap();
+        let values: Vec<&str> = tokens.iter().map(|t| t.members["value"].as_str()).collect();
+        assert_eq!(values, vec!["\"", "\\", "}{"]);
+
+        // Serialization escapes again, so the verbalizer sees the original text
+        assert_eq!(parser.reorder(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_round_trip_fuzz() {
+        // Deterministic pseudo-random values built from characters that are
+        // significant to the token syntax
+        const ALPHABET: &[char] = &['"', '\\', '{', '}', ':', ' ', 'a', '1', '年', '_'];
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % bound as u64) as usize
+        };
+
+        let parser = TokenParser::new(Language::Zh, Operator::Tn);
+        let orders = HashMap::new();
+        for _ in 0..500 {
+            let mut token = Token::new("custom");
+            for key in ["first", "second", "third"].iter().take(1 + next(3)) {
+                let len = next(8);
+                let value: String = (0..len).map(|_| ALPHABET[next(ALPHABET.len())]).collect();
+                token.append(key, &value);
+            }
+
+            let serialized = token.to_string_with_order(&orders);
+            let parsed = parser.parse(&serialized).unwrap();
+            assert_eq!(parsed.len(), 1, "{}", serialized);
+            assert_eq!(parsed[0].name, token.name);
+            assert_eq!(parsed[0].order, token.order);
+            assert_eq!(parsed[0].members, token.members, "{}", serialized);
+        }
+    }
+
+    #[test]
+    fn test_en_variant_date_order() {
+        let input = r#"date { day: "15" month: "january" }"#;
+        let american = TokenParser::new(Language::En, Operator::Tn);
+        assert_eq!(american.reorder(input).unwrap(), input);
+
+        let british =
+            TokenParser::new(Language::En, Operator::Tn).with_en_variant(EnVariant::British);
+        assert_eq!(
+            british.reorder(input).unwrap(),
+            r#"date { month: "january" day: "15" }"#
+        );
+    }
+
+    #[test]
+    fn test_strict_mode() {
+        let malformed = r#"date { year "2024" }"#;
+        let parser = TokenParser::new(Language::Zh, Operator::Tn);
+        assert!(parser.reorder(malformed).is_ok());
+
+        let strict = TokenParser::new(Language::Zh, Operator::Tn).with_strict(true);
+        let err = strict.reorder(malformed).unwrap_err();
+        assert!(
+            matches!(err, WeTextError::TokenParseError(ref m) if m.starts_with("expected ':' at position 11"))
+        );
+        assert!(strict.reorder(r#"date { year: "2024 }"#).is_err());
+        assert!(strict.reorder(r#"date { year: "2024""#).is_err());
+        assert_eq!(
+            strict.reorder(r#"date { day: "1" year: "2024" }"#).unwrap(),
+            r#"date { year: "2024" day: "1" }"#
+        );
+    }
 }
@@ -0,0 +1,165 @@
+//! Capitalization of lowercase ITN output
+//!
+//! ASR transcripts are usually lowercase, so ITN output reads "i paid
+//! $5 on monday". This stage restores the casing display text needs:
+//! sentence-initial capitals, "I", month and day names, and known acronyms.
+//! Words that already contain capitals are left alone.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Latin words, with an optional apostrophe suffix ("i'm")
+static WORD: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z]+(?:'[A-Za-z]+)?").unwrap());
+
+/// Sentence boundaries: terminal punctuation before whitespace, CJK
+/// terminal punctuation, or a line break
+static SENTENCE_END: Lazy<Regex> = Lazy::new(|| Regex::new(r"[.!?]\s|[。！？]|\n").unwrap());
+
+const DAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// Month names that are never ordinary words
+const MONTHS: [&str; 10] = [
+    "january",
+    "february",
+    "april",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// Month names that are also ordinary words, capitalized next to a number
+const AMBIGUOUS_MONTHS: [&str; 2] = ["march", "may"];
+
+const ACRONYMS: &[&str] = &[
+    "AI", "API", "ATM", "BBC", "CEO", "CFO", "CNN", "CTO", "DNA", "EU", "FAQ", "FBI", "GPS",
+    "HTML", "ID", "IBM", "NASA", "NBA", "NFL", "OK", "PDF", "SMS", "TV", "UK", "URL", "USA", "USB",
+];
+
+/// Capitalize the first letter of a word
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Whether the non-space text next to a word contains a digit
+fn next_to_number(before: &str, after: &str) -> bool {
+    let before = before.trim_end().rsplit(char::is_whitespace).next();
+    let after = after.trim_start().split(char::is_whitespace).next();
+    [before, after]
+        .into_iter()
+        .flatten()
+        .any(|word| word.chars().any(|c| c.is_ascii_digit()))
+}
+
+/// Casing of a lowercase word, ignoring its position in the sentence
+fn case_word(word: &str, before: &str, after: &str) -> Option<String> {
+    if word == "i" {
+        return Some("I".to_string());
+    }
+    if let Some(suffix) = word.strip_prefix("i'") {
+        if matches!(suffix, "m" | "ll" | "ve" | "d") {
+            return Some(format!("I'{}", suffix));
+        }
+    }
+    if let Some(acronym) = ACRONYMS.iter().find(|a| a.eq_ignore_ascii_case(word)) {
+        return Some(acronym.to_string());
+    }
+    if DAYS.contains(&word)
+        || MONTHS.contains(&word)
+        || (AMBIGUOUS_MONTHS.contains(&word) && next_to_number(before, after))
+    {
+        return Some(capitalize(word));
+    }
+    None
+}
+
+/// Restore capitalization of lowercase English text
+///
+/// Applied to ITN output by `NormalizerConfig::truecase`; call it directly
+/// on the output of other English ITN systems.
+///
+/// # Example
+/// ```rust
+/// use wetext_rs::truecase;
+///
+/// assert_eq!(truecase("i paid $5 on monday"), "I paid $5 on Monday");
+/// ```
+pub fn truecase(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    let mut sentence_start = true;
+    let mut previous = "";
+    for m in WORD.find_iter(text) {
+        let gap = &text[last..m.start()];
+        // A period after a single letter ends an abbreviation ("p.m."), not a sentence
+        let abbreviation = previous.len() == 1 && previous != "i" && gap.starts_with('.');
+        match SENTENCE_END.find_iter(gap).last() {
+            Some(end) if !abbreviation || gap[end.end()..].contains('\n') => {
+                sentence_start = !gap[end.end()..].chars().any(char::is_alphanumeric);
+            }
+            _ => {
+                if gap.chars().any(char::is_alphanumeric) {
+                    sentence_start = false;
+                }
+            }
+        }
+
+        let word = m.as_str();
+        let cased = if word.chars().all(|c| c.is_ascii_lowercase() || c == '\'') {
+            let cased = case_word(word, &text[..m.start()], &text[m.end()..]);
+            match (cased, sentence_start) {
+                (Some(cased), _) => Some(cased),
+                (None, true) => Some(capitalize(word)),
+                (None, false) => None,
+            }
+        } else {
+            None
+        };
+        output.push_str(gap);
+        output.push_str(cased.as_deref().unwrap_or(word));
+
+        sentence_start = false;
+        previous = word;
+        last = m.end();
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truecase() {
+        assert_eq!(
+            truecase("i paid $5 on monday. then i'm off to the usa"),
+            "I paid $5 on Monday. Then I'm off to the USA"
+        );
+        assert_eq!(
+            truecase("we may meet on may 5 or 1 march"),
+            "We may meet on May 5 or 1 March"
+        );
+        assert_eq!(
+            truecase("see you at 3 p.m. tomorrow! ok?"),
+            "See you at 3 p.m. tomorrow! OK?"
+        );
+        assert_eq!(truecase("5 apples. iPhone sales"), "5 apples. iPhone sales");
+        assert_eq!(truecase("第一行\nhello"), "第一行\nHello");
+    }
+}
@@ -0,0 +1,391 @@
+//! Unit ontology for measures
+//!
+//! Maps the written and spoken forms of common units ("km", "千米",
+//! "kilometers") to a canonical symbol and the physical quantity they
+//! measure, so extracted measures can be compared across languages.
+
+/// Physical quantity a unit measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quantity {
+    /// Distance ("km", "英尺")
+    Length,
+    /// Weight ("kg", "斤")
+    Mass,
+    /// Duration ("h", "秒")
+    Time,
+    /// Length over time ("km/h", "mph")
+    Speed,
+    /// Surface ("m²", "亩")
+    Area,
+    /// Capacity ("L", "立方米")
+    Volume,
+    /// Temperature ("℃", "°F")
+    Temperature,
+    /// Digital storage ("GB")
+    Data,
+    /// Frequency ("Hz")
+    Frequency,
+    /// Power ("W")
+    Power,
+    /// Energy ("kWh", "kcal")
+    Energy,
+    /// Pressure ("Pa")
+    Pressure,
+}
+
+/// Units as (canonical symbol, quantity, lowercase written and spoken forms)
+const UNITS: &[(&str, Quantity, &[&str])] = &[
+    (
+        "km",
+        Quantity::Length,
+        &[
+            "km",
+            "千米",
+            "公里",
+            "キロメートル",
+            "kilometer",
+            "kilometers",
+            "kilometre",
+            "kilometres",
+        ],
+    ),
+    (
+        "m",
+        Quantity::Length,
+        &["m", "米", "メートル", "meter", "meters", "metre", "metres"],
+    ),
+    (
+        "cm",
+        Quantity::Length,
+        &[
+            "cm",
+            "厘米",
+            "センチメートル",
+            "centimeter",
+            "centimeters",
+            "centimetre",
+            "centimetres",
+        ],
+    ),
+    (
+        "mm",
+        Quantity::Length,
+        &[
+            "mm",
+            "毫米",
+            "ミリメートル",
+            "millimeter",
+            "millimeters",
+            "millimetre",
+            "millimetres",
+        ],
+    ),
+    (
+        "mi",
+        Quantity::Length,
+        &["mi", "英里", "マイル", "mile", "miles"],
+    ),
+    (
+        "ft",
+        Quantity::Length,
+        &["ft", "英尺", "フィート", "foot", "feet"],
+    ),
+    (
+        "in",
+        Quantity::Length,
+        &["in", "英寸", "インチ", "inch", "inches"],
+    ),
+    (
+        "kg",
+        Quantity::Mass,
+        &["kg", "千克", "公斤", "キログラム", "kilogram", "kilograms"],
+    ),
+    ("g", Quantity::Mass, &["g", "克", "グラム", "gram", "grams"]),
+    (
+        "mg",
+        Quantity::Mass,
+        &["mg", "毫克", "ミリグラム", "milligram", "milligrams"],
+    ),
+    (
+        "t",
+        Quantity::Mass,
+        &["t", "吨", "噸", "トン", "ton", "tons", "tonne", "tonnes"],
+    ),
+    (
+        "lb",
+        Quantity::Mass,
+        &["lb", "lbs", "磅", "pound", "pounds"],
+    ),
+    ("jin", Quantity::Mass, &["斤"]),
+    (
+        "h",
+        Quantity::Time,
+        &["h", "小时", "小時", "時間", "hour", "hours"],
+    ),
+    (
+        "min",
+        Quantity::Time,
+        &["min", "分钟", "分鐘", "minute", "minutes"],
+    ),
+    (
+        "s",
+        Quantity::Time,
+        &["s", "sec", "秒", "秒钟", "秒鐘", "second", "seconds"],
+    ),
+    (
+        "ms",
+        Quantity::Time,
+        &["ms", "毫秒", "ミリ秒", "millisecond", "milliseconds"],
+    ),
+    (
+        "km/h",
+        Quantity::Speed,
+        &[
+            "km/h",
+            "kmh",
+            "kph",
+            "公里每小时",
+            "千米每小时",
+            "kilometers per hour",
+            "kilometres per hour",
+        ],
+    ),
+    (
+        "m/s",
+        Quantity::Speed,
+        &["m/s", "米每秒", "meters per second", "metres per second"],
+    ),
+    (
+        "mph",
+        Quantity::Speed,
+        &["mph", "mi/h", "英里每小时", "miles per hour"],
+    ),
+    (
+        "m²",
+        Quantity::Area,
+        &[
+            "m²",
+            "m2",
+            "平方米",
+            "平米",
+            "平方メートル",
+            "square meter",
+            "square meters",
+            "square metre",
+            "square metres",
+        ],
+    ),
+    (
+        "km²",
+        Quantity::Area,
+        &[
+            "km²",
+            "km2",
+            "平方公里",
+            "平方千米",
+            "平方キロメートル",
+            "square kilometer",
+            "square kilometers",
+            "square kilometre",
+            "square kilometres",
+        ],
+    ),
+    (
+        "ha",
+        Quantity::Area,
+        &["ha", "公顷", "公頃", "ヘクタール", "hectare", "hectares"],
+    ),
+    ("mu", Quantity::Area, &["亩", "畝"]),
+    (
+        "L",
+        Quantity::Volume,
+        &[
+            "l",
+            "升",
+            "公升",
+            "リットル",
+            "liter",
+            "liters",
+            "litre",
+            "litres",
+        ],
+    ),
+    (
+        "mL",
+        Quantity::Volume,
+        &[
+            "ml",
+            "毫升",
+            "ミリリットル",
+            "milliliter",
+            "milliliters",
+            "millilitre",
+            "millilitres",
+        ],
+    ),
+    (
+        "m³",
+        Quantity::Volume,
+        &[
+            "m³",
+            "m3",
+            "立方米",
+            "cubic meter",
+            "cubic meters",
+            "cubic metre",
+            "cubic metres",
+        ],
+    ),
+    (
+        "°C",
+        Quantity::Temperature,
+        &["°c", "℃", "摄氏度", "攝氏度", "celsius", "degrees celsius"],
+    ),
+    (
+        "°F",
+        Quantity::Temperature,
+        &[
+            "°f",
+            "℉",
+            "华氏度",
+            "華氏度",
+            "fahrenheit",
+            "degrees fahrenheit",
+        ],
+    ),
+    (
+        "KB",
+        Quantity::Data,
+        &["kb", "千字节", "キロバイト", "kilobyte", "kilobytes"],
+    ),
+    (
+        "MB",
+        Quantity::Data,
+        &["mb", "兆字节", "メガバイト", "megabyte", "megabytes"],
+    ),
+    (
+        "GB",
+        Quantity::Data,
+        &["gb", "吉字节", "ギガバイト", "gigabyte", "gigabytes"],
+    ),
+    (
+        "TB",
+        Quantity::Data,
+        &["tb", "太字节", "テラバイト", "terabyte", "terabytes"],
+    ),
+    (
+        "Hz",
+        Quantity::Frequency,
+        &["hz", "赫兹", "赫茲", "ヘルツ", "hertz"],
+    ),
+    (
+        "kHz",
+        Quantity::Frequency,
+        &["khz", "千赫", "千赫兹", "kilohertz"],
+    ),
+    (
+        "MHz",
+        Quantity::Frequency,
+        &["mhz", "兆赫", "兆赫兹", "megahertz"],
+    ),
+    (
+        "GHz",
+        Quantity::Frequency,
+        &["ghz", "吉赫", "吉赫兹", "gigahertz"],
+    ),
+    (
+        "W",
+        Quantity::Power,
+        &["w", "瓦", "瓦特", "ワット", "watt", "watts"],
+    ),
+    (
+        "kW",
+        Quantity::Power,
+        &["kw", "千瓦", "キロワット", "kilowatt", "kilowatts"],
+    ),
+    (
+        "kWh",
+        Quantity::Energy,
+        &["kwh", "千瓦时", "千瓦時", "kilowatt hour", "kilowatt hours"],
+    ),
+    (
+        "kcal",
+        Quantity::Energy,
+        &[
+            "kcal",
+            "千卡",
+            "大卡",
+            "キロカロリー",
+            "kilocalorie",
+            "kilocalories",
+        ],
+    ),
+    (
+        "J",
+        Quantity::Energy,
+        &["j", "焦耳", "ジュール", "joule", "joules"],
+    ),
+    (
+        "Pa",
+        Quantity::Pressure,
+        &["pa", "帕", "帕斯卡", "パスカル", "pascal", "pascals"],
+    ),
+    (
+        "kPa",
+        Quantity::Pressure,
+        &["kpa", "千帕", "キロパスカル", "kilopascal", "kilopascals"],
+    ),
+];
+
+/// Canonical symbol and quantity of a unit name
+pub(crate) fn lookup(name: &str) -> Option<(&'static str, Quantity)> {
+    let name = name.trim().to_lowercase();
+    UNITS
+        .iter()
+        .find(|(_, _, names)| names.contains(&name.as_str()))
+        .map(|(symbol, quantity, _)| (*symbol, *quantity))
+}
+
+/// Split a known unit off the end of a measure ("五千克" -> ("五", "千克"))
+///
+/// Tries the longest unit first, so "千克" wins over "克". `is_amount`
+/// decides whether the remaining head is a valid amount.
+pub(crate) fn split_unit(value: &str, is_amount: impl Fn(&str) -> bool) -> Option<(&str, &str)> {
+    let lower = value.to_lowercase();
+    if lower.len() != value.len() {
+        return None;
+    }
+    UNITS
+        .iter()
+        .flat_map(|(_, _, names)| names.iter())
+        .filter(|name| name.len() < lower.len() && lower.ends_with(*name))
+        .map(|name| value.split_at(value.len() - name.len()))
+        .filter(|(head, _)| is_amount(head.trim_end()))
+        .max_by_key(|(_, unit)| unit.len())
+        .map(|(head, unit)| (head.trim_end(), unit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup("千米"), Some(("km", Quantity::Length)));
+        assert_eq!(lookup("KM/H"), Some(("km/h", Quantity::Speed)));
+        assert_eq!(lookup(" kilograms "), Some(("kg", Quantity::Mass)));
+        assert_eq!(lookup("个"), None);
+    }
+
+    #[test]
+    fn test_split_unit() {
+        let is_amount = |head: &str| !head.is_empty() && !head.ends_with('千');
+        assert_eq!(split_unit("五千克", is_amount), Some(("五", "千克")));
+        assert_eq!(split_unit("100km/h", is_amount), Some(("100", "km/h")));
+        assert_eq!(
+            split_unit("5 Kilograms", is_amount),
+            Some(("5", "Kilograms"))
+        );
+        assert_eq!(split_unit("五个", is_amount), None);
+    }
+}
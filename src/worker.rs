@@ -0,0 +1,348 @@
+//! Normalizer running on a dedicated thread
+//!
+//! `Normalizer` needs `&mut self` to load FSTs lazily, so it can't be shared
+//! between threads directly. `NormalizerWorker` moves it onto its own thread
+//! and feeds it jobs through a bounded queue: callers get a receiver for each
+//! result, and submitting blocks while the queue is full.
+//!
+//! `ShardedNormalizer` runs one or more workers per language, each with its
+//! own FST cache, and routes each text to a worker of its language. Mixed
+//! zh/en/ja traffic then never waits on a shared normalizer, and each
+//! thread only keeps the FSTs of one language warm.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+use crate::config::{Language, NormalizerConfig};
+use crate::error::{Result, WeTextError};
+use crate::normalizer::Normalizer;
+
+type Job = Box<dyn FnOnce(&mut Normalizer) + Send>;
+
+fn stopped() -> WeTextError {
+    WeTextError::WorkerError("worker thread has stopped".to_string())
+}
+
+/// A `Normalizer` owned by a worker thread, fed through a bounded queue
+pub struct NormalizerWorker {
+    sender: Option<SyncSender<Job>>,
+    handle: Option<JoinHandle<Normalizer>>,
+}
+
+impl NormalizerWorker {
+    /// Move `normalizer` onto a new thread
+    ///
+    /// # Arguments
+    /// * `normalizer` - Normalizer to run jobs with
+    /// * `capacity` - Number of jobs that can wait in the queue
+    pub fn new(normalizer: Normalizer, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<Job>(capacity);
+        let handle = thread::spawn(move || {
+            let mut normalizer = normalizer;
+            for job in receiver {
+                job(&mut normalizer);
+            }
+            normalizer
+        });
+        Self {
+            sender: Some(sender),
+            handle: Some(handle),
+        }
+    }
+
+    /// Queue a normalization, blocking while the queue is full
+    ///
+    /// # Returns
+    /// A receiver that yields the result once the worker has run the job
+    pub fn submit(&self, text: &str) -> Result<Receiver<Result<String>>> {
+        let text = text.to_string();
+        self.submit_with(move |normalizer| normalizer.normalize(&text))
+    }
+
+    /// Queue a normalization without blocking
+    ///
+    /// # Returns
+    /// `None` if the queue is full
+    pub fn try_submit(&self, text: &str) -> Result<Option<Receiver<Result<String>>>> {
+        let text = text.to_string();
+        let (job, receiver) = Self::job(move |normalizer| normalizer.normalize(&text));
+        match self.sender()?.try_send(job) {
+            Ok(()) => Ok(Some(receiver)),
+            Err(TrySendError::Full(_)) => Ok(None),
+            Err(TrySendError::Disconnected(_)) => Err(stopped()),
+        }
+    }
+
+    /// Queue an arbitrary job, blocking while the queue is full
+    ///
+    /// Useful for the other `Normalizer` methods, e.g.
+    /// `worker.submit_with(|n| n.normalize_with_report(&text))`.
+    pub fn submit_with<T, F>(&self, f: F) -> Result<Receiver<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Normalizer) -> T + Send + 'static,
+    {
+        let (job, receiver) = Self::job(f);
+        self.sender()?.send(job).map_err(|_| stopped())?;
+        Ok(receiver)
+    }
+
+    /// Normalize text on the worker and wait for the result
+    pub fn normalize(&self, text: &str) -> Result<String> {
+        self.submit(text)?.recv().map_err(|_| stopped())?
+    }
+
+    /// Finish the queued jobs, stop the thread and return the normalizer
+    pub fn shutdown(mut self) -> Result<Normalizer> {
+        self.sender = None;
+        let handle = self.handle.take().ok_or_else(stopped)?;
+        handle
+            .join()
+            .map_err(|_| WeTextError::WorkerError("worker thread panicked".to_string()))
+    }
+
+    fn sender(&self) -> Result<&SyncSender<Job>> {
+        self.sender.as_ref().ok_or_else(stopped)
+    }
+
+    /// Wrap `f` in a job that sends its result to the returned receiver
+    fn job<T, F>(f: F) -> (Job, Receiver<T>)
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Normalizer) -> T + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let job: Job = Box::new(move |normalizer| {
+            // The caller may have dropped the receiver
+            let _ = sender.send(f(normalizer));
+        });
+        (job, receiver)
+    }
+}
+
+impl Drop for NormalizerWorker {
+    fn drop(&mut self) {
+        self.sender = None;
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Workers of one language, used in turn
+struct Shard {
+    lang: Language,
+    workers: Vec<NormalizerWorker>,
+    next: AtomicUsize,
+}
+
+/// Normalizers sharded by language, each on its own worker threads
+///
+/// Shares by reference between threads: submitting only needs `&self`.
+///
+/// # Example
+/// ```ignore
+/// // Two threads each for zh, en and ja, each with up to 64 queued jobs
+/// let sharded = ShardedNormalizer::new("path/to/fsts", NormalizerConfig::new(), 2, 64);
+/// let text = sharded.normalize("2024年")?;
+/// ```
+pub struct ShardedNormalizer {
+    config: NormalizerConfig,
+    shards: Vec<Shard>,
+}
+
+impl ShardedNormalizer {
+    /// Start `threads` workers for each of zh, en and ja
+    ///
+    /// # Arguments
+    /// * `fst_dir` - FST directory shared by all workers
+    /// * `config` - Configuration of every worker, with its language set
+    ///   to the shard's
+    /// * `threads` - Number of workers per language (at least one)
+    /// * `capacity` - Number of jobs that can wait in each worker's queue
+    pub fn new<P: AsRef<Path>>(
+        fst_dir: P,
+        config: NormalizerConfig,
+        threads: usize,
+        capacity: usize,
+    ) -> Self {
+        let shards = [Language::Zh, Language::En, Language::Ja]
+            .into_iter()
+            .map(|lang| {
+                let workers = (0..threads.max(1))
+                    .map(|_| {
+                        let shard_config = config.clone().with_lang(lang);
+                        NormalizerWorker::new(Normalizer::new(&fst_dir, shard_config), capacity)
+                    })
+                    .collect();
+                Shard {
+                    lang,
+                    workers,
+                    next: AtomicUsize::new(0),
+                }
+            })
+            .collect();
+        Self { config, shards }
+    }
+
+    /// Language whose workers normalize `text`
+    ///
+    /// The configured language, or with `Language::Auto` the language
+    /// detected from the raw text.
+    pub fn language(&self, text: &str) -> Language {
+        match self.config.lang {
+            Language::Auto => Normalizer::detect_language(text, &self.config),
+            lang => lang,
+        }
+    }
+
+    /// Queue a normalization on a worker of the text's language, blocking
+    /// while its queue is full
+    pub fn submit(&self, text: &str) -> Result<Receiver<Result<String>>> {
+        self.worker(self.language(text))?.submit(text)
+    }
+
+    /// Queue an arbitrary job on a worker of `lang`
+    pub fn submit_with<T, F>(&self, lang: Language, f: F) -> Result<Receiver<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Normalizer) -> T + Send + 'static,
+    {
+        self.worker(lang)?.submit_with(f)
+    }
+
+    /// Normalize text on a worker of its language and wait for the result
+    pub fn normalize(&self, text: &str) -> Result<String> {
+        self.worker(self.language(text))?.normalize(text)
+    }
+
+    /// Finish the queued jobs, stop every thread and return the normalizers
+    /// with their languages
+    pub fn shutdown(self) -> Result<Vec<(Language, Normalizer)>> {
+        let mut normalizers = Vec::new();
+        for shard in self.shards {
+            for worker in shard.workers {
+                normalizers.push((shard.lang, worker.shutdown()?));
+            }
+        }
+        Ok(normalizers)
+    }
+
+    /// Next worker of `lang`, round robin
+    fn worker(&self, lang: Language) -> Result<&NormalizerWorker> {
+        let shard = self
+            .shards
+            .iter()
+            .find(|shard| shard.lang == lang)
+            .ok_or_else(|| WeTextError::InvalidLanguage(format!("no shard for {:?}", lang)))?;
+        let index = shard.next.fetch_add(1, Ordering::Relaxed) % shard.workers.len();
+        Ok(&shard.workers[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worker() {
+        // Text without digits passes through TN without loading any FST
+        let worker = NormalizerWorker::new(Normalizer::with_defaults("missing"), 2);
+        assert_eq!(worker.normalize("你好").unwrap(), "你好");
+
+        let receivers: Vec<_> = ["一", "二", "三"]
+            .iter()
+            .map(|text| worker.submit(text).unwrap())
+            .collect();
+        let outputs: Vec<String> = receivers
+            .into_iter()
+            .map(|receiver| receiver.recv().unwrap().unwrap())
+            .collect();
+        assert_eq!(outputs, ["一", "二", "三"]);
+
+        let output = worker
+            .submit_with(|normalizer| {
+                normalizer.normalize_with_config("hello", &NormalizerConfig::new())
+            })
+            .unwrap()
+            .recv()
+            .unwrap();
+        assert_eq!(output.unwrap(), "hello");
+
+        // Missing FSTs surface as normal errors
+        assert!(worker.normalize("1").is_err());
+
+        let normalizer = worker.shutdown().unwrap();
+        assert!(normalizer.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_try_submit_full() {
+        let worker = NormalizerWorker::new(
+            Normalizer::new("missing", NormalizerConfig::new().with_lang(Language::Zh)),
+            1,
+        );
+        // Hold the worker busy until released
+        let (release, wait) = mpsc::channel::<()>();
+        let busy = worker
+            .submit_with(move |_| {
+                let _ = wait.recv();
+            })
+            .unwrap();
+        let mut queued = Vec::new();
+        let mut full = false;
+        for _ in 0..3 {
+            match worker.try_submit("你好").unwrap() {
+                Some(receiver) => queued.push(receiver),
+                None => full = true,
+            }
+        }
+        assert!(full);
+        release.send(()).unwrap();
+        busy.recv().unwrap();
+        for receiver in queued {
+            assert_eq!(receiver.recv().unwrap().unwrap(), "你好");
+        }
+    }
+
+    #[test]
+    fn test_sharded() {
+        let sharded = ShardedNormalizer::new("missing", NormalizerConfig::new(), 2, 4);
+        assert_eq!(sharded.language("你好"), Language::Zh);
+        assert_eq!(sharded.language("hello"), Language::En);
+        assert_eq!(sharded.language("こんにちは"), Language::Ja);
+        for text in ["你好", "hello", "こんにちは"] {
+            assert_eq!(sharded.normalize(text).unwrap(), text);
+        }
+        // Missing FSTs surface as normal errors
+        assert!(sharded.normalize("1").is_err());
+
+        let languages: Vec<Language> = sharded
+            .shutdown()
+            .unwrap()
+            .into_iter()
+            .map(|(lang, _)| lang)
+            .collect();
+        assert_eq!(
+            languages,
+            [
+                Language::Zh,
+                Language::Zh,
+                Language::En,
+                Language::En,
+                Language::Ja,
+                Language::Ja
+            ]
+        );
+
+        let sharded = ShardedNormalizer::new(
+            "missing",
+            NormalizerConfig::new().with_lang(Language::Ja),
+            1,
+            1,
+        );
+        assert_eq!(sharded.language("hello"), Language::Ja);
+    }
+}
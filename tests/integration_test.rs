@@ -156,3 +156,27 @@ fn test_contractions_fix() {
         result
     );
 }
+
+#[test]
+fn test_hybrid_phone_rule() {
+    let config = NormalizerConfig::new()
+        .with_lang(Language::Zh)
+        .with_rule_class(EntityClass::Phone);
+    let mut normalizer = Normalizer::new(FST_DIR, config);
+
+    // The whole input is handled by the built-in phone rule, no FST is needed
+    let result = normalizer.normalize("13800138000").unwrap();
+    assert_eq!(result, "幺三八零零幺三八零零零");
+}
+
+#[test]
+fn test_pipeline_stages() {
+    let config = NormalizerConfig::new().with_lang(Language::Zh);
+    let mut normalizer = Normalizer::new(FST_DIR, config);
+
+    // Running the stages by hand matches the full pipeline
+    let tagged = normalizer.tag("2024年").unwrap();
+    let reordered = normalizer.reorder(&tagged).unwrap();
+    let spoken = normalizer.verbalize(&reordered).unwrap();
+    assert_eq!(spoken, normalizer.normalize("2024年").unwrap());
+}